@@ -0,0 +1,467 @@
+//! Opt-in AI moderation stage.
+//!
+//! Sampled messages are classified by the LLM in the background. A message the
+//! model flags above the guild's confidence threshold becomes a pending
+//! `review` case posted to the modlog with Approve / Dismiss buttons; nothing
+//! is acted on until a moderator decides.
+
+use poise::serenity_prelude as serenity;
+use tokio::time::Instant;
+use tracing::{error, warn};
+
+use autumn_core::Data;
+use autumn_database::impls::ai_moderation::{ai_moderation_within_rate, get_ai_moderation_config};
+use autumn_database::impls::cases::{NewCase, create_case, transition_case_status};
+use autumn_database::impls::modlog_config::get_modlog_channel_id;
+use autumn_llm::classify::Classification;
+use autumn_llm::usage::{UsageSource, record_usage};
+use autumn_utils::embed::DEFAULT_EMBED_COLOR;
+use autumn_utils::formatting::{format_case_label, parse_case_label};
+use autumn_utils::permissions::has_user_permission;
+
+const REVIEW_CUSTOM_ID_PREFIX: &str = "ai_review";
+
+/// Characters of the flagged message quoted in the modlog post.
+const EXCERPT_CHARS: usize = 300;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ReviewDecision {
+    Approve,
+    Dismiss,
+}
+
+impl ReviewDecision {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Approve => "approve",
+            Self::Dismiss => "dismiss",
+        }
+    }
+
+    fn case_status(self) -> &'static str {
+        match self {
+            Self::Approve => "approved",
+            Self::Dismiss => "dismissed",
+        }
+    }
+
+    fn display_name(self) -> &'static str {
+        match self {
+            Self::Approve => "Approved",
+            Self::Dismiss => "Dismissed",
+        }
+    }
+}
+
+/// What a review button refers to, round-tripped through its custom id.
+#[derive(Debug, PartialEq, Eq)]
+struct ReviewButton {
+    decision: ReviewDecision,
+    case_label: String,
+    channel_id: u64,
+    message_id: u64,
+}
+
+impl ReviewButton {
+    fn custom_id(&self) -> String {
+        format!(
+            "{}:{}:{}:{}:{}",
+            REVIEW_CUSTOM_ID_PREFIX,
+            self.decision.as_str(),
+            self.case_label,
+            self.channel_id,
+            self.message_id
+        )
+    }
+
+    fn parse(custom_id: &str) -> Option<Self> {
+        let mut parts = custom_id.split(':');
+        if parts.next()? != REVIEW_CUSTOM_ID_PREFIX {
+            return None;
+        }
+
+        let decision = match parts.next()? {
+            "approve" => ReviewDecision::Approve,
+            "dismiss" => ReviewDecision::Dismiss,
+            _ => return None,
+        };
+        let case_label = parts.next()?.to_owned();
+        let channel_id = parts.next()?.parse().ok()?;
+        let message_id = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(Self {
+            decision,
+            case_label,
+            channel_id,
+            message_id,
+        })
+    }
+}
+
+/// Queue a message for AI classification when the guild has AI moderation
+/// enabled and a modlog channel to review in, the message falls in the
+/// sample, and the guild is under its per-minute cap. Classification runs in
+/// the background.
+pub async fn handle_message_ai_moderation(
+    ctx: &serenity::Context,
+    data: &Data,
+    message: &serenity::Message,
+) {
+    // Ignore bots and webhooks.
+    if message.author.bot || message.webhook_id.is_some() {
+        return;
+    }
+
+    let Some(guild_id) = message.guild_id else {
+        return;
+    };
+
+    let Some(llm) = data.llm.clone() else {
+        return;
+    };
+
+    if message.content.trim().is_empty() {
+        return;
+    }
+
+    let config = match get_ai_moderation_config(&data.db, guild_id.get()).await {
+        Ok(config) => config,
+        Err(source) => {
+            error!(?source, "failed to read ai moderation config");
+            return;
+        }
+    };
+
+    if !config.enabled || !is_sampled(message.id.get(), config.sample_percent) {
+        return;
+    }
+
+    // Flags are reviewed from the modlog channel; without one a flag could
+    // never be resolved, so don't spend a classification on it.
+    let modlog_channel_id = match get_modlog_channel_id(&data.db, guild_id.get()).await {
+        Ok(Some(id)) => serenity::ChannelId::new(id),
+        Ok(None) => return,
+        Err(source) => {
+            error!(?source, "failed to read modlog channel for ai review");
+            return;
+        }
+    };
+
+    match ai_moderation_within_rate(&data.db, guild_id.get(), config.max_per_minute).await {
+        Ok(true) => {}
+        Ok(false) => return,
+        Err(source) => {
+            error!(?source, "failed to check ai moderation rate cap");
+            return;
+        }
+    }
+
+    let ctx = ctx.clone();
+    let data = data.clone();
+    let message = message.clone();
+    tokio::spawn(async move {
+        let usage_source = UsageSource {
+            guild_id: guild_id.get(),
+            channel_id: message.channel_id.get(),
+            user_id: ctx.cache.current_user().id.get(),
+            kind: "classify",
+        };
+        let started = Instant::now();
+        let classified = llm.classify_message(&message.content).await;
+        record_usage(
+            &data.db,
+            usage_source,
+            started.elapsed(),
+            classified.as_ref().ok().map(|(_, usage)| *usage),
+        )
+        .await;
+
+        let classification = match classified {
+            Ok((classification, _)) => classification,
+            Err(source) => {
+                warn!(?source, "ai moderation classification failed");
+                return;
+            }
+        };
+
+        if !classification.is_flagged(config.threshold) {
+            return;
+        }
+
+        flag_for_review(
+            &ctx,
+            &data,
+            &message,
+            guild_id,
+            modlog_channel_id,
+            &classification,
+        )
+        .await;
+    });
+}
+
+/// Handle an Approve / Dismiss click on an AI review post.
+pub async fn handle_review_interaction(
+    ctx: &serenity::Context,
+    data: &Data,
+    interaction: &serenity::Interaction,
+) {
+    let serenity::Interaction::Component(component) = interaction else {
+        return;
+    };
+
+    let Some(button) = ReviewButton::parse(&component.data.custom_id) else {
+        return;
+    };
+
+    let Some(guild_id) = component.guild_id else {
+        return;
+    };
+
+    let allowed = match has_user_permission(
+        ctx,
+        data.db.cache(),
+        guild_id,
+        component.user.id,
+        serenity::Permissions::MANAGE_MESSAGES,
+    )
+    .await
+    {
+        Ok(allowed) => allowed,
+        Err(source) => {
+            error!(?source, "failed to check permissions for ai review");
+            false
+        }
+    };
+
+    if !allowed {
+        respond_ephemeral(
+            ctx,
+            component,
+            "You need Manage Messages to review flagged messages.",
+        )
+        .await;
+        return;
+    }
+
+    let Some((case_code, action_case_number)) = parse_case_label(&button.case_label) else {
+        return;
+    };
+
+    let case = match transition_case_status(
+        &data.db,
+        guild_id.get(),
+        &case_code,
+        action_case_number,
+        component.user.id.get(),
+        "pending",
+        button.decision.case_status(),
+    )
+    .await
+    {
+        Ok(case) => case,
+        Err(source) => {
+            error!(?source, "failed to update ai review case");
+            return;
+        }
+    };
+
+    if case.is_none() {
+        respond_ephemeral(ctx, component, "This case has already been reviewed.").await;
+        return;
+    }
+
+    if button.decision == ReviewDecision::Approve {
+        data.suppressed_deletes
+            .write()
+            .await
+            .insert(button.message_id);
+
+        if let Err(source) = serenity::ChannelId::new(button.channel_id)
+            .delete_message(&ctx.http, serenity::MessageId::new(button.message_id))
+            .await
+        {
+            // The author may have deleted it already; the case still records
+            // the decision.
+            warn!(?source, "failed to delete message approved by ai review");
+        }
+    }
+
+    let reviewed_line = format!(
+        "**Reviewed :** {} by <@{}>",
+        button.decision.display_name(),
+        component.user.id
+    );
+    let embed = match component.message.embeds.first() {
+        Some(original) => {
+            let description = original.description.clone().unwrap_or_default();
+            serenity::CreateEmbed::from(original.clone())
+                .description(format!("{}\n{}", description, reviewed_line))
+        }
+        None => serenity::CreateEmbed::new()
+            .color(DEFAULT_EMBED_COLOR)
+            .description(reviewed_line),
+    };
+
+    let response = serenity::CreateInteractionResponse::UpdateMessage(
+        serenity::CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .components(Vec::new()),
+    );
+    if let Err(source) = component.create_response(&ctx.http, response).await {
+        error!(?source, "failed to update ai review post");
+    }
+}
+
+/// Record a pending `review` case for a flagged message and post it to the
+/// modlog channel with review buttons.
+async fn flag_for_review(
+    ctx: &serenity::Context,
+    data: &Data,
+    message: &serenity::Message,
+    guild_id: serenity::GuildId,
+    modlog_channel_id: serenity::ChannelId,
+    classification: &Classification,
+) {
+    let Some(category) = classification.category else {
+        return;
+    };
+
+    let confidence_percent = (classification.confidence * 100.0).round() as u32;
+    let mut reason = format!(
+        "AI flagged: {} ({}%)",
+        category.display_name(),
+        confidence_percent
+    );
+    if !classification.reason.is_empty() {
+        reason.push_str(" - ");
+        reason.push_str(&classification.reason);
+    }
+
+    let new_case = NewCase {
+        guild_id: guild_id.get(),
+        target_user_id: Some(message.author.id.get()),
+        moderator_user_id: ctx.cache.current_user().id.get(),
+        action: "review",
+        reason: &reason,
+        status: "pending",
+        duration_seconds: None,
+    };
+
+    let case = match create_case(&data.db, new_case).await {
+        Ok(case) => case,
+        Err(source) => {
+            error!(?source, "failed to create ai review case");
+            return;
+        }
+    };
+
+    let case_label = format_case_label(&case.case_code, case.action_case_number);
+
+    let mut excerpt: String = message.content.chars().take(EXCERPT_CHARS).collect();
+    if message.content.chars().count() > EXCERPT_CHARS {
+        excerpt.push('…');
+    }
+
+    let mut fields = vec![
+        format!("**User :** <@{}>", message.author.id),
+        format!("**Channel :** <#{}>", message.channel_id),
+        format!("**Category :** {}", category.display_name()),
+        format!("**Confidence :** {}%", confidence_percent),
+    ];
+    if !classification.reason.is_empty() {
+        fields.push(format!("**Reason :** {}", classification.reason));
+    }
+    fields.push(format!(
+        "**Message :** [Jump]({})\n>>> {}",
+        message.link(),
+        excerpt
+    ));
+    fields.push(String::new());
+    fields.push(format!("**When :** <t:{}:R>", case.created_at));
+
+    let embed = serenity::CreateEmbed::new()
+        .color(DEFAULT_EMBED_COLOR)
+        .title(format!("AI Review - #{}", case_label))
+        .description(fields.join("\n"));
+
+    let button = |decision| ReviewButton {
+        decision,
+        case_label: case_label.clone(),
+        channel_id: message.channel_id.get(),
+        message_id: message.id.get(),
+    };
+    let buttons = serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new(button(ReviewDecision::Approve).custom_id())
+            .label("Approve & Delete")
+            .style(serenity::ButtonStyle::Danger),
+        serenity::CreateButton::new(button(ReviewDecision::Dismiss).custom_id())
+            .label("Dismiss")
+            .style(serenity::ButtonStyle::Secondary),
+    ]);
+
+    if let Err(source) = modlog_channel_id
+        .send_message(
+            &ctx.http,
+            serenity::CreateMessage::new()
+                .embed(embed)
+                .components(vec![buttons]),
+        )
+        .await
+    {
+        error!(?source, "failed to publish ai review to modlog channel");
+    }
+}
+
+async fn respond_ephemeral(
+    ctx: &serenity::Context,
+    component: &serenity::ComponentInteraction,
+    content: &str,
+) {
+    let response = serenity::CreateInteractionResponse::Message(
+        serenity::CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    );
+    if let Err(source) = component.create_response(&ctx.http, response).await {
+        error!(?source, "failed to respond to ai review interaction");
+    }
+}
+
+/// Deterministic sampling so a message is either always or never picked,
+/// spread evenly even though snowflake low bits are sequential.
+fn is_sampled(message_id: u64, sample_percent: u8) -> bool {
+    let mixed = (message_id ^ (message_id >> 33)).wrapping_mul(0xff51_afd7_ed55_8ccd);
+    (mixed ^ (mixed >> 33)) % 100 < u64::from(sample_percent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn review_button_custom_id_round_trips() {
+        let button = ReviewButton {
+            decision: ReviewDecision::Dismiss,
+            case_label: "RV12".to_owned(),
+            channel_id: 123,
+            message_id: 456,
+        };
+
+        assert_eq!(ReviewButton::parse(&button.custom_id()), Some(button));
+        assert_eq!(ReviewButton::parse("ai_review:ban:RV1:1:2"), None);
+        assert_eq!(ReviewButton::parse("other:approve:RV1:1:2"), None);
+    }
+
+    #[test]
+    fn sampling_respects_bounds() {
+        assert!((0..1000).all(|id| is_sampled(id, 100)));
+        assert!((0..1000).all(|id| !is_sampled(id, 0)));
+
+        let sampled = (0..10_000u64).filter(|id| is_sampled(*id, 25)).count();
+        assert!((2000..3000).contains(&sampled), "sampled {sampled}");
+    }
+}
@@ -0,0 +1,353 @@
+//! Anti-nuke protection.
+//!
+//! Watches audit-log entries for bursts of destructive actions (bans, kicks,
+//! channel and role deletions, permission changes) by a single actor. An
+//! actor who crosses the guild's threshold has every role granting dangerous
+//! permissions removed, the owner is DMed, the modlog is alerted and an
+//! `anti_nuke` case is recorded. Trusted users and the owner are exempt.
+
+use poise::serenity_prelude as serenity;
+use tracing::{error, warn};
+
+use autumn_core::Data;
+use autumn_database::impls::anti_nuke::{get_anti_nuke_config, record_anti_nuke_action};
+use autumn_database::impls::cases::{NewCase, create_case};
+use autumn_database::impls::modlog_config::get_modlog_channel_id;
+use autumn_database::model::anti_nuke::AntiNukeConfig;
+use autumn_utils::embed::DEFAULT_EMBED_COLOR;
+use autumn_utils::formatting::format_case_label;
+
+/// Roles granting any of these are removed from an offending actor.
+const DANGEROUS_PERMISSIONS: serenity::Permissions = serenity::Permissions::ADMINISTRATOR
+    .union(serenity::Permissions::BAN_MEMBERS)
+    .union(serenity::Permissions::KICK_MEMBERS)
+    .union(serenity::Permissions::MANAGE_CHANNELS)
+    .union(serenity::Permissions::MANAGE_ROLES)
+    .union(serenity::Permissions::MANAGE_GUILD)
+    .union(serenity::Permissions::MANAGE_WEBHOOKS);
+
+const AUDIT_LOG_REASON: &str = "Anti-nuke: too many destructive actions";
+
+/// The actor who crossed the threshold and the action that tipped them over.
+#[derive(Clone, Copy)]
+struct Trigger<'a> {
+    actor_id: serenity::UserId,
+    action_name: &'static str,
+    config: &'a AntiNukeConfig,
+}
+
+/// Roles removed from the actor, and those that couldn't be (managed or
+/// above the bot's highest role).
+struct StrippedRoles {
+    removed: Vec<serenity::RoleId>,
+    failed: Vec<serenity::RoleId>,
+}
+
+/// Count a new audit-log entry against its actor and respond if it pushes
+/// them over the guild's anti-nuke threshold.
+pub async fn handle_audit_log_entry(
+    ctx: &serenity::Context,
+    data: &Data,
+    entry: &serenity::AuditLogEntry,
+    guild_id: serenity::GuildId,
+) {
+    let Some(action_name) = destructive_action_name(entry.action) else {
+        return;
+    };
+
+    let actor_id = entry.user_id;
+    // Our own bans, and our own role removals below, must not count.
+    if actor_id == ctx.cache.current_user().id {
+        return;
+    }
+
+    let config = match get_anti_nuke_config(&data.db, guild_id.get()).await {
+        Ok(config) => config,
+        Err(source) => {
+            error!(?source, "failed to read anti-nuke config");
+            return;
+        }
+    };
+
+    if !config.enabled || config.is_trusted(actor_id.get()) {
+        return;
+    }
+
+    let owner_id = match guild_owner_id(ctx, guild_id).await {
+        Some(owner_id) => owner_id,
+        None => return,
+    };
+    // The owner can't be stripped of anything.
+    if actor_id == owner_id {
+        return;
+    }
+
+    match record_anti_nuke_action(&data.db, &config, actor_id.get()).await {
+        Ok(true) => {}
+        Ok(false) => return,
+        Err(source) => {
+            error!(?source, "failed to record anti-nuke action");
+            return;
+        }
+    }
+
+    warn!(
+        guild_id = guild_id.get(),
+        actor_id = actor_id.get(),
+        "anti-nuke threshold crossed"
+    );
+
+    let stripped = strip_dangerous_roles(ctx, guild_id, actor_id).await;
+    let trigger = Trigger {
+        actor_id,
+        action_name,
+        config: &config,
+    };
+    respond(ctx, data, guild_id, owner_id, &trigger, &stripped).await;
+}
+
+/// Human-readable name for audit-log actions anti-nuke counts.
+fn destructive_action_name(action: serenity::audit_log::Action) -> Option<&'static str> {
+    use serenity::audit_log::{
+        Action, ChannelAction, ChannelOverwriteAction, MemberAction, RoleAction,
+    };
+
+    match action {
+        Action::Member(MemberAction::BanAdd) => Some("ban"),
+        Action::Member(MemberAction::Kick) => Some("kick"),
+        Action::Member(MemberAction::Prune) => Some("member prune"),
+        Action::Member(MemberAction::RoleUpdate) => Some("member role change"),
+        Action::Channel(ChannelAction::Delete) => Some("channel deletion"),
+        Action::Role(RoleAction::Delete) => Some("role deletion"),
+        Action::Role(RoleAction::Update) => Some("role permission change"),
+        Action::ChannelOverwrite(
+            ChannelOverwriteAction::Create
+            | ChannelOverwriteAction::Update
+            | ChannelOverwriteAction::Delete,
+        ) => Some("channel permission change"),
+        _ => None,
+    }
+}
+
+async fn guild_owner_id(
+    ctx: &serenity::Context,
+    guild_id: serenity::GuildId,
+) -> Option<serenity::UserId> {
+    if let Some(guild) = ctx.cache.guild(guild_id) {
+        return Some(guild.owner_id);
+    }
+
+    match guild_id.to_partial_guild(&ctx.http).await {
+        Ok(guild) => Some(guild.owner_id),
+        Err(source) => {
+            error!(?source, "failed to look up guild owner for anti-nuke");
+            None
+        }
+    }
+}
+
+/// Remove every role of the actor that grants a dangerous permission. Roles
+/// are removed one at a time so one the bot can't manage doesn't stop the rest.
+async fn strip_dangerous_roles(
+    ctx: &serenity::Context,
+    guild_id: serenity::GuildId,
+    actor_id: serenity::UserId,
+) -> StrippedRoles {
+    let mut stripped = StrippedRoles {
+        removed: Vec::new(),
+        failed: Vec::new(),
+    };
+
+    let member = match guild_id.member(ctx, actor_id).await {
+        Ok(member) => member,
+        Err(source) => {
+            error!(?source, "failed to fetch member for anti-nuke");
+            return stripped;
+        }
+    };
+
+    let roles = match guild_id.roles(&ctx.http).await {
+        Ok(roles) => roles,
+        Err(source) => {
+            error!(?source, "failed to fetch roles for anti-nuke");
+            return stripped;
+        }
+    };
+
+    for role_id in &member.roles {
+        let Some(role) = roles.get(role_id) else {
+            continue;
+        };
+        if !role.permissions.intersects(DANGEROUS_PERMISSIONS) {
+            continue;
+        }
+
+        if role.managed {
+            stripped.failed.push(*role_id);
+            continue;
+        }
+
+        match ctx
+            .http
+            .remove_member_role(guild_id, actor_id, *role_id, Some(AUDIT_LOG_REASON))
+            .await
+        {
+            Ok(()) => stripped.removed.push(*role_id),
+            Err(source) => {
+                warn!(?source, "failed to remove role for anti-nuke");
+                stripped.failed.push(*role_id);
+            }
+        }
+    }
+
+    stripped
+}
+
+/// Record the `anti_nuke` case, then alert the modlog channel and the owner.
+async fn respond(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    owner_id: serenity::UserId,
+    trigger: &Trigger<'_>,
+    stripped: &StrippedRoles,
+) {
+    let Trigger {
+        actor_id,
+        action_name,
+        config,
+    } = *trigger;
+    let reason = format!(
+        "Anti-nuke: more than {} destructive actions within {}s; removed {} role(s)",
+        config.max_actions,
+        config.window_seconds,
+        stripped.removed.len()
+    );
+
+    let bot_id = ctx.cache.current_user().id;
+    let case = match create_case(
+        &data.db,
+        NewCase {
+            guild_id: guild_id.get(),
+            target_user_id: Some(actor_id.get()),
+            moderator_user_id: bot_id.get(),
+            action: "anti_nuke",
+            reason: &reason,
+            status: "active",
+            duration_seconds: None,
+        },
+    )
+    .await
+    {
+        Ok(case) => Some(case),
+        Err(source) => {
+            error!(?source, "failed to create anti-nuke case");
+            None
+        }
+    };
+
+    let role_list = |roles: &[serenity::RoleId]| {
+        if roles.is_empty() {
+            "None".to_owned()
+        } else {
+            roles
+                .iter()
+                .map(|id| format!("<@&{}>", id))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+    };
+
+    let mut fields = vec![
+        format!("**User :** <@{}>", actor_id),
+        format!(
+            "**Trigger :** more than {} destructive actions within {}s",
+            config.max_actions, config.window_seconds
+        ),
+        format!("**Last Action :** {}", action_name),
+        format!("**Roles Removed :** {}", role_list(&stripped.removed)),
+    ];
+    if !stripped.failed.is_empty() {
+        fields.push(format!(
+            "**Could Not Remove :** {}",
+            role_list(&stripped.failed)
+        ));
+    }
+    fields.push(String::new());
+    fields.push("Review the audit log and restore the roles if this was legitimate.".to_owned());
+
+    let title = match &case {
+        Some(case) => format!(
+            "Anti-Nuke - #{}",
+            format_case_label(&case.case_code, case.action_case_number)
+        ),
+        None => "Anti-Nuke".to_owned(),
+    };
+    let embed = serenity::CreateEmbed::new()
+        .color(DEFAULT_EMBED_COLOR)
+        .title(title)
+        .description(fields.join("\n"));
+
+    match get_modlog_channel_id(&data.db, guild_id.get()).await {
+        Ok(Some(channel_id)) => {
+            if let Err(source) = serenity::ChannelId::new(channel_id)
+                .send_message(
+                    &ctx.http,
+                    serenity::CreateMessage::new().embed(embed.clone()),
+                )
+                .await
+            {
+                error!(?source, "failed to post anti-nuke alert to modlog channel");
+            }
+        }
+        Ok(None) => {}
+        Err(source) => error!(?source, "failed to read modlog channel for anti-nuke"),
+    }
+
+    let guild_name = ctx
+        .cache
+        .guild(guild_id)
+        .map(|guild| guild.name.clone())
+        .unwrap_or_else(|| guild_id.to_string());
+    let dm = async {
+        owner_id
+            .create_dm_channel(&ctx.http)
+            .await?
+            .send_message(
+                &ctx.http,
+                serenity::CreateMessage::new()
+                    .content(format!("Anti-nuke was triggered in **{}**.", guild_name))
+                    .embed(embed),
+            )
+            .await
+    };
+    if let Err(source) = dm.await {
+        error!(?source, "failed to DM guild owner anti-nuke alert");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use poise::serenity_prelude::audit_log::{Action, ChannelAction, MemberAction};
+
+    #[test]
+    fn only_destructive_actions_are_counted() {
+        assert_eq!(
+            destructive_action_name(Action::Member(MemberAction::BanAdd)),
+            Some("ban")
+        );
+        assert_eq!(
+            destructive_action_name(Action::Channel(ChannelAction::Delete)),
+            Some("channel deletion")
+        );
+        assert_eq!(
+            destructive_action_name(Action::Channel(ChannelAction::Create)),
+            None
+        );
+        assert_eq!(
+            destructive_action_name(Action::Member(MemberAction::BanRemove)),
+            None
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use poise::serenity_prelude as serenity;
+use tracing::error;
+
+use autumn_core::Data;
+use autumn_database::impls::attachment_filter::{
+    get_attachment_channel_rule, get_attachment_filter_if_enabled, list_filter_extensions,
+};
+use autumn_database::model::attachment_filter::{AttachmentChannelRule, AttachmentFilterConfig};
+use autumn_utils::formatting::format_size_bytes;
+
+use super::filter_action::{FilterKind, FilterOutcome, enforce_filter_action};
+use super::userlog::media::{MediaKind, classify_media_filename};
+
+/// Check an incoming message's attachments against the guild's attachment
+/// policy and execute the configured action when one is not allowed.
+pub async fn handle_message_attachment_filter(
+    ctx: &serenity::Context,
+    data: &Data,
+    message: &serenity::Message,
+) -> FilterOutcome {
+    // Ignore bots, webhooks, and messages without attachments.
+    if message.author.bot || message.webhook_id.is_some() || message.attachments.is_empty() {
+        return FilterOutcome::Clean;
+    }
+
+    let Some(guild_id) = message.guild_id else {
+        return FilterOutcome::Clean;
+    };
+
+    let config = match get_attachment_filter_if_enabled(&data.db, guild_id.get()).await {
+        Ok(Some(cfg)) => cfg,
+        Ok(None) => return FilterOutcome::Clean,
+        Err(source) => {
+            error!(?source, "failed to read attachment filter config");
+            return FilterOutcome::Clean;
+        }
+    };
+
+    let extensions = match list_filter_extensions(&data.db, guild_id.get()).await {
+        Ok(extensions) => extensions,
+        Err(source) => {
+            error!(?source, "failed to load attachment filter extensions");
+            return FilterOutcome::Clean;
+        }
+    };
+
+    let channel_rule =
+        match get_attachment_channel_rule(&data.db, guild_id.get(), message.channel_id.get()).await
+        {
+            Ok(rule) => rule,
+            Err(source) => {
+                error!(?source, "failed to read attachment filter channel rule");
+                None
+            }
+        };
+
+    let violation = message.attachments.iter().find_map(|attachment| {
+        attachment_violation(
+            &config,
+            &extensions,
+            channel_rule.as_ref(),
+            &attachment.filename,
+            u64::from(attachment.size),
+        )
+    });
+
+    let Some(violation) = violation else {
+        return FilterOutcome::Clean;
+    };
+
+    enforce_filter_action(
+        ctx,
+        data,
+        message,
+        guild_id,
+        FilterKind::Attachment,
+        &config.action,
+        &violation,
+    )
+    .await
+}
+
+/// Describe why a single attachment breaks the policy, or `None` if it is allowed.
+fn attachment_violation(
+    config: &AttachmentFilterConfig,
+    extensions: &[String],
+    channel_rule: Option<&AttachmentChannelRule>,
+    filename: &str,
+    size_bytes: u64,
+) -> Option<String> {
+    let extension = filename
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    let listed = !extension.is_empty() && extensions.contains(&extension);
+
+    let extension_blocked = if config.extension_mode == "allow" {
+        !listed
+    } else {
+        listed
+    };
+
+    if extension_blocked {
+        return Some(if extension.is_empty() {
+            format!("{} (files without an extension are not allowed)", filename)
+        } else {
+            format!("{} (file type `.{}` is not allowed)", filename, extension)
+        });
+    }
+
+    if let Some(max_size) = config.max_size_bytes
+        && size_bytes > max_size
+    {
+        return Some(format!(
+            "{} ({} exceeds the {} limit)",
+            filename,
+            format_size_bytes(size_bytes),
+            format_size_bytes(max_size)
+        ));
+    }
+
+    let rule = channel_rule?;
+    match classify_media_filename(filename) {
+        Some(MediaKind::Image) if !rule.allow_images => Some(format!(
+            "{} (images are not allowed in this channel)",
+            filename
+        )),
+        Some(MediaKind::Video) if !rule.allow_videos => Some(format!(
+            "{} (videos are not allowed in this channel)",
+            filename
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(mode: &str, max_size_bytes: Option<u64>) -> AttachmentFilterConfig {
+        AttachmentFilterConfig {
+            guild_id: 1,
+            enabled: true,
+            action: "delete_and_log".to_owned(),
+            extension_mode: mode.to_owned(),
+            max_size_bytes,
+        }
+    }
+
+    #[test]
+    fn deny_mode_blocks_listed_extensions_only() {
+        let cfg = config("deny", None);
+        let list = vec!["exe".to_owned(), "bat".to_owned()];
+
+        assert!(attachment_violation(&cfg, &list, None, "setup.EXE", 10).is_some());
+        assert!(attachment_violation(&cfg, &list, None, "notes.txt", 10).is_none());
+        assert!(attachment_violation(&cfg, &list, None, "README", 10).is_none());
+    }
+
+    #[test]
+    fn allow_mode_blocks_unlisted_extensions() {
+        let cfg = config("allow", None);
+        let list = vec!["png".to_owned()];
+
+        assert!(attachment_violation(&cfg, &list, None, "cat.png", 10).is_none());
+        assert!(attachment_violation(&cfg, &list, None, "cat.jpg", 10).is_some());
+        assert!(attachment_violation(&cfg, &list, None, "README", 10).is_some());
+    }
+
+    #[test]
+    fn size_limit_and_channel_media_rules_apply() {
+        let cfg = config("deny", Some(1024));
+        let rule = AttachmentChannelRule {
+            channel_id: 5,
+            allow_images: true,
+            allow_videos: false,
+        };
+
+        assert!(attachment_violation(&cfg, &[], None, "big.zip", 2048).is_some());
+        assert!(attachment_violation(&cfg, &[], Some(&rule), "cat.png", 10).is_none());
+        assert!(attachment_violation(&cfg, &[], Some(&rule), "clip.mp4", 10).is_some());
+    }
+}
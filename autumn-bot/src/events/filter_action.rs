@@ -0,0 +1,325 @@
+//! Shared enforcement for message filters.
+//!
+//! The word filter and the attachment filter both end in the same place once a
+//! violation is found: execute the guild's configured action (delete, warn,
+//! timeout, or log only), record a moderation case, and publish it to the
+//! modlog channel.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use poise::serenity_prelude as serenity;
+use tracing::{error, warn};
+
+use autumn_commands::moderation::escalation_check::check_and_escalate;
+use autumn_commands::moderation::{
+    is_missing_permissions_error, send_moderation_target_dm_for_guild,
+};
+use autumn_core::Data;
+use autumn_database::impls::cases::{NewCase, create_case};
+use autumn_database::impls::modlog_config::get_modlog_channel_id;
+use autumn_database::impls::warnings::record_warning;
+use autumn_utils::embed::DEFAULT_EMBED_COLOR;
+use autumn_utils::formatting::{format_case_label, format_compact_duration};
+
+/// Which filter produced a violation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FilterKind {
+    Word,
+    Attachment,
+}
+
+impl FilterKind {
+    fn reason_prefix(self) -> &'static str {
+        match self {
+            Self::Word => "Word filter",
+            Self::Attachment => "Attachment filter",
+        }
+    }
+
+    fn modlog_title(self) -> &'static str {
+        match self {
+            Self::Word => "Word Filter Violation",
+            Self::Attachment => "Attachment Filter Violation",
+        }
+    }
+
+    fn case_action(self, action: &str) -> &'static str {
+        match (self, action) {
+            (Self::Word, "timeout_delete_and_log") => "word_filter_timeout",
+            (Self::Word, "delete_and_log") => "word_filter_delete",
+            (Self::Word, "warn_and_log") => "word_filter_warn",
+            (Self::Word, "automod_block") => "word_filter_automod",
+            (Self::Word, _) => "word_filter_log",
+            (Self::Attachment, "timeout_delete_and_log") => "attachment_filter_timeout",
+            (Self::Attachment, "delete_and_log") => "attachment_filter_delete",
+            (Self::Attachment, "warn_and_log") => "attachment_filter_warn",
+            (Self::Attachment, _) => "attachment_filter_log",
+        }
+    }
+}
+
+/// What a filter did with a message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FilterOutcome {
+    /// Nothing matched.
+    Clean,
+    /// Matched and recorded, but the message is still up.
+    Flagged,
+    Removed,
+}
+
+/// Execute the configured filter action for a violating message, then create a
+/// moderation case and publish it to the modlog channel.
+pub async fn enforce_filter_action(
+    ctx: &serenity::Context,
+    data: &Data,
+    message: &serenity::Message,
+    guild_id: serenity::GuildId,
+    kind: FilterKind,
+    action: &str,
+    violation: &str,
+) -> FilterOutcome {
+    let bot_user_id = ctx.cache.current_user().id.get();
+    let filter_reason = format!("{}: {}", kind.reason_prefix(), violation);
+    let removes_message = matches!(
+        action,
+        "delete_and_log" | "warn_and_log" | "timeout_delete_and_log"
+    );
+
+    // Suppress this message from user-log recording if it will be deleted.
+    if removes_message {
+        let mut suppressed = data.suppressed_deletes.write().await;
+        suppressed.insert(message.id.get());
+    }
+
+    // Execute the configured action.
+    let removed = match action {
+        "delete_and_log" => delete_filtered_message(ctx, message).await,
+        "warn_and_log" => {
+            let removed = delete_filtered_message(ctx, message).await;
+
+            // Issue a warning for the user.
+            if let Err(source) = record_warning(
+                &data.db,
+                guild_id.get(),
+                message.author.id.get(),
+                bot_user_id,
+                &filter_reason,
+            )
+            .await
+            {
+                error!(?source, "failed to record warning for filter violation");
+            }
+
+            // DM the user about the warning.
+            let _ = send_moderation_target_dm_for_guild(
+                &ctx.http,
+                &message.author,
+                guild_id,
+                "warned",
+                Some(&filter_reason),
+                None,
+            )
+            .await;
+
+            // Check for automatic escalation (warn threshold → auto-timeout).
+            check_and_escalate(&ctx.http, &data.db, guild_id, &message.author, bot_user_id).await;
+
+            removed
+        }
+        "timeout_delete_and_log" => {
+            let removed = delete_filtered_message(ctx, message).await;
+
+            // Apply a 5-minute timeout.
+            let timeout_duration = Duration::from_secs(300);
+            let until_system_time = SystemTime::now()
+                .checked_add(timeout_duration)
+                .unwrap_or(SystemTime::now());
+            let until_unix = until_system_time
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()) as i64;
+
+            if let Ok(until) = serenity::Timestamp::from_unix_timestamp(until_unix) {
+                let edit = serenity::EditMember::new().disable_communication_until_datetime(until);
+                if let Err(source) = guild_id
+                    .edit_member(&ctx.http, message.author.id, edit)
+                    .await
+                {
+                    if !is_missing_permissions_error(&source) {
+                        error!(?source, "failed to timeout user for filter violation");
+                    } else {
+                        warn!(
+                            user_id = %message.author.id,
+                            "missing permissions to timeout user for filter violation \
+                             (check role hierarchy)"
+                        );
+                    }
+                }
+            }
+
+            // DM the user about the timeout.
+            let _ = send_moderation_target_dm_for_guild(
+                &ctx.http,
+                &message.author,
+                guild_id,
+                "timed out",
+                Some(&filter_reason),
+                Some("5m"),
+            )
+            .await;
+
+            removed
+        }
+        // "log_only" or anything else — no message action needed.
+        _ => false,
+    };
+
+    // The message is still up, so its deletion mustn't be hidden later.
+    if removes_message && !removed {
+        let mut suppressed = data.suppressed_deletes.write().await;
+        suppressed.remove(&message.id.get());
+    }
+
+    record_filter_case(
+        ctx,
+        data,
+        guild_id,
+        message.author.id,
+        kind,
+        action,
+        violation,
+    )
+    .await;
+
+    if removed {
+        FilterOutcome::Removed
+    } else {
+        FilterOutcome::Flagged
+    }
+}
+
+/// Create a moderation case for a filter violation and publish it to the
+/// modlog channel. Used directly when the violating message never reached the
+/// bot (e.g. it was blocked by Discord AutoMod).
+pub async fn record_filter_case(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    user_id: serenity::UserId,
+    kind: FilterKind,
+    action: &str,
+    violation: &str,
+) {
+    let bot_user_id = ctx.cache.current_user().id.get();
+
+    // Create a moderation case for the violation.
+    let new_case = NewCase {
+        guild_id: guild_id.get(),
+        target_user_id: Some(user_id.get()),
+        moderator_user_id: bot_user_id,
+        action: kind.case_action(action),
+        reason: violation,
+        status: "completed",
+        duration_seconds: if action == "timeout_delete_and_log" {
+            Some(300)
+        } else {
+            None
+        },
+    };
+
+    let case = match create_case(&data.db, new_case).await {
+        Ok(case) => case,
+        Err(source) => {
+            error!(?source, "failed to create filter case");
+            return;
+        }
+    };
+
+    // Publish to modlog channel.
+    if let Err(source) =
+        publish_filter_violation_to_modlog(ctx, data, guild_id, &case, kind, violation, action)
+            .await
+    {
+        error!(?source, "failed to publish filter case to modlog channel");
+    }
+}
+
+/// Returns whether the message was deleted.
+async fn delete_filtered_message(ctx: &serenity::Context, message: &serenity::Message) -> bool {
+    let Err(source) = message.delete(&ctx.http).await else {
+        return true;
+    };
+
+    if !is_missing_permissions_error(&source) {
+        error!(?source, "failed to delete filtered message");
+    } else {
+        warn!("missing permissions to delete filtered message");
+    }
+    false
+}
+
+async fn publish_filter_violation_to_modlog(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    case: &autumn_database::model::cases::CaseSummary,
+    kind: FilterKind,
+    violation: &str,
+    action: &str,
+) -> Result<(), serenity::Error> {
+    let channel_id = match get_modlog_channel_id(&data.db, guild_id.get()).await {
+        Ok(Some(id)) => id,
+        Ok(None) => return Ok(()),
+        Err(source) => {
+            error!(
+                ?source,
+                "failed to read modlog channel for filter violation"
+            );
+            return Ok(());
+        }
+    };
+
+    let case_label = format_case_label(&case.case_code, case.action_case_number);
+
+    let action_label = match action {
+        "timeout_delete_and_log" => "Timeout, Delete & Log",
+        "delete_and_log" => "Delete & Log",
+        "warn_and_log" => "Warn, Delete & Log",
+        "automod_block" => "Blocked by AutoMod",
+        _ => "Log Only",
+    };
+
+    let mut fields = Vec::new();
+    fields.push(format!(
+        "**User :** <@{}>",
+        case.target_user_id.unwrap_or(0)
+    ));
+    fields.push(format!("**Violation :** {}", violation));
+    fields.push(format!("**Action Taken :** {}", action_label));
+
+    if let Some(duration_seconds) = case.duration_seconds {
+        fields.push(format!(
+            "**Timeout Duration :** {}",
+            format_compact_duration(duration_seconds)
+        ));
+    }
+
+    // Blank line separator before metadata.
+    fields.push(String::new());
+
+    fields.push(format!("**When :** <t:{}:R>", case.created_at));
+
+    let title = format!("{} - #{}", kind.modlog_title(), case_label);
+    let description = fields.join("\n");
+
+    let embed = serenity::CreateEmbed::new()
+        .color(DEFAULT_EMBED_COLOR)
+        .title(title)
+        .description(description);
+
+    serenity::ChannelId::new(channel_id)
+        .send_message(&ctx.http, serenity::CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
@@ -1,10 +1,89 @@
+use std::time::Duration;
+
 use poise::serenity_prelude as serenity;
-use tracing::error;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tracing::{error, warn};
 
+use autumn_commands::command_meta;
 use autumn_core::{Data, Error};
-use autumn_database::impls::ai_config::get_llm_enabled;
-use autumn_database::impls::llm_chat::insert_llm_chat_message;
+use autumn_database::impls::ai_config::{
+    get_llm_enabled, get_llm_long_reply_mode, get_llm_thread_mode,
+};
+use autumn_database::impls::disabled_commands::get_disabled_commands;
+use autumn_database::impls::llm_chat::{
+    get_llm_thread_parent, insert_llm_chat_message, insert_llm_thread,
+};
 use autumn_database::impls::rate_limit::llm_mention_within_limit;
+use autumn_database::model::disabled_commands::{LLM_CHAT_COMMAND, is_command_disabled};
+use autumn_database::model::llm_chat::LlmChatEntry;
+use autumn_llm::context::{load_channel_context, load_reply_chain_context, load_thread_context};
+use autumn_llm::prompt::IMAGES_UNSUPPORTED_NOTE;
+use autumn_llm::provider::ChatImage;
+use autumn_llm::usage::{UsageSource, daily_quota_refusal, record_usage};
+use autumn_utils::permissions::resolve_parent_channel;
+use autumn_utils::split::{DISCORD_MESSAGE_LIMIT, split_markdown};
+use autumn_utils::time::now_unix_secs;
+
+use crate::events::llm_tools::ModerationTools;
+use crate::events::word_filter::{active_filter_words, redact_filtered_words};
+
+/// Minimum time between edits of a streamed reply. Discord allows roughly five
+/// message edits per five seconds per channel.
+const STREAM_EDIT_INTERVAL: Duration = Duration::from_millis(1500);
+/// Leaves room for the trailing ellipsis under the 2000-character limit.
+const STREAM_PREVIEW_MAX_CHARS: usize = 1990;
+/// Most messages walked back through a reply chain.
+const REPLY_CHAIN_MAX_MESSAGES: usize = 30;
+/// Characters of the prompt used to name a conversation thread.
+const THREAD_NAME_MAX_CHARS: usize = 60;
+/// Largest image attachment passed to the model; bigger ones are ignored.
+const IMAGE_MAX_BYTES: u32 = 5 * 1024 * 1024;
+/// Most images passed to the model per message.
+const IMAGE_MAX_COUNT: usize = 4;
+
+/// Which history a prompt is answered from.
+enum Conversation {
+    /// A mention outside any conversation: the channel's recent history.
+    Channel,
+    /// A reply: the referenced messages, newest first.
+    ReplyChain(Vec<LlmChatEntry>),
+    /// A conversation thread: the thread's history, under its parent's prompt.
+    Thread { parent_channel_id: u64 },
+}
+
+/// Where the reply is posted.
+enum ReplyTarget<'a> {
+    /// As a reply to the prompt message.
+    Inline(&'a serenity::Message),
+    /// As the first message of a newly started thread.
+    Thread(serenity::ChannelId),
+}
+
+impl ReplyTarget<'_> {
+    fn channel_id(&self) -> serenity::ChannelId {
+        match self {
+            Self::Inline(message) => message.channel_id,
+            Self::Thread(thread_id) => *thread_id,
+        }
+    }
+
+    async fn send(
+        &self,
+        ctx: &serenity::Context,
+        builder: serenity::CreateMessage,
+    ) -> serenity::Result<serenity::Message> {
+        match self {
+            Self::Inline(message) => {
+                message
+                    .channel_id
+                    .send_message(&ctx.http, builder.reference_message(*message))
+                    .await
+            }
+            Self::Thread(thread_id) => thread_id.send_message(&ctx.http, builder).await,
+        }
+    }
+}
 
 pub async fn handle_message_mention_llm(
     ctx: &serenity::Context,
@@ -35,24 +114,67 @@ pub async fn handle_message_mention_llm(
         return Ok(());
     }
 
-    let mentions_bot = match new_message.mentions_me(ctx).await {
-        Ok(value) => value,
+    match get_disabled_commands(&data.db, guild_id.get()).await {
+        Ok(disabled) => {
+            // Threads follow their parent channel's setting.
+            let channel_id =
+                resolve_parent_channel(ctx, data.db.cache(), guild_id, new_message.channel_id)
+                    .await
+                    .unwrap_or_else(|source| {
+                        error!(?source, "failed to resolve thread parent channel");
+                        new_message.channel_id
+                    });
+            if is_command_disabled(&disabled, LLM_CHAT_COMMAND, channel_id.get()) {
+                return Ok(());
+            }
+        }
         Err(source) => {
-            error!(?source, "failed to evaluate bot mention");
-            false
+            error!(?source, "failed to read disabled commands");
         }
-    };
+    }
+
+    let bot_user_id = ctx.cache.current_user().id;
+
+    let thread_parent =
+        match get_llm_thread_parent(&data.db, guild_id.get(), new_message.channel_id.get()).await {
+            Ok(parent) => parent,
+            Err(source) => {
+                error!(?source, "failed to look up llm conversation thread");
+                None
+            }
+        };
+
+    let replies_to_bot = new_message
+        .referenced_message
+        .as_ref()
+        .is_some_and(|referenced| referenced.author.id == bot_user_id);
+
+    if thread_parent.is_none() && !replies_to_bot {
+        let mentions_bot = match new_message.mentions_me(ctx).await {
+            Ok(value) => value,
+            Err(source) => {
+                error!(?source, "failed to evaluate bot mention");
+                false
+            }
+        };
+
+        if !mentions_bot {
+            return Ok(());
+        }
+    }
 
-    if !mentions_bot {
+    // `@Autumn <command>` runs the command through mention-as-prefix instead.
+    if is_command_by_mention(&new_message.content, bot_user_id) {
         return Ok(());
     }
 
-    let bot_user_id = ctx.cache.current_user().id;
     let author_display_name = message_display_name(new_message);
     let bot_display_name = ctx.cache.current_user().name.clone();
-    let prompt = strip_bot_mention(&new_message.content, bot_user_id)
-        .trim()
-        .to_owned();
+    let image_attachments = image_attachments(new_message);
+    let prompt = with_image_markers(
+        strip_bot_mention(&new_message.content, bot_user_id).trim(),
+        &image_attachments,
+    );
 
     if prompt.is_empty() {
         new_message.reply(&ctx.http, "a?").await?;
@@ -84,62 +206,677 @@ pub async fn handle_message_mention_llm(
         return Ok(());
     }
 
-    let _ = new_message.channel_id.broadcast_typing(&ctx.http).await;
+    let usage_source = UsageSource {
+        guild_id: guild_id.get(),
+        channel_id: new_message.channel_id.get(),
+        user_id: new_message.author.id.get(),
+        kind: "reply",
+    };
+    match daily_quota_refusal(&data.db, usage_source, now_unix_secs()).await {
+        Ok(Some(refusal)) => {
+            new_message.reply(&ctx.http, refusal).await?;
+            return Ok(());
+        }
+        Ok(None) => {}
+        Err(source) => error!(?source, "failed to check llm quota"),
+    }
 
-    let llm_reply = match llm
-        .generate_channel_reply(
-            &data.db,
-            guild_id.get(),
-            new_message.channel_id.get(),
-            &prompt,
-            &author_display_name,
+    // Replies go through the guild's word filter like any member's message.
+    let filter_words = active_filter_words(data, guild_id).await;
+
+    // The stored prompt keeps only the image markers; the model also gets the
+    // images themselves, or a note that it can't see them.
+    let mut model_prompt = prompt.clone();
+    let images = if image_attachments.is_empty() {
+        Vec::new()
+    } else if llm.supports_images().await {
+        download_images(&image_attachments).await
+    } else {
+        model_prompt.push_str("\n\n");
+        model_prompt.push_str(IMAGES_UNSUPPORTED_NOTE);
+        Vec::new()
+    };
+
+    let (conversation, target) = if let Some(parent_channel_id) = thread_parent {
+        (
+            Conversation::Thread { parent_channel_id },
+            ReplyTarget::Inline(new_message),
         )
-        .await
+    } else if new_message.referenced_message.is_some() {
+        (
+            Conversation::ReplyChain(collect_reply_chain(ctx, new_message, bot_user_id).await),
+            ReplyTarget::Inline(new_message),
+        )
+    } else {
+        match start_conversation_thread(ctx, data, guild_id, new_message, &prompt).await {
+            Some(thread_id) => (
+                Conversation::Thread {
+                    parent_channel_id: new_message.channel_id.get(),
+                },
+                ReplyTarget::Thread(thread_id),
+            ),
+            None => (Conversation::Channel, ReplyTarget::Inline(new_message)),
+        }
+    };
+
+    // History is stored under the channel the conversation happens in, so
+    // each thread keeps its own.
+    let conversation_channel_id = target.channel_id().get();
+    let prompt_channel_id = match &conversation {
+        Conversation::Thread { parent_channel_id } => *parent_channel_id,
+        _ => conversation_channel_id,
+    };
+
+    let _ = target.channel_id().broadcast_typing(&ctx.http).await;
+
+    // Moderation lookups are only offered to members who could run the
+    // equivalent commands themselves, in channels hidden from `@everyone`.
+    let role_ids = new_message
+        .member
+        .as_ref()
+        .map(|member| member.roles.iter().map(|id| id.get()).collect())
+        .unwrap_or_default();
+    let tools = match ModerationTools::for_member(
+        ctx.http.clone(),
+        data.db.clone(),
+        guild_id,
+        new_message.channel_id,
+        new_message.author.id,
+        role_ids,
+    )
+    .await
     {
-        Ok(content) if !content.trim().is_empty() => content,
+        Ok(tools) => tools,
+        Err(source) => {
+            error!(?source, "failed to resolve llm tool permissions");
+            None
+        }
+    };
+
+    let (delta_tx, delta_rx) = mpsc::unbounded_channel();
+    let started = Instant::now();
+    let generation = {
+        let llm = llm.clone();
+        let db = data.db.clone();
+        let prompt = model_prompt;
+        let author_display_name = author_display_name.clone();
+        let author_id = new_message.author.id.get();
+        tokio::spawn(async move {
+            let budget = llm.context_token_budget();
+            let mut context = match conversation {
+                Conversation::Channel => {
+                    load_channel_context(
+                        &db,
+                        guild_id.get(),
+                        conversation_channel_id,
+                        &prompt,
+                        budget,
+                    )
+                    .await?
+                }
+                Conversation::ReplyChain(chain) => {
+                    load_reply_chain_context(
+                        &db,
+                        guild_id.get(),
+                        conversation_channel_id,
+                        chain,
+                        &prompt,
+                        budget,
+                    )
+                    .await?
+                }
+                Conversation::Thread { parent_channel_id } => {
+                    load_thread_context(
+                        &db,
+                        guild_id.get(),
+                        conversation_channel_id,
+                        parent_channel_id,
+                        &prompt,
+                        budget,
+                    )
+                    .await?
+                }
+            };
+
+            // Other members' messages could steer the lookups, so a
+            // tool-assisted reply only sees the requester's own messages.
+            if tools.is_some() {
+                context.memory = None;
+                context
+                    .history
+                    .retain(|entry| entry.role == "user" && entry.user_id == author_id);
+            }
+
+            match tools {
+                Some(tools) => {
+                    // Tool rounds are not streamed; the answer arrives in one piece.
+                    let reply = llm
+                        .generate_reply_with_tools(
+                            &context,
+                            &prompt,
+                            &author_display_name,
+                            &images,
+                            &tools,
+                        )
+                        .await?;
+                    let _ = delta_tx.send(reply.content.clone());
+                    Ok(reply)
+                }
+                None => {
+                    llm.stream_reply_with_context(
+                        &context,
+                        &prompt,
+                        &author_display_name,
+                        &images,
+                        delta_tx,
+                    )
+                    .await
+                }
+            }
+        })
+    };
+
+    let reply_message = stream_reply_edits(ctx, &target, delta_rx, &filter_words).await;
+
+    let generated = match generation.await {
+        Ok(result) => result,
+        Err(source) => Err(source.into()),
+    };
+    record_usage(
+        &data.db,
+        usage_source,
+        started.elapsed(),
+        generated.as_ref().ok().map(|reply| reply.usage),
+    )
+    .await;
+
+    // A reply built from moderation lookups answers only the moderator who
+    // asked; it stays out of the shared history other members' prompts read.
+    let used_tools = generated.as_ref().is_ok_and(|reply| reply.used_tools);
+
+    let llm_reply = match generated {
+        Ok(reply) if !reply.content.trim().is_empty() => reply.content,
         Ok(_) => "I couldn't generate a useful response for that. Try rephrasing?".to_owned(),
         Err(source) => {
             error!(?source, "llm reply generation failed");
-            new_message
-                .reply(&ctx.http, "I ran into an LLM error. Try again in a moment.")
-                .await?;
+            let failure = "I ran into an LLM error. Try again in a moment.";
+            match reply_message {
+                Some(mut message) => {
+                    message
+                        .edit(&ctx.http, serenity::EditMessage::new().content(failure))
+                        .await?;
+                }
+                None => {
+                    target
+                        .send(ctx, serenity::CreateMessage::new().content(failure))
+                        .await?;
+                }
+            }
             return Ok(());
         }
     };
 
-    if let Err(source) = insert_llm_chat_message(
-        &data.db,
-        guild_id.get(),
-        new_message.channel_id.get(),
-        new_message.author.id.get(),
-        Some(author_display_name.as_str()),
-        "user",
-        &prompt,
-    )
-    .await
+    let llm_reply = match redact_filtered_words(&llm_reply, &filter_words) {
+        Some(redacted) => {
+            warn!(
+                guild_id = guild_id.get(),
+                "llm reply contained filtered words; redacted"
+            );
+            redacted
+        }
+        None => llm_reply,
+    };
+
+    if !used_tools
+        && let Err(source) = insert_llm_chat_message(
+            &data.db,
+            guild_id.get(),
+            conversation_channel_id,
+            new_message.author.id.get(),
+            Some(author_display_name.as_str()),
+            "user",
+            &prompt,
+            None,
+        )
+        .await
     {
         error!(?source, "failed to persist user llm chat message");
     }
 
-    new_message.reply(&ctx.http, &llm_reply).await?;
+    let long_reply_mode = match get_llm_long_reply_mode(&data.db, guild_id.get()).await {
+        Ok(mode) => mode,
+        Err(source) => {
+            error!(?source, "failed to read guild long reply mode");
+            "split".to_owned()
+        }
+    };
+
+    deliver_final_reply(ctx, &target, reply_message, &llm_reply, &long_reply_mode).await?;
+
+    if used_tools {
+        return Ok(());
+    }
 
     if let Err(source) = insert_llm_chat_message(
         &data.db,
         guild_id.get(),
-        new_message.channel_id.get(),
+        conversation_channel_id,
         bot_user_id.get(),
         Some(bot_display_name.as_str()),
         "assistant",
         &llm_reply,
+        Some(new_message.author.id.get()),
     )
     .await
     {
         error!(?source, "failed to persist assistant llm chat message");
     }
 
+    let llm = llm.clone();
+    let db = data.db.clone();
+    tokio::spawn(async move {
+        let started = Instant::now();
+        match llm
+            .refresh_channel_memory(
+                &db,
+                guild_id.get(),
+                conversation_channel_id,
+                prompt_channel_id,
+            )
+            .await
+        {
+            Ok(Some(usage)) => {
+                let source = UsageSource {
+                    guild_id: guild_id.get(),
+                    channel_id: conversation_channel_id,
+                    user_id: bot_user_id.get(),
+                    kind: "memory",
+                };
+                record_usage(&db, source, started.elapsed(), Some(usage)).await;
+            }
+            Ok(None) => {}
+            Err(source) => {
+                error!(?source, "failed to refresh llm channel memory");
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Image attachments small enough to pass to the model, up to
+/// [`IMAGE_MAX_COUNT`].
+fn image_attachments(message: &serenity::Message) -> Vec<&serenity::Attachment> {
+    message
+        .attachments
+        .iter()
+        .filter(|attachment| {
+            attachment
+                .content_type
+                .as_deref()
+                .is_some_and(|content_type| content_type.starts_with("image/"))
+                && attachment.size <= IMAGE_MAX_BYTES
+        })
+        .take(IMAGE_MAX_COUNT)
+        .collect()
+}
+
+/// Download attachments for the model. Failed downloads are skipped.
+async fn download_images(attachments: &[&serenity::Attachment]) -> Vec<ChatImage> {
+    let mut images = Vec::with_capacity(attachments.len());
+    for attachment in attachments {
+        let mime_type = attachment.content_type.as_deref().unwrap_or("image/png");
+        match attachment.download().await {
+            Ok(bytes) => images.push(ChatImage::from_bytes(mime_type, &bytes)),
+            Err(source) => error!(?source, "failed to download llm image attachment"),
+        }
+    }
+
+    images
+}
+
+/// Append an `[image: name]` marker per attachment so history records that
+/// images were part of the message.
+fn with_image_markers(text: &str, attachments: &[&serenity::Attachment]) -> String {
+    let markers: Vec<String> = attachments
+        .iter()
+        .map(|attachment| format!("[image: {}]", attachment.filename))
+        .collect();
+
+    match (text.is_empty(), markers.is_empty()) {
+        (_, true) => text.to_owned(),
+        (true, false) => markers.join(" "),
+        (false, false) => format!("{}\n{}", text, markers.join(" ")),
+    }
+}
+
+/// Walk reply references back from `message`, newest first, turning each
+/// message into a history entry. Stops at the start of the chain, at
+/// [`REPLY_CHAIN_MAX_MESSAGES`], or at a message that can't be fetched.
+async fn collect_reply_chain(
+    ctx: &serenity::Context,
+    message: &serenity::Message,
+    bot_user_id: serenity::UserId,
+) -> Vec<LlmChatEntry> {
+    let mut chain = Vec::new();
+    let mut next = message.referenced_message.as_deref().cloned();
+
+    while let Some(current) = next.take() {
+        if chain.len() >= REPLY_CHAIN_MAX_MESSAGES {
+            break;
+        }
+
+        if let Some(entry) = reply_chain_entry(&current, bot_user_id) {
+            chain.push(entry);
+        }
+
+        let Some(reference_id) = current
+            .message_reference
+            .as_ref()
+            .filter(|reference| reference.channel_id == current.channel_id)
+            .and_then(|reference| reference.message_id)
+        else {
+            break;
+        };
+
+        let cached = ctx
+            .cache
+            .message(current.channel_id, reference_id)
+            .map(|message| message.clone());
+        next = match cached {
+            Some(message) => Some(message),
+            None => match ctx.http.get_message(current.channel_id, reference_id).await {
+                Ok(message) => Some(message),
+                Err(source) => {
+                    error!(?source, "failed to fetch llm reply chain message");
+                    None
+                }
+            },
+        };
+    }
+
+    chain
+}
+
+fn reply_chain_entry(
+    message: &serenity::Message,
+    bot_user_id: serenity::UserId,
+) -> Option<LlmChatEntry> {
+    let content = strip_bot_mention(&message.content, bot_user_id);
+    if content.is_empty() {
+        return None;
+    }
+
+    let from_bot = message.author.id == bot_user_id;
+    Some(LlmChatEntry {
+        id: message.id.get(),
+        user_id: message.author.id.get(),
+        display_name: Some(message_display_name(message)),
+        role: if from_bot { "assistant" } else { "user" }.to_owned(),
+        content,
+        created_at: u64::try_from(message.timestamp.unix_timestamp()).unwrap_or_default(),
+    })
+}
+
+/// Start a thread on `message` when the guild has thread mode on and the
+/// message isn't already in a thread. Returns `None` to answer inline.
+async fn start_conversation_thread(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    message: &serenity::Message,
+    prompt: &str,
+) -> Option<serenity::ChannelId> {
+    let thread_mode = match get_llm_thread_mode(&data.db, guild_id.get()).await {
+        Ok(enabled) => enabled,
+        Err(source) => {
+            error!(?source, "failed to read guild thread mode");
+            false
+        }
+    };
+
+    if !thread_mode {
+        return None;
+    }
+
+    let in_thread = match message.channel(ctx).await {
+        Ok(serenity::Channel::Guild(channel)) => channel.thread_metadata.is_some(),
+        Ok(_) => true,
+        Err(source) => {
+            error!(?source, "failed to resolve llm prompt channel");
+            true
+        }
+    };
+
+    if in_thread {
+        return None;
+    }
+
+    let builder = serenity::CreateThread::new(thread_name(prompt))
+        .auto_archive_duration(serenity::AutoArchiveDuration::OneDay);
+    let thread = match message
+        .channel_id
+        .create_thread_from_message(&ctx.http, message.id, builder)
+        .await
+    {
+        Ok(thread) => thread,
+        Err(source) => {
+            error!(?source, "failed to start llm conversation thread");
+            return None;
+        }
+    };
+
+    if let Err(source) = insert_llm_thread(
+        &data.db,
+        guild_id.get(),
+        thread.id.get(),
+        message.channel_id.get(),
+        message.author.id.get(),
+    )
+    .await
+    {
+        error!(?source, "failed to record llm conversation thread");
+    }
+
+    Some(thread.id)
+}
+
+fn thread_name(prompt: &str) -> String {
+    let first_line = prompt.lines().next().unwrap_or_default().trim();
+    if first_line.is_empty() {
+        return "AI conversation".to_owned();
+    }
+
+    if first_line.chars().count() <= THREAD_NAME_MAX_CHARS {
+        return first_line.to_owned();
+    }
+
+    let mut name: String = first_line.chars().take(THREAD_NAME_MAX_CHARS - 1).collect();
+    name.push('…');
+    name
+}
+
+/// Post the reply as soon as the first piece arrives, then edit it with the
+/// accumulated text at most once per [`STREAM_EDIT_INTERVAL`] until the stream
+/// ends. Previews are redacted with the guild's `filter_words`. Returns the
+/// posted message, if any, so the caller can finalize it.
+async fn stream_reply_edits(
+    ctx: &serenity::Context,
+    target: &ReplyTarget<'_>,
+    mut deltas: mpsc::UnboundedReceiver<String>,
+    filter_words: &[String],
+) -> Option<serenity::Message> {
+    let mut text = String::new();
+    let mut reply_message: Option<serenity::Message> = None;
+    let mut pending = false;
+    let mut next_edit = Instant::now();
+
+    loop {
+        tokio::select! {
+            delta = deltas.recv() => {
+                let Some(delta) = delta else {
+                    break;
+                };
+                text.push_str(&delta);
+                pending = true;
+            }
+            _ = tokio::time::sleep_until(next_edit), if pending => {}
+        }
+
+        if !pending || Instant::now() < next_edit {
+            continue;
+        }
+
+        let preview = match redact_filtered_words(&text, filter_words) {
+            Some(redacted) => stream_preview(&redacted),
+            None => stream_preview(&text),
+        };
+        if preview.is_empty() {
+            continue;
+        }
+
+        match reply_message.as_mut() {
+            Some(message) => {
+                if let Err(source) = message
+                    .edit(
+                        &ctx.http,
+                        serenity::EditMessage::new()
+                            .content(preview)
+                            .allowed_mentions(reply_allowed_mentions()),
+                    )
+                    .await
+                {
+                    error!(?source, "failed to edit streamed llm reply");
+                }
+            }
+            None => match target
+                .send(
+                    ctx,
+                    serenity::CreateMessage::new()
+                        .content(preview)
+                        .allowed_mentions(reply_allowed_mentions()),
+                )
+                .await
+            {
+                Ok(message) => reply_message = Some(message),
+                Err(source) => error!(?source, "failed to send streamed llm reply"),
+            },
+        }
+
+        pending = false;
+        next_edit = Instant::now() + STREAM_EDIT_INTERVAL;
+    }
+
+    reply_message
+}
+
+/// Replace the streamed preview with the final reply. Replies over Discord's
+/// length limit are either split into a reply chain or, in `attachment` mode,
+/// attached in full as a markdown file.
+async fn deliver_final_reply(
+    ctx: &serenity::Context,
+    target: &ReplyTarget<'_>,
+    reply_message: Option<serenity::Message>,
+    llm_reply: &str,
+    long_reply_mode: &str,
+) -> Result<(), Error> {
+    if long_reply_mode == "attachment" && llm_reply.chars().count() > DISCORD_MESSAGE_LIMIT {
+        let content = "That answer is too long for one message, so I've attached it as a file.";
+        let attachment = serenity::CreateAttachment::bytes(llm_reply.as_bytes(), "reply.md");
+
+        match reply_message {
+            Some(mut message) => {
+                message
+                    .edit(
+                        &ctx.http,
+                        serenity::EditMessage::new()
+                            .content(content)
+                            .new_attachment(attachment),
+                    )
+                    .await?;
+            }
+            None => {
+                target
+                    .send(
+                        ctx,
+                        serenity::CreateMessage::new()
+                            .content(content)
+                            .add_file(attachment),
+                    )
+                    .await?;
+            }
+        }
+
+        return Ok(());
+    }
+
+    let mut chunks = split_markdown(llm_reply, DISCORD_MESSAGE_LIMIT).into_iter();
+    let first = chunks.next().unwrap_or_else(|| llm_reply.to_owned());
+
+    let mut previous = match reply_message {
+        Some(mut message) => {
+            message
+                .edit(
+                    &ctx.http,
+                    serenity::EditMessage::new()
+                        .content(first)
+                        .allowed_mentions(reply_allowed_mentions()),
+                )
+                .await?;
+            message
+        }
+        None => {
+            target
+                .send(
+                    ctx,
+                    serenity::CreateMessage::new()
+                        .content(first)
+                        .allowed_mentions(reply_allowed_mentions()),
+                )
+                .await?
+        }
+    };
+
+    // Each continuation replies to the previous chunk to keep the chain readable.
+    for chunk in chunks {
+        previous = previous
+            .channel_id
+            .send_message(
+                &ctx.http,
+                serenity::CreateMessage::new()
+                    .content(chunk)
+                    .reference_message(&previous)
+                    .allowed_mentions(reply_allowed_mentions()),
+            )
+            .await?;
+    }
+
     Ok(())
 }
 
+/// In-progress text with a trailing ellipsis, kept under Discord's message
+/// length limit.
+fn stream_preview(text: &str) -> String {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return String::new();
+    }
+
+    let mut preview: String = trimmed.chars().take(STREAM_PREVIEW_MAX_CHARS).collect();
+    preview.push_str(" …");
+    preview
+}
+
+/// Mentions a model reply may ping. A prompt can talk the model into writing
+/// `@everyone` or a role mention, so only users are ever notified.
+fn reply_allowed_mentions() -> serenity::CreateAllowedMentions {
+    serenity::CreateAllowedMentions::new()
+        .everyone(false)
+        .all_roles(false)
+        .all_users(true)
+        .replied_user(true)
+}
+
 fn strip_bot_mention(content: &str, bot_user_id: serenity::UserId) -> String {
     content
         .replace(&format!("<@{}>", bot_user_id.get()), "")
@@ -148,6 +885,16 @@ fn strip_bot_mention(content: &str, bot_user_id: serenity::UserId) -> String {
         .to_owned()
 }
 
+/// Whether `content` starts with a bot mention followed by a command name.
+fn is_command_by_mention(content: &str, bot_user_id: serenity::UserId) -> bool {
+    let rest = content
+        .strip_prefix(&format!("<@{}>", bot_user_id.get()))
+        .or_else(|| content.strip_prefix(&format!("<@!{}>", bot_user_id.get())));
+
+    rest.and_then(|rest| rest.split_whitespace().next())
+        .is_some_and(|name| command_meta(&name.to_ascii_lowercase()).is_some())
+}
+
 fn message_display_name(message: &serenity::Message) -> String {
     if let Some(member) = &message.member
         && let Some(nick) = &member.nick
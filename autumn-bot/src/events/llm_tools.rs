@@ -0,0 +1,369 @@
+use std::sync::Arc;
+
+use anyhow::{Context as _, anyhow};
+use poise::serenity_prelude as serenity;
+use serde_json::{Value, json};
+
+use autumn_commands::access::CommandAccess;
+use autumn_commands::command_meta;
+use autumn_database::Database;
+use autumn_database::impls::cases::{
+    CaseFilters, get_case_by_label, get_case_events, list_recent_cases,
+};
+use autumn_database::impls::notes::list_user_notes;
+use autumn_database::impls::warnings::warnings_since;
+use autumn_llm::provider::{ToolCall, ToolSpec};
+use autumn_llm::tools::{ToolFuture, ToolRegistry};
+use autumn_utils::formatting::{format_case_label, parse_case_label};
+use autumn_utils::permissions::resolve_everyone_permissions;
+use autumn_utils::time::now_unix_secs;
+
+/// Each tool and the command whose access it follows, so command rules and
+/// disables apply to the tools too.
+const TOOL_COMMANDS: &[(&str, &str)] = &[
+    ("get_case", "case"),
+    ("list_user_cases", "modlogs"),
+    ("count_warnings", "warnings"),
+    ("list_user_notes", "notes"),
+];
+const DEFAULT_CASE_LIMIT: u32 = 10;
+const MAX_CASE_LIMIT: u32 = 25;
+const MAX_NOTES: usize = 10;
+const MAX_WARNINGS_LISTED: usize = 10;
+
+/// Read-only moderation lookups, run as the member who asked.
+pub struct ModerationTools {
+    http: Arc<serenity::Http>,
+    db: Database,
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+    user_id: serenity::UserId,
+    role_ids: Vec<u64>,
+    /// Names of the tools offered.
+    allowed: Vec<&'static str>,
+}
+
+impl ModerationTools {
+    /// Tools for `user_id` in `channel_id`, or `None` if they may run none of
+    /// the equivalent commands. Answers are posted where everyone in the
+    /// channel can read them, so tools are only offered in channels hidden
+    /// from `@everyone`.
+    pub async fn for_member(
+        http: Arc<serenity::Http>,
+        db: Database,
+        guild_id: serenity::GuildId,
+        channel_id: serenity::ChannelId,
+        user_id: serenity::UserId,
+        role_ids: Vec<u64>,
+    ) -> anyhow::Result<Option<Self>> {
+        let everyone =
+            resolve_everyone_permissions(&http, db.cache(), guild_id, channel_id).await?;
+        if everyone.contains(serenity::Permissions::VIEW_CHANNEL) {
+            return Ok(None);
+        }
+
+        let mut tools = Self {
+            http,
+            db,
+            guild_id,
+            channel_id,
+            user_id,
+            role_ids,
+            allowed: Vec::new(),
+        };
+        tools.allowed = tools.allowed_tools().await?;
+        if tools.allowed.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(tools))
+    }
+
+    /// The tools whose equivalent command the member may run right now.
+    async fn allowed_tools(&self) -> anyhow::Result<Vec<&'static str>> {
+        let access = CommandAccess::for_member(
+            &self.http,
+            &self.db,
+            self.guild_id,
+            self.channel_id,
+            self.user_id,
+            self.role_ids.clone(),
+        )
+        .await?;
+
+        Ok(TOOL_COMMANDS
+            .iter()
+            .filter(|(_, command)| command_meta(command).is_some_and(|meta| access.can_use(meta)))
+            .map(|(tool, _)| *tool)
+            .collect())
+    }
+
+    async fn run(&self, call: &ToolCall) -> anyhow::Result<Value> {
+        // Permissions can change mid-conversation, so check every call.
+        if !self.allowed.contains(&call.name.as_str())
+            || !self.allowed_tools().await?.contains(&call.name.as_str())
+        {
+            return Err(anyhow!(
+                "the requesting member no longer has permission to view moderation records"
+            ));
+        }
+
+        let args = &call.arguments;
+        match call.name.as_str() {
+            "get_case" => self.get_case(args).await,
+            "list_user_cases" => self.list_user_cases(args).await,
+            "count_warnings" => self.count_warnings(args).await,
+            "list_user_notes" => self.list_user_notes(args).await,
+            other => Err(anyhow!("unknown tool `{other}`")),
+        }
+    }
+
+    async fn get_case(&self, args: &Value) -> anyhow::Result<Value> {
+        let raw_label = string_arg(args, "label")?;
+        let (case_code, action_case_number) =
+            parse_case_label(raw_label).context("label must look like W1, B2, K3, etc.")?;
+
+        let guild_id = self.guild_id.get();
+        let Some(case) =
+            get_case_by_label(&self.db, guild_id, &case_code, action_case_number).await?
+        else {
+            return Ok(json!({ "found": false, "label": raw_label }));
+        };
+        let events = get_case_events(&self.db, guild_id, &case_code, action_case_number).await?;
+
+        Ok(json!({
+            "found": true,
+            "label": format_case_label(&case.case_code, case.action_case_number),
+            "action": case.action,
+            "status": case.status,
+            "target_user_id": case.target_user_id.map(|id| id.to_string()),
+            "moderator_user_id": case.moderator_user_id.to_string(),
+            "reason": case.reason,
+            "duration_seconds": case.duration_seconds,
+            "created_at": iso_timestamp(case.created_at),
+            "events": events
+                .iter()
+                .map(|event| json!({
+                    "type": event.event_type,
+                    "actor_user_id": event.actor_user_id.to_string(),
+                    "old_reason": event.old_reason,
+                    "new_reason": event.new_reason,
+                    "note": event.note,
+                    "at": iso_timestamp(event.created_at),
+                }))
+                .collect::<Vec<_>>(),
+            "now": iso_timestamp(now_unix_secs()),
+        }))
+    }
+
+    async fn list_user_cases(&self, args: &Value) -> anyhow::Result<Value> {
+        let user_id = user_id_arg(args)?;
+        let limit = args
+            .get("limit")
+            .and_then(Value::as_u64)
+            .and_then(|limit| u32::try_from(limit).ok())
+            .unwrap_or(DEFAULT_CASE_LIMIT)
+            .clamp(1, MAX_CASE_LIMIT);
+        let action = args
+            .get("action")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|action| !action.is_empty());
+
+        let cases = list_recent_cases(
+            &self.db,
+            self.guild_id.get(),
+            CaseFilters {
+                target_user_id: Some(user_id),
+                moderator_user_id: None,
+                action,
+                limit,
+            },
+        )
+        .await?;
+
+        Ok(json!({
+            "user_id": user_id.to_string(),
+            "cases": cases
+                .iter()
+                .map(|case| json!({
+                    "label": format_case_label(&case.case_code, case.action_case_number),
+                    "action": case.action,
+                    "moderator_user_id": case.moderator_user_id.to_string(),
+                    "reason": case.reason,
+                    "duration_seconds": case.duration_seconds,
+                    "created_at": iso_timestamp(case.created_at),
+                }))
+                .collect::<Vec<_>>(),
+            "now": iso_timestamp(now_unix_secs()),
+        }))
+    }
+
+    async fn count_warnings(&self, args: &Value) -> anyhow::Result<Value> {
+        let user_id = user_id_arg(args)?;
+        let days = args
+            .get("days")
+            .and_then(Value::as_u64)
+            .filter(|days| *days > 0);
+        let now = now_unix_secs();
+        let since = days.map_or(0, |days| now.saturating_sub(days.saturating_mul(86_400)));
+
+        let warnings = warnings_since(&self.db, self.guild_id.get(), user_id, since).await?;
+
+        Ok(json!({
+            "user_id": user_id.to_string(),
+            "window_days": days,
+            "count": warnings.len(),
+            "most_recent": warnings
+                .iter()
+                .rev()
+                .take(MAX_WARNINGS_LISTED)
+                .map(|warning| json!({
+                    "moderator_user_id": warning.moderator_id.to_string(),
+                    "reason": warning.reason,
+                    "warned_at": iso_timestamp(warning.warned_at),
+                }))
+                .collect::<Vec<_>>(),
+            "now": iso_timestamp(now),
+        }))
+    }
+
+    async fn list_user_notes(&self, args: &Value) -> anyhow::Result<Value> {
+        let user_id = user_id_arg(args)?;
+        let notes = list_user_notes(&self.db, self.guild_id.get(), user_id).await?;
+
+        Ok(json!({
+            "user_id": user_id.to_string(),
+            "total": notes.len(),
+            "notes": notes
+                .iter()
+                .take(MAX_NOTES)
+                .map(|note| json!({
+                    "author_user_id": note.author_user_id.to_string(),
+                    "content": note.content,
+                    "created_at": iso_timestamp(note.created_at),
+                }))
+                .collect::<Vec<_>>(),
+        }))
+    }
+}
+
+impl ToolRegistry for ModerationTools {
+    fn specs(&self) -> Vec<ToolSpec> {
+        let user_id = json!({
+            "type": "string",
+            "description": "Discord user ID, or a <@user> mention"
+        });
+
+        let specs = vec![
+            ToolSpec {
+                name: "get_case".to_owned(),
+                description: "Look up one moderation case by its label, including edits and notes."
+                    .to_owned(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "label": { "type": "string", "description": "Case label such as W1 or B3" }
+                    },
+                    "required": ["label"]
+                }),
+            },
+            ToolSpec {
+                name: "list_user_cases".to_owned(),
+                description: "List a user's most recent moderation cases, newest first.".to_owned(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "user_id": user_id,
+                        "limit": { "type": "integer", "minimum": 1, "maximum": MAX_CASE_LIMIT },
+                        "action": { "type": "string", "description": "Only this action, e.g. ban, kick, warn, timeout" }
+                    },
+                    "required": ["user_id"]
+                }),
+            },
+            ToolSpec {
+                name: "count_warnings".to_owned(),
+                description: "Count a user's warnings, optionally only within the last N days."
+                    .to_owned(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "user_id": user_id,
+                        "days": { "type": "integer", "minimum": 1 }
+                    },
+                    "required": ["user_id"]
+                }),
+            },
+            ToolSpec {
+                name: "list_user_notes".to_owned(),
+                description: "Fetch the moderator notes recorded for a user, newest first."
+                    .to_owned(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": { "user_id": user_id },
+                    "required": ["user_id"]
+                }),
+            },
+        ];
+
+        specs
+            .into_iter()
+            .filter(|spec| self.allowed.contains(&spec.name.as_str()))
+            .collect()
+    }
+
+    fn call<'a>(&'a self, call: &'a ToolCall) -> ToolFuture<'a> {
+        Box::pin(async move { Ok(self.run(call).await?.to_string()) })
+    }
+}
+
+fn string_arg<'a>(args: &'a Value, name: &str) -> anyhow::Result<&'a str> {
+    args.get(name)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .with_context(|| format!("missing `{name}` argument"))
+}
+
+/// Accepts a raw ID (as a string or number) or a user mention.
+fn user_id_arg(args: &Value) -> anyhow::Result<u64> {
+    let parsed = match args.get("user_id") {
+        Some(Value::Number(number)) => number.as_u64(),
+        Some(Value::String(raw)) => raw
+            .trim()
+            .trim_start_matches("<@")
+            .trim_start_matches('!')
+            .trim_end_matches('>')
+            .parse::<u64>()
+            .ok(),
+        _ => None,
+    };
+
+    parsed
+        .filter(|id| *id > 0)
+        .context("`user_id` must be a Discord user ID")
+}
+
+fn iso_timestamp(unix_secs: u64) -> String {
+    i64::try_from(unix_secs)
+        .ok()
+        .and_then(|secs| serenity::Timestamp::from_unix_timestamp(secs).ok())
+        .map_or_else(|| unix_secs.to_string(), |timestamp| timestamp.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_id_accepts_ids_and_mentions() {
+        assert_eq!(user_id_arg(&json!({ "user_id": "123" })).ok(), Some(123));
+        assert_eq!(user_id_arg(&json!({ "user_id": 123 })).ok(), Some(123));
+        assert_eq!(
+            user_id_arg(&json!({ "user_id": "<@!123>" })).ok(),
+            Some(123)
+        );
+        assert!(user_id_arg(&json!({ "user_id": "someone" })).is_err());
+        assert!(user_id_arg(&json!({})).is_err());
+    }
+}
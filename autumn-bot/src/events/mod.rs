@@ -1,3 +1,8 @@
+pub mod ai_moderation;
+pub mod anti_nuke;
+pub mod attachment_filter;
+pub mod filter_action;
 pub mod llm_events;
+pub mod llm_tools;
 pub mod userlog;
 pub mod word_filter;
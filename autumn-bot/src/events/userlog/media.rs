@@ -24,15 +24,7 @@ pub fn parse_attachment_summary(raw: &str) -> Vec<AttachmentInfo> {
 
             let filename = trimmed[..start].to_owned();
             let url = trimmed[start + 2..trimmed.len() - 1].to_owned();
-            let lower = filename.to_ascii_lowercase();
-            let is_media = lower.ends_with(".png")
-                || lower.ends_with(".jpg")
-                || lower.ends_with(".jpeg")
-                || lower.ends_with(".gif")
-                || lower.ends_with(".webp")
-                || lower.ends_with(".mp4")
-                || lower.ends_with(".webm")
-                || lower.ends_with(".mov");
+            let is_media = classify_media_filename(&filename).is_some();
 
             Some(AttachmentInfo {
                 filename,
@@ -43,6 +35,32 @@ pub fn parse_attachment_summary(raw: &str) -> Vec<AttachmentInfo> {
         .collect()
 }
 
+/// Media category of an attachment, derived from its filename extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MediaKind {
+    Image,
+    Video,
+}
+
+/// Classifies a filename as an image or video by extension; `None` for other files.
+pub fn classify_media_filename(filename: &str) -> Option<MediaKind> {
+    let lower = filename.to_ascii_lowercase();
+    if lower.ends_with(".png")
+        || lower.ends_with(".jpg")
+        || lower.ends_with(".jpeg")
+        || lower.ends_with(".gif")
+        || lower.ends_with(".webp")
+    {
+        return Some(MediaKind::Image);
+    }
+
+    if lower.ends_with(".mp4") || lower.ends_with(".webm") || lower.ends_with(".mov") {
+        return Some(MediaKind::Video);
+    }
+
+    None
+}
+
 /// Finds the first URL in content that looks like an embeddable media link.
 pub fn extract_first_media_url(content: Option<&str>) -> Option<String> {
     let content = content?.trim();
@@ -1,6 +1,6 @@
 mod embed;
 mod handlers;
-mod media;
+pub(crate) mod media;
 mod util;
 
 pub use handlers::{
@@ -1,19 +1,15 @@
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
-
 use poise::serenity_prelude as serenity;
-use tracing::{error, warn};
+use tracing::error;
 
-use autumn_commands::moderation::escalation_check::check_and_escalate;
-use autumn_commands::moderation::send_moderation_target_dm_for_guild;
 use autumn_core::Data;
-use autumn_database::impls::cases::{NewCase, create_case};
-use autumn_database::impls::modlog_config::get_modlog_channel_id;
-use autumn_database::impls::warnings::record_warning;
 use autumn_database::impls::word_filter::{
-    get_all_filter_words_for_guild, get_word_filter_if_enabled,
+    get_all_filter_words_for_guild, get_word_filter_if_enabled, is_word_filter_automod_rule,
 };
-use autumn_utils::embed::DEFAULT_EMBED_COLOR;
-use autumn_utils::formatting::{format_case_label, format_compact_duration};
+
+use super::filter_action::{FilterKind, FilterOutcome, enforce_filter_action, record_filter_case};
+
+/// Stands in for a filtered word in text Autumn posts itself.
+const REDACTED_WORD: &str = "[filtered]";
 
 /// Check an incoming message against the guild's word filter and execute the
 /// configured action when a match is found.
@@ -21,23 +17,23 @@ pub async fn handle_message_word_filter(
     ctx: &serenity::Context,
     data: &Data,
     message: &serenity::Message,
-) {
+) -> FilterOutcome {
     // Ignore bots and webhooks.
     if message.author.bot || message.webhook_id.is_some() {
-        return;
+        return FilterOutcome::Clean;
     }
 
     let Some(guild_id) = message.guild_id else {
-        return;
+        return FilterOutcome::Clean;
     };
 
     // Check if the word filter is enabled for this guild.
     let config = match get_word_filter_if_enabled(&data.db, guild_id.get()).await {
         Ok(Some(cfg)) => cfg,
-        Ok(None) => return,
+        Ok(None) => return FilterOutcome::Clean,
         Err(source) => {
             error!(?source, "failed to read word filter config");
-            return;
+            return FilterOutcome::Clean;
         }
     };
 
@@ -46,250 +42,155 @@ pub async fn handle_message_word_filter(
         Ok(w) => w,
         Err(source) => {
             error!(?source, "failed to load word filter list");
-            return;
+            return FilterOutcome::Clean;
         }
     };
 
     if words.is_empty() {
-        return;
+        return FilterOutcome::Clean;
     }
 
-    // Check if any filtered word appears as a whole word in the message content.
-    let content_lower = message.content.to_lowercase();
-    let matched_word = words.iter().find(|w| {
-        // Match the word only at word boundaries to avoid false positives
-        // (e.g. "fag" should not match "leafage").
-        content_lower
-            .split(|c: char| !c.is_alphanumeric())
-            .any(|token| token == w.as_str())
-    });
-
-    let Some(matched_word) = matched_word else {
-        return;
+    let Some(matched_word) = find_filtered_word(&message.content, &words) else {
+        return FilterOutcome::Clean;
     };
 
-    let matched_word = matched_word.clone();
-    let action = config.action.as_str();
-    let bot_user_id = ctx.cache.current_user().id.get();
+    enforce_filter_action(
+        ctx,
+        data,
+        message,
+        guild_id,
+        FilterKind::Word,
+        &config.action,
+        matched_word,
+    )
+    .await
+}
 
-    // Suppress this message from user-log recording if it will be deleted.
-    if matches!(
-        action,
-        "delete_and_log" | "warn_and_log" | "timeout_delete_and_log"
-    ) {
-        let mut suppressed = data.suppressed_deletes.write().await;
-        suppressed.insert(message.id.get());
+/// The guild's filtered words when its word filter is enabled, or an empty
+/// list otherwise.
+pub(crate) async fn active_filter_words(data: &Data, guild_id: serenity::GuildId) -> Vec<String> {
+    match get_word_filter_if_enabled(&data.db, guild_id.get()).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return Vec::new(),
+        Err(source) => {
+            error!(?source, "failed to read word filter config");
+            return Vec::new();
+        }
     }
 
-    // Execute the configured action.
-    match action {
-        "delete_and_log" => {
-            if let Err(source) = message.delete(&ctx.http).await {
-                if !is_missing_permissions(&source) {
-                    error!(?source, "failed to delete filtered message");
-                } else {
-                    warn!("missing permissions to delete filtered message");
-                }
-            }
+    match get_all_filter_words_for_guild(&data.db, guild_id.get()).await {
+        Ok(words) => words,
+        Err(source) => {
+            error!(?source, "failed to load word filter list");
+            Vec::new()
         }
-        "warn_and_log" => {
-            if let Err(source) = message.delete(&ctx.http).await {
-                if !is_missing_permissions(&source) {
-                    error!(?source, "failed to delete filtered message");
-                } else {
-                    warn!("missing permissions to delete filtered message");
-                }
-            }
-
-            // Issue a warning for the user.
-            let warn_reason = format!("Word filter: {}", matched_word);
-            if let Err(source) = record_warning(
-                &data.db,
-                guild_id.get(),
-                message.author.id.get(),
-                bot_user_id,
-                &warn_reason,
-            )
-            .await
-            {
-                error!(
-                    ?source,
-                    "failed to record warning for word filter violation"
-                );
-            }
-
-            // DM the user about the warning.
-            let _ = send_moderation_target_dm_for_guild(
-                &ctx.http,
-                &message.author,
-                guild_id,
-                "warned",
-                Some(&warn_reason),
-                None,
-            )
-            .await;
+    }
+}
 
-            // Check for automatic escalation (warn threshold → auto-timeout).
-            check_and_escalate(&ctx.http, &data.db, guild_id, &message.author, bot_user_id).await;
-        }
-        "timeout_delete_and_log" => {
-            if let Err(source) = message.delete(&ctx.http).await {
-                if !is_missing_permissions(&source) {
-                    error!(?source, "failed to delete filtered message");
-                } else {
-                    warn!("missing permissions to delete filtered message");
-                }
-            }
+/// The first filtered word that appears as a whole word in `content`.
+fn find_filtered_word<'a>(content: &str, words: &'a [String]) -> Option<&'a String> {
+    let content_lower = content.to_lowercase();
+    words.iter().find(|w| {
+        // Match the word only at word boundaries to avoid false positives
+        // (e.g. "fag" should not match "leafage").
+        content_lower
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|token| token == w.as_str())
+    })
+}
 
-            // Apply a 5-minute timeout.
-            let timeout_duration = Duration::from_secs(300);
-            let until_system_time = SystemTime::now()
-                .checked_add(timeout_duration)
-                .unwrap_or(SystemTime::now());
-            let until_unix = until_system_time
-                .duration_since(UNIX_EPOCH)
-                .map_or(0, |d| d.as_secs()) as i64;
+/// Replace every whole-word match of a filtered word in `content`, using the
+/// same word boundaries as the message filter. Returns `None` when nothing
+/// matched.
+pub(crate) fn redact_filtered_words(content: &str, words: &[String]) -> Option<String> {
+    if words.is_empty() {
+        return None;
+    }
 
-            if let Ok(until) = serenity::Timestamp::from_unix_timestamp(until_unix) {
-                let edit = serenity::EditMember::new().disable_communication_until_datetime(until);
-                if let Err(source) = guild_id
-                    .edit_member(&ctx.http, message.author.id, edit)
-                    .await
-                {
-                    if !is_missing_permissions(&source) {
-                        error!(?source, "failed to timeout user for word filter violation");
-                    } else {
-                        warn!(
-                            user_id = %message.author.id,
-                            "missing permissions to timeout user for word filter violation \
-                             (check role hierarchy)"
-                        );
-                    }
-                }
-            }
+    let mut redacted = String::with_capacity(content.len());
+    let mut matched = false;
+    let mut rest = content;
 
-            // DM the user about the timeout.
-            let _ = send_moderation_target_dm_for_guild(
-                &ctx.http,
-                &message.author,
-                guild_id,
-                "timed out",
-                Some(&format!("Word filter: {}", matched_word)),
-                Some("5m"),
-            )
-            .await;
+    while let Some(first) = rest.chars().next() {
+        if !first.is_alphanumeric() {
+            redacted.push(first);
+            rest = &rest[first.len_utf8()..];
+            continue;
         }
-        // "log_only" or anything else — no message action needed.
-        _ => {}
-    }
 
-    // Create a moderation case for the violation.
-    let reason = matched_word.clone();
-    let case_action = match action {
-        "timeout_delete_and_log" => "word_filter_timeout",
-        "delete_and_log" => "word_filter_delete",
-        "warn_and_log" => "word_filter_warn",
-        _ => "word_filter_log",
-    };
+        let token_len = rest
+            .find(|c: char| !c.is_alphanumeric())
+            .unwrap_or(rest.len());
+        let (token, tail) = rest.split_at(token_len);
+        let token_lower = token.to_lowercase();
 
-    let new_case = NewCase {
-        guild_id: guild_id.get(),
-        target_user_id: Some(message.author.id.get()),
-        moderator_user_id: bot_user_id,
-        action: case_action,
-        reason: &reason,
-        status: "completed",
-        duration_seconds: if action == "timeout_delete_and_log" {
-            Some(300)
+        if words.contains(&token_lower) {
+            redacted.push_str(REDACTED_WORD);
+            matched = true;
         } else {
-            None
-        },
-    };
-
-    let case = match create_case(&data.db, new_case).await {
-        Ok(case) => case,
-        Err(source) => {
-            error!(?source, "failed to create word filter case");
-            return;
+            redacted.push_str(token);
         }
-    };
-
-    // Publish to modlog channel.
-    if let Err(source) =
-        publish_word_filter_to_modlog(ctx, data, guild_id, &case, &matched_word, action).await
-    {
-        error!(
-            ?source,
-            "failed to publish word filter case to modlog channel"
-        );
+        rest = tail;
     }
+
+    matched.then_some(redacted)
 }
 
-async fn publish_word_filter_to_modlog(
+/// Record a case for a message that Discord AutoMod blocked using one of the
+/// rules created by `!wordfilter sync-automod`. The message never reaches the
+/// regular message handler, so this is the only place it gets logged.
+pub async fn handle_automod_action_execution(
     ctx: &serenity::Context,
     data: &Data,
-    guild_id: serenity::GuildId,
-    case: &autumn_database::model::cases::CaseSummary,
-    matched_word: &str,
-    action: &str,
-) -> Result<(), serenity::Error> {
-    let channel_id = match get_modlog_channel_id(&data.db, guild_id.get()).await {
-        Ok(Some(id)) => id,
-        Ok(None) => return Ok(()),
-        Err(source) => {
-            error!(?source, "failed to read modlog channel for word filter");
-            return Ok(());
-        }
-    };
-
-    let case_label = format_case_label(&case.case_code, case.action_case_number);
-
-    let action_label = match action {
-        "timeout_delete_and_log" => "Timeout, Delete & Log",
-        "delete_and_log" => "Delete & Log",
-        "warn_and_log" => "Warn, Delete & Log",
-        _ => "Log Only",
-    };
-
-    let mut fields = Vec::new();
-    fields.push(format!(
-        "**User :** <@{}>",
-        case.target_user_id.unwrap_or(0)
-    ));
-    fields.push(format!("**Violation :** {}", matched_word));
-    fields.push(format!("**Action Taken :** {}", action_label));
-
-    if let Some(duration_seconds) = case.duration_seconds {
-        fields.push(format!(
-            "**Timeout Duration :** {}",
-            format_compact_duration(duration_seconds)
-        ));
+    execution: &serenity::ActionExecution,
+) {
+    // A rule fires one event per configured action; only the block counts.
+    if !matches!(execution.action, serenity::Action::BlockMessage { .. }) {
+        return;
     }
 
-    // Blank line separator before metadata.
-    fields.push(String::new());
-
-    fields.push(format!("**When :** <t:{}:R>", case.created_at));
+    let guild_id = execution.guild_id;
 
-    let title = format!("Word Filter Violation - #{}", case_label);
-    let description = fields.join("\n");
+    match is_word_filter_automod_rule(&data.db, guild_id.get(), execution.rule_id.get()).await {
+        Ok(true) => {}
+        Ok(false) => return,
+        Err(source) => {
+            error!(?source, "failed to read word filter automod rules");
+            return;
+        }
+    }
 
-    let embed = serenity::CreateEmbed::new()
-        .color(DEFAULT_EMBED_COLOR)
-        .title(title)
-        .description(description);
+    let matched = execution
+        .matched_keyword
+        .as_deref()
+        .or(execution.matched_content.as_deref())
+        .unwrap_or("unknown keyword");
+
+    record_filter_case(
+        ctx,
+        data,
+        guild_id,
+        execution.user_id,
+        FilterKind::Word,
+        "automod_block",
+        matched,
+    )
+    .await;
+}
 
-    serenity::ChannelId::new(channel_id)
-        .send_message(&ctx.http, serenity::CreateMessage::new().embed(embed))
-        .await?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok(())
-}
+    #[test]
+    fn redaction_matches_whole_words_only() {
+        let words = vec!["darn".to_owned()];
 
-fn is_missing_permissions(source: &serenity::Error) -> bool {
-    matches!(
-        source,
-        serenity::Error::Http(serenity::HttpError::UnsuccessfulRequest(response))
-            if response.status_code.as_u16() == 403 || response.error.code == 50013
-    )
+        assert_eq!(
+            redact_filtered_words("Well, DARN it... darned darn!", &words).as_deref(),
+            Some("Well, [filtered] it... darned [filtered]!")
+        );
+        assert_eq!(redact_filtered_words("nothing to see", &words), None);
+        assert_eq!(redact_filtered_words("darn", &[]), None);
+    }
 }
@@ -16,10 +16,13 @@ use sqlx::postgres::PgPoolOptions;
 use autumn_core::{Data, Error};
 use autumn_database::{
     CacheService, Database, MIGRATOR, cache::DEFAULT_LLM_MENTION_RATE_LIMIT_MAX_HITS,
-    cache::DEFAULT_LLM_MENTION_RATE_LIMIT_WINDOW,
+    cache::DEFAULT_LLM_MENTION_RATE_LIMIT_WINDOW, cache::DEFAULT_LOCAL_CACHE_TTL,
+    cache::DEFAULT_MEMORY_CACHE_MAX_ENTRIES,
 };
 use autumn_llm::LlmService;
 
+use events::filter_action::FilterOutcome;
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let fmt_layer = tracing_subscriber::fmt::layer().with_filter(filter_fn(|metadata| {
@@ -53,15 +56,34 @@ async fn main() -> anyhow::Result<()> {
         .await?;
     info!("PostgreSQL connection established.");
 
-    let redis_enabled = env_bool("REDIS_ENABLED", false);
     let redis_key_prefix =
         env::var("REDIS_KEY_PREFIX").unwrap_or_else(|_| "autumn:prod".to_string());
 
-    let mut cache = if redis_enabled {
+    // CACHE_BACKEND picks the backend explicitly; without it, REDIS_ENABLED
+    // keeps its old meaning.
+    let cache_backend = match env::var("CACHE_BACKEND") {
+        Ok(value) => value.trim().to_ascii_lowercase(),
+        Err(_) if env_bool("REDIS_ENABLED", false) => "redis".to_string(),
+        Err(_) => "none".to_string(),
+    };
+
+    let max_entries = env_u64(
+        "CACHE_MEMORY_MAX_ENTRIES",
+        DEFAULT_MEMORY_CACHE_MAX_ENTRIES as u64,
+    );
+    let max_entries = usize::try_from(max_entries).unwrap_or(usize::MAX);
+
+    let mut cache = if cache_backend == "memory" {
+        info!(max_entries, "In-memory cache enabled.");
+        CacheService::memory(redis_key_prefix.clone(), max_entries)
+    } else if cache_backend == "redis" {
         match env::var("REDIS_URL") {
             Ok(redis_url) => match CacheService::redis(&redis_url, redis_key_prefix.clone()) {
-                Ok(cache) => {
-                    info!(key_prefix = %redis_key_prefix, "Redis cache enabled.");
+                Ok(mut cache) => {
+                    let local_ttl_seconds =
+                        env_u64("CACHE_LOCAL_TTL_SECONDS", DEFAULT_LOCAL_CACHE_TTL.as_secs());
+                    cache.configure_local_tier(Duration::from_secs(local_ttl_seconds), max_entries);
+                    info!(key_prefix = %redis_key_prefix, local_ttl_seconds, "Redis cache enabled.");
                     cache
                 }
                 Err(err) => {
@@ -70,12 +92,15 @@ async fn main() -> anyhow::Result<()> {
                 }
             },
             Err(_) => {
-                warn!(key_prefix = %redis_key_prefix, "REDIS_ENABLED=true but REDIS_URL is missing; continuing with DB-only mode.");
+                warn!(key_prefix = %redis_key_prefix, "Redis cache selected but REDIS_URL is missing; continuing with DB-only mode.");
                 CacheService::disabled(redis_key_prefix.clone())
             }
         }
     } else {
-        info!("Redis cache disabled (set REDIS_ENABLED=true to enable).");
+        if cache_backend != "none" {
+            warn!(cache_backend = %cache_backend, "Unknown CACHE_BACKEND; expected redis, memory, or none.");
+        }
+        info!("Cache disabled (set CACHE_BACKEND=redis or CACHE_BACKEND=memory to enable).");
         CacheService::disabled(redis_key_prefix.clone())
     };
 
@@ -106,15 +131,19 @@ async fn main() -> anyhow::Result<()> {
         } else {
             info!("Redis cache health check passed.");
         }
+
+        cache.spawn_invalidation_listener();
     }
 
     let db = Database::with_cache(db_pool, cache);
 
     let llm = LlmService::from_env_optional()?;
-    if llm.is_some() {
-        info!("LLM integration enabled.");
+    if let Some(llm) = &llm {
+        info!(provider = llm.provider_name(), "LLM integration enabled.");
     } else {
-        info!("LLM integration disabled (missing/empty OLLAMA_* vars or OLLAMA_ENABLED=false).");
+        info!(
+            "LLM integration disabled (missing/empty OLLAMA_* or OPENAI_* vars, or OLLAMA_ENABLED=false)."
+        );
     }
 
     let auto_run_migrations = env_bool("AUTO_RUN_MIGRATIONS", true);
@@ -127,7 +156,10 @@ async fn main() -> anyhow::Result<()> {
 
     let intents = serenity::GatewayIntents::GUILDS
         | serenity::GatewayIntents::GUILD_MESSAGES
-        | serenity::GatewayIntents::MESSAGE_CONTENT;
+        | serenity::GatewayIntents::MESSAGE_CONTENT
+        | serenity::GatewayIntents::AUTO_MODERATION_EXECUTION
+        | serenity::GatewayIntents::GUILD_MODERATION;
+    autumn_utils::permissions::set_gateway_intents(intents);
 
     let framework = poise::Framework::builder()
         .options(poise::FrameworkOptions {
@@ -136,9 +168,16 @@ async fn main() -> anyhow::Result<()> {
                 Box::pin(handle_event(ctx, event, framework, data))
             },
             on_error: |error| Box::pin(on_error(error)),
+            // Command rules and cooldowns. Cooldowns are enforced through the
+            // shared cache so they hold across instances and restarts; poise's
+            // in-process tracking is bypassed.
+            command_check: Some(|ctx| Box::pin(autumn_commands::checks::command_check(ctx))),
+            manual_cooldowns: true,
+            // Each guild's own prefix, cached. A mention always works too, so
+            // a forgotten prefix can be recovered with `@Autumn prefix`.
             prefix_options: poise::PrefixFrameworkOptions {
-                prefix: Some(autumn_utils::COMMAND_PREFIX.to_string()),
-                mention_as_prefix: false,
+                dynamic_prefix: Some(|ctx| Box::pin(autumn_commands::prefix::dynamic_prefix(ctx))),
+                mention_as_prefix: true,
                 ..Default::default()
             },
             ..Default::default()
@@ -202,7 +241,8 @@ async fn on_error(error: poise::FrameworkError<'_, Data, Error>) {
                 .await;
         }
         poise::FrameworkError::ArgumentParse { ctx, input, .. } => {
-            let usage = format!("Usage: `!{}`", ctx.command().qualified_name);
+            let prefix = autumn_commands::prefix::command_prefix(ctx).await;
+            let usage = format!("Usage: `{}{}`", prefix, ctx.command().qualified_name);
             let description = if let Some(input) = input {
                 format!("Invalid argument: `{}`\n{}", input, usage)
             } else {
@@ -228,9 +268,32 @@ async fn handle_event(
 ) -> Result<(), Error> {
     match event {
         serenity::FullEvent::Message { new_message } => {
-            events::word_filter::handle_message_word_filter(ctx, data, new_message).await;
+            // The first filter to match settles the message, so one message
+            // can't earn two cases; once it's removed nothing else acts on it.
+            let mut outcome =
+                events::word_filter::handle_message_word_filter(ctx, data, new_message).await;
+            if outcome == FilterOutcome::Clean {
+                outcome = events::attachment_filter::handle_message_attachment_filter(
+                    ctx,
+                    data,
+                    new_message,
+                )
+                .await;
+            }
             events::userlog::handle_message_create_userlog(data, new_message).await;
-            events::llm_events::handle_message_mention_llm(ctx, data, new_message).await?;
+            if outcome != FilterOutcome::Removed {
+                events::ai_moderation::handle_message_ai_moderation(ctx, data, new_message).await;
+                events::llm_events::handle_message_mention_llm(ctx, data, new_message).await?;
+            }
+        }
+        serenity::FullEvent::InteractionCreate { interaction } => {
+            events::ai_moderation::handle_review_interaction(ctx, data, interaction).await;
+        }
+        serenity::FullEvent::GuildAuditLogEntryCreate { entry, guild_id } => {
+            events::anti_nuke::handle_audit_log_entry(ctx, data, entry, *guild_id).await;
+        }
+        serenity::FullEvent::AutoModActionExecution { execution } => {
+            events::word_filter::handle_automod_action_execution(ctx, data, execution).await;
         }
         serenity::FullEvent::MessageUpdate { event, .. } => {
             events::userlog::handle_message_update_userlog(ctx, data, event).await;
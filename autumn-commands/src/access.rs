@@ -0,0 +1,240 @@
+//! Who may run which command.
+//!
+//! Each command's `META.required_permissions` is checked once, by the global
+//! command check, before the command runs. Permissions are guild-wide,
+//! except for `channel_scoped` commands, which are resolved in the invoking
+//! channel so overwrites can grant or revoke them there alone.
+//! Per-guild command rules (by role, user or channel) come first: a deny
+//! stops the command, an allow lets it run without the Discord permission.
+//! Refusals are silent, as a missing permission always has been.
+//!
+//! A command disabled with `!command disable`, guild-wide or in the invoking
+//! channel (a thread's parent, for threads), is refused for everyone,
+//! administrators included. Slash commands get an ephemeral note so the
+//! interaction doesn't just fail.
+
+use poise::serenity_prelude as serenity;
+
+use crate::{CommandMeta, command_meta};
+use autumn_core::{Context, Error};
+use autumn_database::Database;
+use autumn_database::impls::command_acl::get_command_acl_rules;
+use autumn_database::impls::disabled_commands::get_disabled_commands;
+use autumn_database::model::command_acl::{CommandAclRule, evaluate_command_acl};
+use autumn_database::model::disabled_commands::{DisabledCommand, is_command_disabled};
+use autumn_utils::permissions::{
+    has_user_permission, resolve_channel_permissions, resolve_parent_channel,
+    resolve_user_permissions,
+};
+
+/// Invocation data marking that a rule allowed this invocation.
+struct AllowedByRule;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    /// Turned off in this guild or channel.
+    Disabled,
+    Denied,
+    /// Only because a command rule allows it.
+    AllowedByRule,
+    Allowed,
+}
+
+/// The author's permissions, the guild's command rules and its disabled
+/// commands, resolved once so many commands can be checked (e.g. for `!help`).
+pub struct CommandAccess {
+    permissions: serenity::Permissions,
+    /// In the invoking channel, for `channel_scoped` commands.
+    channel_permissions: serenity::Permissions,
+    rules: Vec<CommandAclRule>,
+    disabled: Vec<DisabledCommand>,
+    role_ids: Vec<u64>,
+    /// The invoking channel, or its parent for a thread, so threads follow
+    /// their channel's rules.
+    channel_id: u64,
+    user_id: u64,
+}
+
+impl CommandAccess {
+    pub async fn load(ctx: Context<'_>, guild_id: serenity::GuildId) -> Result<Self, Error> {
+        let mut access = Self::for_member(
+            ctx,
+            &ctx.data().db,
+            guild_id,
+            ctx.channel_id(),
+            ctx.author().id,
+            Vec::new(),
+        )
+        .await?;
+
+        // Roles only matter to rules, so skip looking them up otherwise.
+        if !access.rules.is_empty()
+            && let Some(member) = ctx.author_member().await
+        {
+            access
+                .role_ids
+                .extend(member.roles.iter().map(|id| id.get()));
+        }
+
+        Ok(access)
+    }
+
+    /// Access for `user_id`, who holds `role_ids`, in `channel_id`, outside
+    /// a command invocation (e.g. for a mention). Everyone holds `@everyone`,
+    /// whose role id is the guild's, so it's added here.
+    pub async fn for_member(
+        cache_http: impl serenity::CacheHttp,
+        db: &Database,
+        guild_id: serenity::GuildId,
+        channel_id: serenity::ChannelId,
+        user_id: serenity::UserId,
+        mut role_ids: Vec<u64>,
+    ) -> Result<Self, Error> {
+        role_ids.push(guild_id.get());
+
+        let cache = db.cache();
+        let permissions = resolve_user_permissions(&cache_http, cache, guild_id, user_id).await?;
+        let channel_permissions =
+            resolve_channel_permissions(&cache_http, cache, guild_id, channel_id, user_id).await?;
+
+        let rules = get_command_acl_rules(db, guild_id.get()).await?;
+        let disabled = get_disabled_commands(db, guild_id.get()).await?;
+        let channel_id = resolve_parent_channel(&cache_http, cache, guild_id, channel_id).await?;
+
+        Ok(Self {
+            permissions,
+            channel_permissions,
+            rules,
+            disabled,
+            role_ids,
+            channel_id: channel_id.get(),
+            user_id: user_id.get(),
+        })
+    }
+
+    /// Administrators are never denied by a rule, so a bad rule can't lock
+    /// anyone out of fixing it; rules never stand in for `ADMINISTRATOR`.
+    pub fn decide(&self, meta: &CommandMeta) -> Access {
+        if is_command_disabled(&self.disabled, meta.name, self.channel_id) {
+            return Access::Disabled;
+        }
+
+        let is_admin = self
+            .permissions
+            .contains(serenity::Permissions::ADMINISTRATOR);
+        let permissions = if meta.channel_scoped {
+            self.channel_permissions
+        } else {
+            self.permissions
+        };
+        let has_required = meta
+            .required_permissions
+            .is_none_or(|required| permissions.contains(required));
+        let grantable = meta
+            .required_permissions
+            .is_none_or(|required| !required.contains(serenity::Permissions::ADMINISTRATOR));
+
+        let rule = evaluate_command_acl(
+            &self.rules,
+            meta.name,
+            self.channel_id,
+            self.user_id,
+            &self.role_ids,
+        );
+        match rule {
+            Some(false) if !is_admin => Access::Denied,
+            Some(true) if !has_required && grantable => Access::AllowedByRule,
+            _ if has_required => Access::Allowed,
+            _ => Access::Denied,
+        }
+    }
+
+    pub fn can_use(&self, meta: &CommandMeta) -> bool {
+        !matches!(self.decide(meta), Access::Disabled | Access::Denied)
+    }
+}
+
+/// Global command check for disabled commands, command rules and required
+/// permissions. Outside guilds commands run as-is and answer for themselves.
+pub async fn check_command_access(ctx: Context<'_>) -> Result<bool, Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(true);
+    };
+
+    let command = ctx.command();
+    let root_name = command
+        .qualified_name
+        .split_whitespace()
+        .next()
+        .unwrap_or(&command.name);
+    let Some(meta) = command_meta(root_name) else {
+        return Ok(true);
+    };
+
+    match CommandAccess::load(ctx, guild_id).await?.decide(meta) {
+        Access::Disabled => {
+            if let poise::Context::Application(_) = ctx {
+                ctx.send(
+                    poise::CreateReply::default()
+                        .content(format!("`/{}` is disabled here.", meta.name))
+                        .ephemeral(true),
+                )
+                .await?;
+            }
+            Ok(false)
+        }
+        Access::Denied => Ok(false),
+        Access::AllowedByRule => {
+            ctx.set_invocation_data(AllowedByRule).await;
+            Ok(true)
+        }
+        Access::Allowed => Ok(true),
+    }
+}
+
+/// For a permission a command needs beyond its `META` (e.g. one subcommand
+/// needing more): the author needs `permissions`, unless a command rule
+/// allowed them. Rules never stand in for `ADMINISTRATOR`.
+pub(crate) async fn has_command_permission(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    permissions: serenity::Permissions,
+) -> Result<bool, Error> {
+    if !permissions.contains(serenity::Permissions::ADMINISTRATOR)
+        && ctx.invocation_data::<AllowedByRule>().await.is_some()
+    {
+        return Ok(true);
+    }
+
+    has_user_permission(
+        ctx,
+        ctx.data().db.cache(),
+        guild_id,
+        ctx.author().id,
+        permissions,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channel_overwrites_only_unlock_channel_scoped_commands() {
+        let access = CommandAccess {
+            permissions: serenity::Permissions::empty(),
+            channel_permissions: serenity::Permissions::MANAGE_MESSAGES,
+            rules: Vec::new(),
+            disabled: Vec::new(),
+            role_ids: Vec::new(),
+            channel_id: 1,
+            user_id: 2,
+        };
+
+        let warn = command_meta("warn").unwrap();
+        let purge = command_meta("purge").unwrap();
+        assert_eq!(access.decide(warn), Access::Denied);
+        assert_eq!(access.decide(purge), Access::Allowed);
+    }
+}
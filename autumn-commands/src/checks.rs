@@ -0,0 +1,15 @@
+//! The framework-wide `command_check`, run before every command.
+
+use autumn_core::{Context, Error};
+
+use crate::access::check_command_access;
+use crate::cooldowns::check_command_cooldowns;
+
+/// Access runs first so a refused invocation doesn't start a cooldown.
+pub async fn command_check(ctx: Context<'_>) -> Result<bool, Error> {
+    if !check_command_access(ctx).await? {
+        return Ok(false);
+    }
+
+    check_command_cooldowns(ctx).await
+}
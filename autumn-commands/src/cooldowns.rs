@@ -0,0 +1,76 @@
+//! Command cooldowns shared across bot instances.
+//!
+//! Commands declare cooldowns with poise's `user_cooldown`, `channel_cooldown`,
+//! `guild_cooldown`, `member_cooldown` and `global_cooldown` attributes. poise's
+//! own tracking is in-process only, so the framework runs with
+//! `manual_cooldowns` and this check enforces them through the cache instead.
+//! Without Redis the cache keeps rate limit state in process, so cooldowns
+//! still apply to a single instance even with `CACHE_BACKEND=none`.
+
+use std::time::Duration;
+
+use tracing::warn;
+
+use autumn_core::{Context, Error};
+use autumn_database::cache::{RateLimiter, command_cooldown_key};
+use autumn_utils::formatting::format_compact_duration;
+
+/// Global command check: refuse the invocation while any of the command's
+/// cooldowns is running. Cache errors let the command through.
+pub async fn check_command_cooldowns(ctx: Context<'_>) -> Result<bool, Error> {
+    let command = ctx.command();
+    let config = command
+        .cooldown_config
+        .read()
+        .map(|config| config.clone())
+        .unwrap_or_default();
+
+    let user_id = ctx.author().id.get();
+    let guild_id = ctx.guild_id().map(|id| id.get());
+    let buckets = [
+        (config.global, Some("global".to_owned())),
+        (config.user, Some(format!("user:{user_id}"))),
+        (
+            config.channel,
+            Some(format!("channel:{}", ctx.channel_id().get())),
+        ),
+        (config.guild, guild_id.map(|id| format!("guild:{id}"))),
+        (
+            config.member,
+            guild_id.map(|id| format!("guild:{id}:user:{user_id}")),
+        ),
+    ];
+
+    let cache = ctx.data().db.cache();
+    let mut wait = Duration::ZERO;
+    for (cooldown, bucket) in buckets {
+        let (Some(cooldown), Some(bucket)) = (cooldown, bucket) else {
+            continue;
+        };
+
+        let key = command_cooldown_key(cache, &bucket, &command.qualified_name);
+        let limiter = RateLimiter::SlidingWindow {
+            window: cooldown,
+            max_hits: 1,
+        };
+        match limiter.check(cache, &key).await {
+            Ok(decision) if !decision.allowed => wait = wait.max(decision.retry_after),
+            Ok(_) => {}
+            Err(source) => warn!(?source, "failed to check command cooldown"),
+        }
+    }
+
+    if wait.is_zero() {
+        return Ok(true);
+    }
+
+    let wait_secs = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+    ctx.say(format!(
+        "`{}` is on cooldown. Try again in {}.",
+        command.qualified_name,
+        format_compact_duration(wait_secs.max(1))
+    ))
+    .await?;
+
+    Ok(false)
+}
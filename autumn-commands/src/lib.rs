@@ -1,71 +1,108 @@
+pub mod access;
+pub mod checks;
+pub mod cooldowns;
 pub mod moderation;
+pub mod prefix;
 pub mod utility;
 
 use autumn_core::{Data, Error};
+use poise::serenity_prelude as serenity;
 
+/// Everything `!help`, `!usage` and the permission check know about a
+/// command. Each command module defines its own `META`.
 pub struct CommandMeta {
     pub name: &'static str,
     pub desc: &'static str,
     pub category: &'static str,
     pub usage: &'static str,
+    /// Discord permissions needed to run the command, unless a command rule
+    /// allows it. Checked once, centrally, before the command runs.
+    pub required_permissions: Option<serenity::Permissions>,
+    /// Check `required_permissions` in the invoking channel, with its
+    /// overwrites, instead of guild-wide. Only for commands that act on that
+    /// channel alone.
+    pub channel_scoped: bool,
+    pub examples: &'static [&'static str],
 }
 
-pub const COMMANDS: &[CommandMeta] = &[
-    utility::ping::META,
-    utility::universe::META,
-    utility::help::META,
-    utility::usage::META,
-    utility::pagetest::META,
-    moderation::aitoggle::META,
-    moderation::ban::META,
-    moderation::unban::META,
-    moderation::kick::META,
-    moderation::timeout::META,
-    moderation::untimeout::META,
-    moderation::warn::META,
-    moderation::warnings::META,
-    moderation::unwarn::META,
-    moderation::purge::META,
-    moderation::permissions::META,
-    moderation::terminate::META,
-    moderation::modlogs::META,
-    moderation::modlogchannel::META,
-    moderation::userlogs::META,
-    moderation::userlogchannel::META,
-    moderation::case::META,
-    moderation::notes::META,
-    moderation::wordfilter::META,
-    moderation::escalation::META,
-    moderation::setup::META,
-];
+/// Lists every command once, as `group::module`, where the module defines
+/// `META` and a command function named after itself. Generates [`COMMANDS`]
+/// and [`commands`] so the two can't drift apart.
+macro_rules! command_registry {
+    ($($group:ident::$module:ident),* $(,)?) => {
+        pub const COMMANDS: &[CommandMeta] = &[$($group::$module::META),*];
 
-pub fn commands() -> Vec<poise::Command<Data, Error>> {
-    vec![
-        utility::ping::ping(),
-        utility::universe::universe(),
-        utility::help::help(),
-        utility::usage::usage(),
-        utility::pagetest::pagetest(),
-        moderation::aitoggle::aitoggle(),
-        moderation::ban::ban(),
-        moderation::unban::unban(),
-        moderation::kick::kick(),
-        moderation::timeout::timeout(),
-        moderation::untimeout::untimeout(),
-        moderation::warn::warn(),
-        moderation::warnings::warnings(),
-        moderation::unwarn::unwarn(),
-        moderation::purge::purge(),
-        moderation::permissions::permissions(),
-        moderation::terminate::terminate(),
-        moderation::modlogs::modlogs(),
-        moderation::modlogchannel::modlogchannel(),
-        moderation::userlogs::userlogs(),
-        moderation::userlogchannel::userlogchannel(),
-        moderation::case::case(),
-        moderation::notes::notes(),
-        moderation::wordfilter::wordfilter(),
-        moderation::escalation::escalation(),
-        moderation::setup::setup(),
-    ]
+        pub fn commands() -> Vec<poise::Command<Data, Error>> {
+            vec![$(with_meta($group::$module::$module(), &$group::$module::META)),*]
+        }
+    };
+}
+
+command_registry! {
+    utility::ping,
+    utility::universe,
+    utility::help,
+    utility::usage,
+    utility::pagetest,
+    utility::forgetme,
+    utility::prefix,
+    moderation::aitoggle,
+    moderation::aiprompt,
+    moderation::aimod,
+    moderation::aiusage,
+    moderation::aihistory,
+    moderation::ban,
+    moderation::unban,
+    moderation::kick,
+    moderation::timeout,
+    moderation::untimeout,
+    moderation::warn,
+    moderation::warnings,
+    moderation::unwarn,
+    moderation::purge,
+    moderation::permissions,
+    moderation::terminate,
+    moderation::modlogs,
+    moderation::modlogchannel,
+    moderation::userlogs,
+    moderation::userlogchannel,
+    moderation::case,
+    moderation::notes,
+    moderation::summarize,
+    moderation::wordfilter,
+    moderation::attachmentfilter,
+    moderation::escalation,
+    moderation::setup,
+    moderation::safety,
+    moderation::antinuke,
+    moderation::command,
+}
+
+/// The metadata for a top-level command name.
+pub fn command_meta(name: &str) -> Option<&'static CommandMeta> {
+    COMMANDS.iter().find(|meta| meta.name == name)
+}
+
+/// Fill in what poise shows for slash commands from the command's metadata.
+fn with_meta(
+    mut command: poise::Command<Data, Error>,
+    meta: &CommandMeta,
+) -> poise::Command<Data, Error> {
+    if command.description.is_none() {
+        command.description = Some(meta.desc.to_owned());
+    }
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_names_match_command_names() {
+        let names: Vec<String> = commands().into_iter().map(|command| command.name).collect();
+        let meta_names: Vec<&str> = COMMANDS.iter().map(|meta| meta.name).collect();
+
+        assert_eq!(names, meta_names);
+    }
 }
@@ -11,13 +11,15 @@ use autumn_utils::embed::DEFAULT_EMBED_COLOR;
 use autumn_utils::formatting::{
     action_display_name, event_display_name, format_compact_duration, parse_case_label,
 };
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "case",
     desc: "View or edit a moderation case.",
     category: "moderation",
     usage: "!case <case_id> [reason|note] [text]",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    channel_scoped: false,
+    examples: &["!case W3", "!case B1 reason Spamming invites"],
 };
 
 #[poise::command(prefix_command, slash_command, category = "Moderation")]
@@ -34,19 +36,8 @@ pub async fn case(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_MESSAGES,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(case_id) = case_id else {
-        ctx.say(usage_message(META.usage)).await?;
+        ctx.say(usage_message(ctx, META.usage).await).await?;
         return Ok(());
     };
 
@@ -58,7 +49,7 @@ pub async fn case(
     if let Some(action) = action.as_deref().map(str::trim) {
         if action.eq_ignore_ascii_case("reason") {
             let Some(new_reason) = value.map(|entry| entry.trim().to_owned()) else {
-                ctx.say("Usage: `!case <case_id> reason <new reason>`")
+                ctx.say(usage_message(ctx, "!case <case_id> reason <new reason>").await)
                     .await?;
                 return Ok(());
             };
@@ -106,7 +97,8 @@ pub async fn case(
 
         if action.eq_ignore_ascii_case("note") {
             let Some(note) = value.map(|entry| entry.trim().to_owned()) else {
-                ctx.say("Usage: `!case <case_id> note <note text>`").await?;
+                ctx.say(usage_message(ctx, "!case <case_id> note <note text>").await)
+                    .await?;
                 return Ok(());
             };
 
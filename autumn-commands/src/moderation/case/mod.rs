@@ -1,5 +1,6 @@
 pub mod case;
 pub mod modlogs;
 pub mod notes;
+pub mod summarize;
 pub mod userlogs;
 pub mod warnings;
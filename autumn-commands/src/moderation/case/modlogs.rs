@@ -6,13 +6,15 @@ use autumn_core::{Context, Error};
 use autumn_database::impls::cases::{CaseFilters, list_recent_cases};
 use autumn_utils::formatting::{action_display_name, format_case_label, format_compact_duration};
 use autumn_utils::pagination::paginate_embed_pages;
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "modlogs",
     desc: "View recent moderation actions.",
     category: "moderation",
     usage: "!modlogs [target_user] [moderator] [action]",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    channel_scoped: false,
+    examples: &["!modlogs @user", "!modlogs @user @moderator ban"],
 };
 
 const CASES_PER_PAGE: usize = 5;
@@ -29,22 +31,11 @@ pub async fn modlogs(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_MESSAGES,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     if action
         .as_deref()
         .is_some_and(|value| value.trim().is_empty())
     {
-        ctx.say(usage_message(META.usage)).await?;
+        ctx.say(usage_message(ctx, META.usage).await).await?;
         return Ok(());
     }
 
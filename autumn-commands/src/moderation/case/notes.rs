@@ -2,18 +2,20 @@ use poise::serenity_prelude as serenity;
 use std::time::Duration;
 
 use crate::CommandMeta;
-use crate::moderation::embeds::guild_only_message;
+use crate::moderation::embeds::{guild_only_message, usage_message};
 use autumn_core::{Context, Error};
 use autumn_database::impls::notes::{add_user_note, clear_user_notes, list_user_notes};
 use autumn_utils::confirmation::{prompt_confirm_decline, resolve_confirmation_result};
 use autumn_utils::pagination::paginate_embed_pages;
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "notes",
     desc: "Add or view a moderator note for a user.",
     category: "moderation",
     usage: "!notes <user> [note|clear]",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    channel_scoped: false,
+    examples: &["!notes @user", "!notes @user Prefers DMs for warnings"],
 };
 
 const NOTES_PER_PAGE: usize = 5;
@@ -32,19 +34,8 @@ pub async fn notes(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_MESSAGES,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(user) = user else {
-        ctx.say(format!("Usage: `{}`", META.usage)).await?;
+        ctx.say(usage_message(ctx, META.usage).await).await?;
         return Ok(());
     };
 
@@ -0,0 +1,145 @@
+use std::time::Instant;
+
+use poise::serenity_prelude as serenity;
+use tracing::error;
+
+use crate::CommandMeta;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_llm::summary::{load_moderation_record, unknown_case_citations};
+use autumn_llm::usage::{UsageSource, daily_quota_refusal, record_usage};
+use autumn_utils::embed::DEFAULT_EMBED_COLOR;
+use autumn_utils::time::now_unix_secs;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "summarize",
+    desc: "Summarize a user's moderation history with AI.",
+    category: "moderation",
+    usage: "!summarize <user>",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    channel_scoped: false,
+    examples: &["!summarize @user"],
+};
+
+const EMBED_DESCRIPTION_LIMIT: usize = 4096;
+
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    member_cooldown = 30
+)]
+pub async fn summarize(
+    ctx: Context<'_>,
+    #[description = "User to summarize"] user: Option<serenity::User>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(user) = user else {
+        ctx.say(usage_message(ctx, META.usage).await).await?;
+        return Ok(());
+    };
+
+    let Some(llm) = ctx.data().llm.as_ref() else {
+        ctx.say("AI features are not configured on this bot.")
+            .await?;
+        return Ok(());
+    };
+
+    let record = load_moderation_record(&ctx.data().db, guild_id.get(), user.id.get()).await?;
+    if record.is_empty() {
+        ctx.say(format!(
+            "No cases, notes, or logged messages found for <@{}>.",
+            user.id.get()
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let usage_source = UsageSource {
+        guild_id: guild_id.get(),
+        channel_id: ctx.channel_id().get(),
+        user_id: ctx.author().id.get(),
+        kind: "summarize",
+    };
+    // A failed quota lookup shouldn't block the command, as with mentions.
+    match daily_quota_refusal(&ctx.data().db, usage_source, now_unix_secs()).await {
+        Ok(Some(refusal)) => {
+            ctx.say(refusal).await?;
+            return Ok(());
+        }
+        Ok(None) => {}
+        Err(source) => error!(?source, "failed to check llm quota"),
+    }
+
+    ctx.defer_or_broadcast().await?;
+
+    let user_label = format!("{} (ID {})", user.name, user.id.get());
+    let started = Instant::now();
+    let generated = llm.summarize_moderation_record(&record, &user_label).await;
+    record_usage(
+        &ctx.data().db,
+        usage_source,
+        started.elapsed(),
+        generated.as_ref().ok().map(|reply| reply.usage),
+    )
+    .await;
+
+    let summary = match generated {
+        Ok(summary) if !summary.content.is_empty() => summary.content,
+        Ok(_) => {
+            ctx.say("The AI returned an empty summary. Try again in a moment.")
+                .await?;
+            return Ok(());
+        }
+        Err(source) => {
+            error!(?source, "moderation summary generation failed");
+            ctx.say("I ran into an LLM error. Try again in a moment.")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let mut lines = vec![
+        format!("**User :** <@{}>", user.id.get()),
+        format!("**Cases :** {}", record.cases.len()),
+        format!("**Notes :** {}", record.notes.len()),
+        format!("**Logged messages :** {}", record.user_logs.len()),
+        String::new(),
+        summary.replace('@', "@\u{200B}"),
+    ];
+
+    let unknown = unknown_case_citations(&summary, &record.case_labels());
+    if !unknown.is_empty() {
+        lines.push(String::new());
+        lines.push(format!(
+            "**Unverified citations :** {} (not in this user's cases)",
+            unknown.join(", ")
+        ));
+    }
+
+    let embed = serenity::CreateEmbed::new()
+        .title("Moderation Summary")
+        .description(truncate_description(&lines.join("\n")))
+        .footer(serenity::CreateEmbedFooter::new(
+            "AI-generated from the records above. Check the cited cases before acting.",
+        ))
+        .color(DEFAULT_EMBED_COLOR);
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+fn truncate_description(text: &str) -> String {
+    if text.chars().count() <= EMBED_DESCRIPTION_LIMIT {
+        return text.to_owned();
+    }
+
+    let mut truncated: String = text.chars().take(EMBED_DESCRIPTION_LIMIT - 1).collect();
+    truncated.push('…');
+    truncated
+}
@@ -5,13 +5,15 @@ use crate::moderation::embeds::{guild_only_message, usage_message};
 use autumn_core::{Context, Error};
 use autumn_database::impls::user_logs::{UserLogFilters, list_recent_user_logs};
 use autumn_utils::pagination::paginate_embed_pages;
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "userlogs",
     desc: "View recent user message edit/delete activity.",
     category: "moderation",
     usage: "!userlogs [target_user] [event]",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    channel_scoped: false,
+    examples: &["!userlogs @user", "!userlogs @user message_delete"],
 };
 
 const LOGS_PER_PAGE: usize = 5;
@@ -28,22 +30,11 @@ pub async fn userlogs(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_MESSAGES,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     if event
         .as_deref()
         .is_some_and(|value| value.trim().is_empty())
     {
-        ctx.say(usage_message(META.usage)).await?;
+        ctx.say(usage_message(ctx, META.usage).await).await?;
         return Ok(());
     }
 
@@ -7,13 +7,15 @@ use crate::moderation::embeds::{
 use autumn_core::{Context, Error};
 use autumn_database::impls::warnings::{now_unix_secs, warnings_since};
 use autumn_utils::pagination::paginate_embed_pages_with_icon;
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "warnings",
     desc: "Show warning history for a user in a time window.",
     category: "moderation",
     usage: "!warnings <user> [days|all]",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    channel_scoped: false,
+    examples: &["!warnings @user", "!warnings @user 30"],
 };
 
 const DEFAULT_DAYS: u64 = 30;
@@ -35,19 +37,8 @@ pub async fn warnings(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_MESSAGES,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(user) = user else {
-        ctx.say(usage_message(META.usage)).await?;
+        ctx.say(usage_message(ctx, META.usage).await).await?;
         return Ok(());
     };
 
@@ -0,0 +1,188 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_database::impls::llm_chat::{clear_llm_channel_history, list_recent_llm_chat_messages};
+use autumn_utils::pagination::paginate_embed_pages;
+use autumn_utils::parse::parse_channel_id;
+use autumn_utils::permissions::has_channel_permission;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "aihistory",
+    desc: "Show or clear the AI conversation history for a channel.",
+    category: "moderation",
+    usage: "!aihistory <show|clear> [#channel]",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    channel_scoped: false,
+    examples: &["!aihistory show", "!aihistory clear #general"],
+};
+
+/// Most history rows `show` pages through.
+const SHOW_LIMIT: u32 = 200;
+const ENTRIES_PER_PAGE: usize = 10;
+/// Characters of each message shown in the listing.
+const ENTRY_PREVIEW_CHARS: usize = 200;
+
+/// Manage the AI conversation history for a channel.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("show", "clear")
+)]
+pub async fn aihistory(ctx: Context<'_>) -> Result<(), Error> {
+    if ctx.guild_id().is_none() {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    }
+
+    ctx.say(usage_message(ctx, META.usage).await).await?;
+    Ok(())
+}
+
+/// Show the stored AI conversation for this or another channel.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn show(
+    ctx: Context<'_>,
+    #[description = "Optional channel mention/id"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(channel_id) = resolve_channel(ctx, input.as_deref()) else {
+        ctx.say("Provide a valid channel mention/id, or nothing for this channel.")
+            .await?;
+        return Ok(());
+    };
+
+    if !can_view_channel(ctx, guild_id, channel_id).await? {
+        ctx.say(format!("You can't view <#{}>.", channel_id))
+            .await?;
+        return Ok(());
+    }
+
+    let entries =
+        list_recent_llm_chat_messages(&ctx.data().db, guild_id.get(), channel_id, SHOW_LIMIT)
+            .await?;
+
+    if entries.is_empty() {
+        ctx.say(format!("<#{}> has no AI conversation history.", channel_id))
+            .await?;
+        return Ok(());
+    }
+
+    let lines: Vec<String> = entries
+        .iter()
+        .map(|entry| {
+            let speaker = match entry.role.as_str() {
+                "assistant" => "Autumn".to_owned(),
+                _ => format!("<@{}>", entry.user_id),
+            };
+            format!(
+                "<t:{}:f> {}\n> {}",
+                entry.created_at,
+                speaker,
+                preview(&entry.content)
+            )
+        })
+        .collect();
+
+    let pages: Vec<String> = lines
+        .chunks(ENTRIES_PER_PAGE)
+        .map(|chunk| {
+            format!(
+                "**Channel :** <#{}> (newest first)\n\n{}",
+                channel_id,
+                chunk.join("\n")
+            )
+        })
+        .collect();
+
+    paginate_embed_pages(ctx, "AI Conversation History", &pages, 1).await?;
+
+    Ok(())
+}
+
+/// Clear the AI conversation history and memory for this or another channel.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn clear(
+    ctx: Context<'_>,
+    #[description = "Optional channel mention/id"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(channel_id) = resolve_channel(ctx, input.as_deref()) else {
+        ctx.say("Provide a valid channel mention/id, or nothing for this channel.")
+            .await?;
+        return Ok(());
+    };
+
+    if !can_view_channel(ctx, guild_id, channel_id).await? {
+        ctx.say(format!("You can't view <#{}>.", channel_id))
+            .await?;
+        return Ok(());
+    }
+
+    let removed = clear_llm_channel_history(&ctx.data().db, guild_id.get(), channel_id).await?;
+
+    let message = if removed == 0 {
+        format!("<#{}> has no AI conversation history.", channel_id)
+    } else {
+        format!(
+            "Cleared **{}** AI message{} in <#{}>. The next conversation there starts fresh.",
+            removed,
+            if removed == 1 { "" } else { "s" },
+            channel_id
+        )
+    };
+    ctx.say(message).await?;
+
+    Ok(())
+}
+
+/// The channel named in `raw`, or the current channel when `raw` is empty.
+fn resolve_channel(ctx: Context<'_>, raw: Option<&str>) -> Option<u64> {
+    match raw.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => parse_channel_id(value),
+        None => Some(ctx.channel_id().get()),
+    }
+}
+
+/// Whether the invoker can read `channel_id`, so its history isn't shown to
+/// or cleared by someone who can't see the channel itself.
+async fn can_view_channel(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    channel_id: u64,
+) -> Result<bool, Error> {
+    has_channel_permission(
+        ctx,
+        ctx.data().db.cache(),
+        guild_id,
+        serenity::ChannelId::new(channel_id),
+        ctx.author().id,
+        serenity::Permissions::VIEW_CHANNEL,
+    )
+    .await
+}
+
+fn preview(content: &str) -> String {
+    let single_line = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    if single_line.chars().count() <= ENTRY_PREVIEW_CHARS {
+        return single_line;
+    }
+
+    let mut preview: String = single_line.chars().take(ENTRY_PREVIEW_CHARS).collect();
+    preview.push('…');
+    preview
+}
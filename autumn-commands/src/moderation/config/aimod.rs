@@ -0,0 +1,123 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_database::impls::ai_moderation::{
+    get_ai_moderation_config, set_ai_moderation_enabled, set_ai_moderation_max_per_minute,
+    set_ai_moderation_sample_percent, set_ai_moderation_threshold,
+};
+
+pub const META: CommandMeta = CommandMeta {
+    name: "aimod",
+    desc: "Configure AI moderation, which flags messages for moderator review.",
+    category: "moderation",
+    usage: "!aimod <on|off|status|threshold <50-100>|sample <1-100>|rate <per minute>>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    channel_scoped: false,
+    examples: &["!aimod on", "!aimod threshold 90"],
+};
+
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn aimod(
+    ctx: Context<'_>,
+    #[description = "on, off, status, threshold <50-100>, sample <1-100>, or rate <per minute>"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let raw = input.as_deref().map(str::trim).unwrap_or_default();
+    let mut parts = raw.split_whitespace();
+    let subcommand = parts.next().map(str::to_ascii_lowercase);
+    let value = parts.next();
+    let db = &ctx.data().db;
+
+    match subcommand.as_deref() {
+        Some("status") => {
+            let config = get_ai_moderation_config(db, guild_id.get()).await?;
+            let mut status = format!(
+                "AI moderation is **{}** for this server.\n\
+                 Messages at or above **{:.0}%** confidence are flagged for review.\n\
+                 **{}%** of messages are checked, at most **{}** per minute.",
+                if config.enabled {
+                    "enabled"
+                } else {
+                    "disabled"
+                },
+                config.threshold * 100.0,
+                config.sample_percent,
+                config.max_per_minute
+            );
+            if ctx.data().llm.is_none() {
+                status.push_str("\nAI features are not configured on this bot.");
+            }
+            ctx.say(status).await?;
+        }
+        Some(state @ ("on" | "off")) => {
+            let enabled = state == "on";
+            set_ai_moderation_enabled(db, guild_id.get(), enabled).await?;
+            ctx.say(format!(
+                "AI moderation is now **{}** for this server.",
+                if enabled { "enabled" } else { "disabled" }
+            ))
+            .await?;
+        }
+        Some("threshold") => {
+            let Some(percent) = value
+                .and_then(|value| value.trim_end_matches('%').parse::<u8>().ok())
+                .filter(|percent| (50..=100).contains(percent))
+            else {
+                ctx.say(usage_message(ctx, "!aimod threshold <50-100>").await)
+                    .await?;
+                return Ok(());
+            };
+
+            set_ai_moderation_threshold(db, guild_id.get(), f32::from(percent) / 100.0).await?;
+            ctx.say(format!(
+                "Messages at or above **{}%** confidence will now be flagged for review.",
+                percent
+            ))
+            .await?;
+        }
+        Some("sample") => {
+            let Some(percent) = value
+                .and_then(|value| value.trim_end_matches('%').parse::<u8>().ok())
+                .filter(|percent| (1..=100).contains(percent))
+            else {
+                ctx.say(usage_message(ctx, "!aimod sample <1-100>").await)
+                    .await?;
+                return Ok(());
+            };
+
+            set_ai_moderation_sample_percent(db, guild_id.get(), percent).await?;
+            ctx.say(format!("**{}%** of messages will now be checked.", percent))
+                .await?;
+        }
+        Some("rate") => {
+            let Some(max_per_minute) = value
+                .and_then(|value| value.parse::<u32>().ok())
+                .filter(|max| *max > 0)
+            else {
+                ctx.say(usage_message(ctx, "!aimod rate <per minute>").await)
+                    .await?;
+                return Ok(());
+            };
+
+            set_ai_moderation_max_per_minute(db, guild_id.get(), max_per_minute).await?;
+            ctx.say(format!(
+                "At most **{}** messages per minute will now be checked.",
+                max_per_minute
+            ))
+            .await?;
+        }
+        _ => {
+            ctx.say(usage_message(ctx, META.usage).await).await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,246 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_database::impls::llm_prompt::{
+    LLM_PROMPT_MAX_CHARS, get_llm_system_prompt, reset_llm_system_prompt, set_llm_system_prompt,
+};
+use autumn_llm::prompt::default_system_prompt;
+use autumn_utils::embed::DEFAULT_EMBED_COLOR;
+use autumn_utils::parse::parse_channel_id;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "aiprompt",
+    desc: "Set, view, or reset the AI system prompt for this server or a channel.",
+    category: "moderation",
+    usage: "!aiprompt <set|show|reset> [#channel] [prompt]",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    channel_scoped: false,
+    examples: &[
+        "!aiprompt set Be brief and friendly.",
+        "!aiprompt show #general",
+    ],
+};
+
+/// Characters of the prompt shown in the confirmation after `set`.
+const PREVIEW_CHARS: usize = 300;
+
+/// Manage the AI system prompt for this server or a channel.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands("set", "show", "reset")
+)]
+pub async fn aiprompt(ctx: Context<'_>) -> Result<(), Error> {
+    if ctx.guild_id().is_none() {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    }
+
+    ctx.say(usage_message(ctx, META.usage).await).await?;
+    Ok(())
+}
+
+/// Set the AI system prompt for this server, or for one channel.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn set(
+    ctx: Context<'_>,
+    #[description = "Optional #channel, followed by the prompt"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let raw = input.as_deref().map(str::trim).unwrap_or_default();
+    let (channel_id, prompt) = split_channel_prefix(raw);
+
+    if prompt.is_empty() {
+        ctx.say(usage_message(ctx, "!aiprompt set [#channel] <prompt>").await)
+            .await?;
+        return Ok(());
+    }
+
+    let prompt_chars = prompt.chars().count();
+    if prompt_chars > LLM_PROMPT_MAX_CHARS {
+        ctx.say(format!(
+            "That prompt is **{}** characters long. The limit is **{}**.",
+            prompt_chars, LLM_PROMPT_MAX_CHARS
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    set_llm_system_prompt(
+        &ctx.data().db,
+        guild_id.get(),
+        channel_id,
+        prompt,
+        ctx.author().id.get(),
+    )
+    .await?;
+
+    let preview = if prompt_chars > PREVIEW_CHARS {
+        let mut preview: String = prompt.chars().take(PREVIEW_CHARS).collect();
+        preview.push('…');
+        preview
+    } else {
+        prompt.to_owned()
+    };
+
+    let embed = serenity::CreateEmbed::new()
+        .title("AI Prompt Updated")
+        .description(format!(
+            "**Scope :** {}\n**Length :** {}/{} characters\n\n**Preview**\n{}",
+            scope_display(channel_id),
+            prompt_chars,
+            LLM_PROMPT_MAX_CHARS,
+            preview
+        ))
+        .color(DEFAULT_EMBED_COLOR);
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Show the AI system prompt in effect for this server or a channel.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn show(
+    ctx: Context<'_>,
+    #[description = "Optional channel mention/id"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Ok(channel_id) = parse_optional_channel(input.as_deref()) else {
+        ctx.say("Provide a valid channel mention/id, or nothing for the server prompt.")
+            .await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    let channel_prompt = match channel_id {
+        Some(channel_id) => get_llm_system_prompt(db, guild_id.get(), Some(channel_id)).await?,
+        None => None,
+    };
+    let guild_prompt = get_llm_system_prompt(db, guild_id.get(), None).await?;
+
+    let (source, prompt, updated) = match (&channel_prompt, &guild_prompt) {
+        (Some(entry), _) => (
+            "Channel override",
+            entry.prompt.as_str(),
+            Some((entry.updated_by, entry.updated_at)),
+        ),
+        (None, Some(entry)) => (
+            "Server prompt",
+            entry.prompt.as_str(),
+            Some((entry.updated_by, entry.updated_at)),
+        ),
+        (None, None) => ("Default", default_system_prompt(), None),
+    };
+
+    let mut lines = vec![
+        format!("**Scope :** {}", scope_display(channel_id)),
+        format!("**Source :** {}", source),
+    ];
+    if let Some((updated_by, updated_at)) = updated {
+        lines.push(format!(
+            "**Updated :** <t:{}:R> by <@{}>",
+            updated_at, updated_by
+        ));
+    }
+    lines.push(String::new());
+    lines.push(prompt.to_owned());
+
+    let embed = serenity::CreateEmbed::new()
+        .title("AI Prompt")
+        .description(truncate_embed_description(&lines.join("\n")))
+        .color(DEFAULT_EMBED_COLOR);
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Reset the AI system prompt for this server or a channel.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn reset(
+    ctx: Context<'_>,
+    #[description = "Optional channel mention/id"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Ok(channel_id) = parse_optional_channel(input.as_deref()) else {
+        ctx.say("Provide a valid channel mention/id, or nothing for the server prompt.")
+            .await?;
+        return Ok(());
+    };
+
+    let removed = reset_llm_system_prompt(&ctx.data().db, guild_id.get(), channel_id).await?;
+
+    let message = match (removed, channel_id) {
+        (true, Some(channel_id)) => format!(
+            "Removed the AI prompt override for <#{}>. It now uses the server prompt.",
+            channel_id
+        ),
+        (true, None) => "Reset the server AI prompt to the default.".to_owned(),
+        (false, Some(channel_id)) => format!("<#{}> has no AI prompt override.", channel_id),
+        (false, None) => "This server is already using the default AI prompt.".to_owned(),
+    };
+    ctx.say(message).await?;
+
+    Ok(())
+}
+
+/// Split a leading `<#channel>` mention off the input.
+fn split_channel_prefix(raw: &str) -> (Option<u64>, &str) {
+    let (first, rest) = raw.split_once(char::is_whitespace).unwrap_or((raw, ""));
+    if first.starts_with("<#")
+        && let Some(channel_id) = parse_channel_id(first)
+    {
+        return (Some(channel_id), rest.trim());
+    }
+
+    (None, raw)
+}
+
+/// `Ok(None)` for no input, `Err(())` for input that is not a channel.
+fn parse_optional_channel(raw: Option<&str>) -> Result<Option<u64>, ()> {
+    match raw.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => parse_channel_id(value).map(Some).ok_or(()),
+        None => Ok(None),
+    }
+}
+
+fn scope_display(channel_id: Option<u64>) -> String {
+    match channel_id {
+        Some(channel_id) => format!("<#{}>", channel_id),
+        None => "Server".to_owned(),
+    }
+}
+
+fn truncate_embed_description(text: &str) -> String {
+    const EMBED_DESCRIPTION_LIMIT: usize = 4096;
+
+    if text.chars().count() <= EMBED_DESCRIPTION_LIMIT {
+        return text.to_owned();
+    }
+
+    let mut truncated: String = text.chars().take(EMBED_DESCRIPTION_LIMIT - 1).collect();
+    truncated.push('…');
+    truncated
+}
@@ -3,47 +3,92 @@ use poise::serenity_prelude as serenity;
 use crate::CommandMeta;
 use crate::moderation::embeds::{guild_only_message, usage_message};
 use autumn_core::{Context, Error};
-use autumn_database::impls::ai_config::{get_llm_enabled, set_llm_enabled};
-use autumn_utils::permissions::has_user_permission;
+use autumn_database::impls::ai_config::{
+    get_llm_enabled, get_llm_long_reply_mode, get_llm_thread_mode, set_llm_enabled,
+    set_llm_long_reply_mode, set_llm_thread_mode,
+};
 
 pub const META: CommandMeta = CommandMeta {
     name: "aitoggle",
     desc: "Enable or disable AI mention replies for this server.",
     category: "moderation",
-    usage: "!aitoggle <on|off|status>",
+    usage: "!aitoggle <on|off|status|longreply <split|file>|threads <on|off>>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    channel_scoped: false,
+    examples: &["!aitoggle on", "!aitoggle longreply file"],
 };
 
 #[poise::command(prefix_command, slash_command, category = "Moderation")]
 pub async fn aitoggle(
     ctx: Context<'_>,
-    #[description = "Desired state: on, off, or status"] state: Option<String>,
+    #[description = "Desired state: on, off, status, longreply <split|file>, or threads <on|off>"]
+    #[rest]
+    state: Option<String>,
 ) -> Result<(), Error> {
     let Some(guild_id) = ctx.guild_id() else {
         ctx.say(guild_only_message()).await?;
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(raw_state) = state.as_deref().map(str::trim) else {
-        ctx.say(usage_message(META.usage)).await?;
+        ctx.say(usage_message(ctx, META.usage).await).await?;
         return Ok(());
     };
 
     if raw_state.eq_ignore_ascii_case("status") {
         let enabled = get_llm_enabled(&ctx.data().db, guild_id.get()).await?;
+        let long_reply_mode = get_llm_long_reply_mode(&ctx.data().db, guild_id.get()).await?;
+        let thread_mode = get_llm_thread_mode(&ctx.data().db, guild_id.get()).await?;
+        ctx.say(format!(
+            "AI mention replies are currently **{}** for this server.\n\
+             Long replies are sent as **{}**.\n\
+             New conversations **{}**.",
+            if enabled { "enabled" } else { "disabled" },
+            long_reply_display(&long_reply_mode),
+            thread_mode_display(thread_mode)
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let mut parts = raw_state.split_whitespace();
+    let subcommand = parts.next().map(str::to_ascii_lowercase);
+
+    if subcommand.as_deref() == Some("threads") {
+        let enabled = match parts.next().map(str::to_ascii_lowercase).as_deref() {
+            Some("on") => true,
+            Some("off") => false,
+            _ => {
+                ctx.say(usage_message(ctx, "!aitoggle threads <on|off>").await)
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        set_llm_thread_mode(&ctx.data().db, guild_id.get(), enabled).await?;
+        ctx.say(format!(
+            "New AI conversations **{}**.",
+            thread_mode_display(enabled)
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    if subcommand.as_deref() == Some("longreply") {
+        let mode = match parts.next().map(str::to_ascii_lowercase).as_deref() {
+            Some("split") => "split",
+            Some("file") => "attachment",
+            _ => {
+                ctx.say(usage_message(ctx, "!aitoggle longreply <split|file>").await)
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        set_llm_long_reply_mode(&ctx.data().db, guild_id.get(), mode).await?;
         ctx.say(format!(
-            "AI mention replies are currently **{}** for this server.",
-            if enabled { "enabled" } else { "disabled" }
+            "Long AI replies will now be sent as **{}**.",
+            long_reply_display(mode)
         ))
         .await?;
         return Ok(());
@@ -54,7 +99,7 @@ pub async fn aitoggle(
     } else if raw_state.eq_ignore_ascii_case("off") {
         false
     } else {
-        ctx.say(usage_message(META.usage)).await?;
+        ctx.say(usage_message(ctx, META.usage).await).await?;
         return Ok(());
     };
 
@@ -67,3 +112,18 @@ pub async fn aitoggle(
 
     Ok(())
 }
+
+fn long_reply_display(mode: &str) -> &'static str {
+    match mode {
+        "attachment" => "a .md file attachment",
+        _ => "a reply chain",
+    }
+}
+
+fn thread_mode_display(enabled: bool) -> &'static str {
+    if enabled {
+        "start in their own thread"
+    } else {
+        "are answered inline"
+    }
+}
@@ -0,0 +1,172 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_database::impls::llm_usage::{
+    get_llm_quota, list_top_llm_users, llm_usage_totals, set_llm_quota,
+};
+use autumn_database::model::llm_usage::LlmQuotaScope;
+use autumn_utils::embed::DEFAULT_EMBED_COLOR;
+use autumn_utils::time::now_unix_secs;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "aiusage",
+    desc: "Show AI token usage, or set daily AI quotas.",
+    category: "moderation",
+    usage: "!aiusage [days] | !aiusage quota <user|server> <tokens|off>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    channel_scoped: false,
+    examples: &["!aiusage 7", "!aiusage quota server 100000"],
+};
+
+const DEFAULT_PERIOD_DAYS: u64 = 7;
+const MAX_PERIOD_DAYS: u64 = 90;
+const TOP_USERS_LIMIT: u32 = 5;
+
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn aiusage(
+    ctx: Context<'_>,
+    #[description = "Days to report on (default 7), or quota <user|server> <tokens|off>"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let raw = input.as_deref().map(str::trim).unwrap_or_default();
+    let mut parts = raw.split_whitespace();
+    let first = parts.next().map(str::to_ascii_lowercase);
+
+    if first.as_deref() == Some("quota") {
+        let scope = match parts.next().map(str::to_ascii_lowercase).as_deref() {
+            Some("user") => LlmQuotaScope::User,
+            Some("server") => LlmQuotaScope::Guild,
+            _ => {
+                ctx.say(usage_message(ctx, "!aiusage quota <user|server> <tokens|off>").await)
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        let daily_tokens = match parts.next().map(str::to_ascii_lowercase).as_deref() {
+            Some("off") => None,
+            Some(value) => match value.replace(['_', ','], "").parse::<u64>() {
+                Ok(tokens) if tokens > 0 => Some(tokens),
+                _ => {
+                    ctx.say(usage_message(ctx, "!aiusage quota <user|server> <tokens|off>").await)
+                        .await?;
+                    return Ok(());
+                }
+            },
+            None => {
+                ctx.say(usage_message(ctx, "!aiusage quota <user|server> <tokens|off>").await)
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        set_llm_quota(&ctx.data().db, guild_id.get(), scope, daily_tokens).await?;
+        let scope_label = match scope {
+            LlmQuotaScope::User => "per member",
+            LlmQuotaScope::Guild => "for the whole server",
+        };
+        ctx.say(format!(
+            "The daily AI quota {} is now **{}**.",
+            scope_label,
+            quota_display(daily_tokens)
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let days = match first.as_deref() {
+        None => DEFAULT_PERIOD_DAYS,
+        Some(value) => match value.trim_end_matches('d').parse::<u64>() {
+            Ok(days) if (1..=MAX_PERIOD_DAYS).contains(&days) => days,
+            _ => {
+                ctx.say(format!(
+                    "Provide a period between 1 and {} days.",
+                    MAX_PERIOD_DAYS
+                ))
+                .await?;
+                return Ok(());
+            }
+        },
+    };
+
+    let db = &ctx.data().db;
+    let since = now_unix_secs().saturating_sub(days * 86_400);
+    let totals = llm_usage_totals(db, guild_id.get(), since).await?;
+    let top_users = list_top_llm_users(db, guild_id.get(), since, TOP_USERS_LIMIT).await?;
+    let quota = get_llm_quota(db, guild_id.get()).await?;
+
+    let error_rate = if totals.requests == 0 {
+        0.0
+    } else {
+        totals.failed_requests as f64 * 100.0 / totals.requests as f64
+    };
+
+    let mut lines = vec![
+        format!(
+            "**Period :** last {} day{}",
+            days,
+            if days == 1 { "" } else { "s" }
+        ),
+        format!("**Requests :** {}", totals.requests),
+        format!(
+            "**Errors :** {} ({:.1}%)",
+            totals.failed_requests, error_rate
+        ),
+        format!(
+            "**Tokens :** {} ({} prompt, {} completion)",
+            totals.total_tokens(),
+            totals.prompt_tokens,
+            totals.completion_tokens
+        ),
+        format!("**Average latency :** {} ms", totals.average_latency_ms),
+        format!(
+            "**Daily quotas :** {} per member, {} per server",
+            quota_display(quota.daily_user_tokens),
+            quota_display(quota.daily_guild_tokens)
+        ),
+        String::new(),
+        "**Top users**".to_owned(),
+    ];
+
+    if top_users.is_empty() {
+        lines.push("No AI requests in this period.".to_owned());
+    } else {
+        lines.extend(top_users.iter().enumerate().map(|(index, usage)| {
+            format!(
+                "{}. <@{}> — {} tokens over {} request{}",
+                index + 1,
+                usage.user_id,
+                usage.total_tokens,
+                usage.requests,
+                if usage.requests == 1 { "" } else { "s" }
+            )
+        }));
+    }
+
+    let embed = serenity::CreateEmbed::new()
+        .title("AI Usage")
+        .description(lines.join("\n"))
+        .footer(serenity::CreateEmbedFooter::new(
+            "Token counts come from the AI backend, or are estimated when it reports none. Quotas reset at 00:00 UTC.",
+        ))
+        .color(DEFAULT_EMBED_COLOR);
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+fn quota_display(daily_tokens: Option<u64>) -> String {
+    match daily_tokens {
+        Some(tokens) => format!("{} tokens", tokens),
+        None => "unlimited".to_owned(),
+    }
+}
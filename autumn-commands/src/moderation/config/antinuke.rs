@@ -0,0 +1,138 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use crate::moderation::safety_check::is_guild_owner;
+use autumn_core::{Context, Error};
+use autumn_database::impls::anti_nuke::{
+    add_anti_nuke_trusted_user, get_anti_nuke_config, remove_anti_nuke_trusted_user,
+    set_anti_nuke_enabled, set_anti_nuke_threshold,
+};
+use autumn_utils::parse::parse_user_id;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "antinuke",
+    desc: "Strip the roles of anyone making a burst of bans, kicks or deletions.",
+    category: "moderation",
+    usage: "!antinuke <on|off|status|threshold <actions> <seconds>|trust <user>|untrust <user>>",
+    required_permissions: Some(serenity::Permissions::ADMINISTRATOR),
+    channel_scoped: false,
+    examples: &["!antinuke on", "!antinuke threshold 5 60"],
+};
+
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn antinuke(
+    ctx: Context<'_>,
+    #[description = "on, off, status, threshold <actions> <seconds>, trust <user>, or untrust <user>"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let raw = input.as_deref().map(str::trim).unwrap_or_default();
+    let mut parts = raw.split_whitespace();
+    let subcommand = parts.next().map(str::to_ascii_lowercase);
+    let db = &ctx.data().db;
+
+    match subcommand.as_deref() {
+        Some("status") => {
+            let config = get_anti_nuke_config(db, guild_id.get()).await?;
+            let trusted = if config.trusted_user_ids.is_empty() {
+                "None".to_owned()
+            } else {
+                config
+                    .trusted_user_ids
+                    .iter()
+                    .map(|id| format!("<@{}>", id))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            ctx.say(format!(
+                "Anti-nuke is **{}** for this server.\n\
+                 Anyone making more than **{}** bans, kicks, deletions or permission changes within **{}s** loses their dangerous roles.\n\
+                 **Trusted :** {}",
+                if config.enabled {
+                    "enabled"
+                } else {
+                    "disabled"
+                },
+                config.max_actions,
+                config.window_seconds,
+                trusted
+            ))
+            .await?;
+        }
+        Some(state @ ("on" | "off")) => {
+            let enabled = state == "on";
+            set_anti_nuke_enabled(db, guild_id.get(), enabled).await?;
+            let mut reply = format!(
+                "Anti-nuke is now **{}** for this server.",
+                if enabled { "enabled" } else { "disabled" }
+            );
+            if enabled {
+                reply.push_str(
+                    "\nMake sure my role is above your moderator roles and I can view the audit log.",
+                );
+            }
+            ctx.say(reply).await?;
+        }
+        Some("threshold") => {
+            let max_actions = parts
+                .next()
+                .and_then(|value| value.parse::<u32>().ok())
+                .filter(|max| *max > 0);
+            let window_seconds = parts
+                .next()
+                .and_then(|value| value.trim_end_matches('s').parse::<u32>().ok())
+                .filter(|window| (1..=3_600).contains(window));
+            let (Some(max_actions), Some(window_seconds)) = (max_actions, window_seconds) else {
+                ctx.say(usage_message(ctx, "!antinuke threshold <actions> <1-3600 seconds>").await)
+                    .await?;
+                return Ok(());
+            };
+
+            set_anti_nuke_threshold(db, guild_id.get(), max_actions, window_seconds).await?;
+            ctx.say(format!(
+                "Anti-nuke will now act on more than **{}** destructive actions within **{}s**.",
+                max_actions, window_seconds
+            ))
+            .await?;
+        }
+        Some(change @ ("trust" | "untrust")) => {
+            // An admin account is exactly what anti-nuke guards against, so
+            // only the owner can exempt anyone from it.
+            if !is_guild_owner(ctx, guild_id, ctx.author().id).await {
+                ctx.say("Only the server owner can change who anti-nuke trusts.")
+                    .await?;
+                return Ok(());
+            }
+
+            let Some(user_id) = parts.next().and_then(parse_user_id) else {
+                ctx.say(usage_message(ctx, &format!("!antinuke {} <user>", change)).await)
+                    .await?;
+                return Ok(());
+            };
+
+            let reply = if change == "trust" {
+                if add_anti_nuke_trusted_user(db, guild_id.get(), user_id).await? {
+                    format!("<@{}> is now trusted by anti-nuke.", user_id)
+                } else {
+                    format!("<@{}> is already trusted.", user_id)
+                }
+            } else if remove_anti_nuke_trusted_user(db, guild_id.get(), user_id).await? {
+                format!("<@{}> is no longer trusted by anti-nuke.", user_id)
+            } else {
+                format!("<@{}> wasn't trusted.", user_id)
+            };
+            ctx.say(reply).await?;
+        }
+        _ => {
+            ctx.say(usage_message(ctx, META.usage).await).await?;
+        }
+    }
+
+    Ok(())
+}
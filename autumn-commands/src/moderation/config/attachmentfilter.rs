@@ -0,0 +1,506 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use crate::prefix::{command_prefix, with_prefix};
+use autumn_core::{Context, Error};
+use autumn_database::impls::attachment_filter::{
+    add_filter_extension, clear_attachment_channel_rule, get_attachment_filter_config,
+    list_attachment_channel_rules, list_filter_extensions, normalize_extension,
+    remove_filter_extension, set_attachment_filter_action, set_attachment_filter_enabled,
+    set_attachment_filter_extension_mode, set_attachment_filter_max_size, set_channel_allow_images,
+    set_channel_allow_videos,
+};
+use autumn_utils::embed::DEFAULT_EMBED_COLOR;
+use autumn_utils::formatting::format_size_bytes;
+use autumn_utils::pagination::paginate_embed_pages;
+use autumn_utils::parse::{parse_channel_id, parse_size_bytes};
+
+pub const META: CommandMeta = CommandMeta {
+    name: "attachmentfilter",
+    desc: "Manage the attachment and file-type filter for this server.",
+    category: "moderation",
+    usage: "!attachmentfilter <enable|disable|action|mode|maxsize|add|remove|list|channel>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    channel_scoped: false,
+    examples: &["!attachmentfilter add exe", "!attachmentfilter maxsize 8mb"],
+};
+
+/// Manage the attachment and file-type filter for this server.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    subcommands(
+        "enable", "disable", "action", "mode", "maxsize", "add", "remove", "list", "channel"
+    )
+)]
+pub async fn attachmentfilter(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    let config = get_attachment_filter_config(db, guild_id.get()).await?;
+    let extensions = list_filter_extensions(db, guild_id.get()).await?;
+    let channel_rules = list_attachment_channel_rules(db, guild_id.get()).await?;
+
+    let (enabled, action, mode, max_size) = match &config {
+        Some(cfg) => (
+            cfg.enabled,
+            cfg.action.as_str(),
+            cfg.extension_mode.as_str(),
+            cfg.max_size_bytes,
+        ),
+        None => (false, "delete_and_log", "deny", None),
+    };
+
+    let status = if enabled { "Enabled" } else { "Disabled" };
+    let extension_list = if extensions.is_empty() {
+        "None".to_owned()
+    } else {
+        extensions
+            .iter()
+            .map(|ext| format!("`.{}`", ext))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let mut lines = vec![
+        format!("**Status :** {}", status),
+        format!("**Action :** {}", action_display(action)),
+        format!("**Extension Mode :** {}", mode_display(mode)),
+        format!(
+            "**Max Size :** {}",
+            max_size
+                .map(format_size_bytes)
+                .unwrap_or_else(|| "No limit".to_owned())
+        ),
+        format!("**Extensions :** {}", extension_list),
+    ];
+
+    if !channel_rules.is_empty() {
+        lines.push(String::new());
+        lines.push("**Channel Rules**".to_owned());
+        for rule in &channel_rules {
+            lines.push(format!(
+                "<#{}> — Images: {} • Videos: {}",
+                rule.channel_id,
+                allowed_display(rule.allow_images),
+                allowed_display(rule.allow_videos)
+            ));
+        }
+    }
+
+    let embed = serenity::CreateEmbed::new()
+        .title("Attachment Filter Status")
+        .description(lines.join("\n"))
+        .color(DEFAULT_EMBED_COLOR)
+        .footer(serenity::CreateEmbedFooter::new(
+            "Subcommands: enable, disable, action, mode, maxsize, add, remove, list, channel",
+        ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Enable the attachment filter.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn enable(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    set_attachment_filter_enabled(&ctx.data().db, guild_id.get(), true).await?;
+    ctx.say("Attachment filter has been **enabled**.").await?;
+
+    Ok(())
+}
+
+/// Disable the attachment filter.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    set_attachment_filter_enabled(&ctx.data().db, guild_id.get(), false).await?;
+    ctx.say("Attachment filter has been **disabled**.").await?;
+
+    Ok(())
+}
+
+/// Set the action taken when a blocked attachment is detected.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn action(
+    ctx: Context<'_>,
+    #[description = "Action: log, delete, warn, or timeout"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let action_help = "• `log` — Only log the violation\n\
+                       • `delete` — Delete message and log\n\
+                       • `warn` — Warn user, delete message, and log\n\
+                       • `timeout` — Timeout user, delete message, and log";
+
+    let Some(raw) = input.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
+        ctx.say(format!(
+            "{}\n{}",
+            usage_message(ctx, "!attachmentfilter action <log|delete|warn|timeout>").await,
+            action_help
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    let action_str = match raw.to_lowercase().as_str() {
+        "log" => "log_only",
+        "delete" => "delete_and_log",
+        "warn" => "warn_and_log",
+        "timeout" => "timeout_delete_and_log",
+        _ => {
+            ctx.say(format!(
+                "Invalid action. Use one of: `log`, `delete`, `warn`, `timeout`.\n\n{}",
+                action_help
+            ))
+            .await?;
+            return Ok(());
+        }
+    };
+
+    set_attachment_filter_action(&ctx.data().db, guild_id.get(), action_str).await?;
+
+    ctx.say(format!(
+        "Attachment filter action set to **{}**.",
+        action_display(action_str)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Choose whether the extension list blocks (deny) or permits (allow) file types.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn mode(
+    ctx: Context<'_>,
+    #[description = "Mode: deny or allow"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let mode_help = "• `deny` — Block listed extensions, allow everything else\n\
+                     • `allow` — Allow listed extensions, block everything else";
+
+    let Some(raw) = input.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
+        ctx.say(format!(
+            "{}\n{}",
+            usage_message(ctx, "!attachmentfilter mode <deny|allow>").await,
+            mode_help
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    let mode_str = match raw.to_lowercase().as_str() {
+        "deny" => "deny",
+        "allow" => "allow",
+        _ => {
+            ctx.say(format!(
+                "Invalid mode. Use `deny` or `allow`.\n\n{}",
+                mode_help
+            ))
+            .await?;
+            return Ok(());
+        }
+    };
+
+    set_attachment_filter_extension_mode(&ctx.data().db, guild_id.get(), mode_str).await?;
+
+    ctx.say(format!(
+        "Extension mode set to **{}**.",
+        mode_display(mode_str)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Set the maximum attachment size, or `off` to remove the limit.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn maxsize(
+    ctx: Context<'_>,
+    #[description = "Size (e.g. 8mb, 500kb) or off"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(raw) = input.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
+        ctx.say(format!(
+            "{} (e.g. `8mb`, `500kb`)",
+            usage_message(ctx, "!attachmentfilter maxsize <size|off>").await
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    if raw.eq_ignore_ascii_case("off") {
+        set_attachment_filter_max_size(&ctx.data().db, guild_id.get(), None).await?;
+        ctx.say("Attachment size limit **removed**.").await?;
+        return Ok(());
+    }
+
+    let Some(bytes) = parse_size_bytes(raw) else {
+        ctx.say("Invalid size. Examples: `500kb`, `8mb`, `25mb`, or `off`")
+            .await?;
+        return Ok(());
+    };
+
+    set_attachment_filter_max_size(&ctx.data().db, guild_id.get(), Some(bytes)).await?;
+    ctx.say(format!(
+        "Attachment size limit set to **{}**.",
+        format_size_bytes(bytes)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Add a file extension to the list.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "Extension to add (e.g. exe)"]
+    #[rest]
+    extension: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(extension) = extension
+        .as_deref()
+        .map(normalize_extension)
+        .filter(|ext| is_valid_extension(ext))
+    else {
+        ctx.say(format!(
+            "{} (e.g. `exe`)",
+            usage_message(ctx, "!attachmentfilter add <extension>").await
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    let inserted = add_filter_extension(&ctx.data().db, guild_id.get(), &extension).await?;
+
+    if inserted {
+        ctx.say(format!("Added `.{}` to the extension list.", extension))
+            .await?;
+    } else {
+        ctx.say(format!(
+            "`.{}` is already in the extension list.",
+            extension
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Remove a file extension from the list.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "Extension to remove"]
+    #[rest]
+    extension: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let Some(extension) = extension
+        .as_deref()
+        .map(normalize_extension)
+        .filter(|ext| !ext.is_empty())
+    else {
+        ctx.say(usage_message(ctx, "!attachmentfilter remove <extension>").await)
+            .await?;
+        return Ok(());
+    };
+
+    let removed = remove_filter_extension(&ctx.data().db, guild_id.get(), &extension).await?;
+
+    if removed {
+        ctx.say(format!("Removed `.{}` from the extension list.", extension))
+            .await?;
+    } else {
+        ctx.say(format!(
+            "`.{}` was not found in the extension list.",
+            extension
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// List all listed file extensions for this server.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let extensions = list_filter_extensions(&ctx.data().db, guild_id.get()).await?;
+
+    if extensions.is_empty() {
+        ctx.say("The extension list is empty.").await?;
+        return Ok(());
+    }
+
+    let lines: Vec<String> = extensions
+        .iter()
+        .enumerate()
+        .map(|(i, ext)| format!("{}. `.{}`", i + 1, ext))
+        .collect();
+
+    let items_per_page = 20;
+    let pages: Vec<String> = lines
+        .chunks(items_per_page)
+        .map(|chunk| chunk.join("\n"))
+        .collect();
+
+    paginate_embed_pages(ctx, "Attachment Filter Extensions", &pages, 1).await?;
+
+    Ok(())
+}
+
+/// Allow or block images and videos in a specific channel.
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn channel(
+    ctx: Context<'_>,
+    #[description = "Channel mention or ID"] channel: Option<String>,
+    #[description = "Setting: images, videos, or reset"] setting: Option<String>,
+    #[description = "State: on or off"] state: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let prefix = command_prefix(ctx).await;
+    let usage = format!(
+        "Usage: `{}`\nor `{}`",
+        with_prefix(
+            &prefix,
+            "!attachmentfilter channel <#channel> <images|videos> <on|off>"
+        ),
+        with_prefix(&prefix, "!attachmentfilter channel <#channel> reset")
+    );
+
+    let (Some(channel_id), Some(setting)) = (
+        channel.as_deref().and_then(parse_channel_id),
+        setting.as_deref().map(|s| s.trim().to_lowercase()),
+    ) else {
+        ctx.say(usage).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+
+    if setting == "reset" {
+        let removed = clear_attachment_channel_rule(db, guild_id.get(), channel_id).await?;
+        if removed {
+            ctx.say(format!(
+                "Media rules for <#{}> reset; images and videos are allowed.",
+                channel_id
+            ))
+            .await?;
+        } else {
+            ctx.say(format!("<#{}> has no media rules.", channel_id))
+                .await?;
+        }
+        return Ok(());
+    }
+
+    let allowed = match state.as_deref().map(|s| s.trim().to_lowercase()).as_deref() {
+        Some("on") => true,
+        Some("off") => false,
+        _ => {
+            ctx.say(usage).await?;
+            return Ok(());
+        }
+    };
+
+    let label = match setting.as_str() {
+        "images" => {
+            set_channel_allow_images(db, guild_id.get(), channel_id, allowed).await?;
+            "Images"
+        }
+        "videos" => {
+            set_channel_allow_videos(db, guild_id.get(), channel_id, allowed).await?;
+            "Videos"
+        }
+        _ => {
+            ctx.say(usage).await?;
+            return Ok(());
+        }
+    };
+
+    ctx.say(format!(
+        "{} are now **{}** in <#{}>.",
+        label,
+        if allowed { "allowed" } else { "blocked" },
+        channel_id
+    ))
+    .await?;
+
+    Ok(())
+}
+
+fn is_valid_extension(extension: &str) -> bool {
+    !extension.is_empty()
+        && extension.len() <= 16
+        && extension.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn action_display(action: &str) -> &str {
+    match action {
+        "log_only" => "Only Log",
+        "delete_and_log" => "Delete and Log",
+        "warn_and_log" => "Warn, Delete and Log",
+        "timeout_delete_and_log" => "Timeout, Delete and Log",
+        _ => "Unknown",
+    }
+}
+
+fn mode_display(mode: &str) -> &str {
+    match mode {
+        "allow" => "Allow List",
+        _ => "Deny List",
+    }
+}
+
+fn allowed_display(allowed: bool) -> &'static str {
+    if allowed { "Allowed" } else { "Blocked" }
+}
@@ -0,0 +1,126 @@
+use poise::serenity_prelude as serenity;
+
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use crate::prefix::command_prefix;
+use crate::{CommandMeta, command_meta};
+use autumn_core::{Context, Error};
+use autumn_database::impls::disabled_commands::{
+    disable_command, enable_command, get_disabled_commands,
+};
+use autumn_database::model::disabled_commands::LLM_CHAT_COMMAND;
+use autumn_utils::parse::parse_channel_id;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "command",
+    desc: "Disable or enable a command in this server or one channel.",
+    category: "moderation",
+    usage: "!command <disable|enable <name> [#channel]|list>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    channel_scoped: false,
+    examples: &[
+        "!command disable universe",
+        "!command disable chat #announcements",
+        "!command enable universe",
+    ],
+};
+
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn command(
+    ctx: Context<'_>,
+    #[description = "disable <name> [#channel], enable <name> [#channel], or list"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let raw = input.as_deref().map(str::trim).unwrap_or_default();
+    let args = raw.split_whitespace().collect::<Vec<_>>();
+    let subcommand = args.first().map(|value| value.to_ascii_lowercase());
+    let db = &ctx.data().db;
+
+    let disable = match subcommand.as_deref() {
+        Some("list") => {
+            let disabled = get_disabled_commands(db, guild_id.get()).await?;
+            if disabled.is_empty() {
+                ctx.say("No commands are disabled in this server.").await?;
+                return Ok(());
+            }
+
+            let lines = disabled
+                .iter()
+                .map(|entry| match entry.channel_id {
+                    Some(channel_id) => format!("- `{}` in <#{}>", entry.command_name, channel_id),
+                    None => format!("- `{}` everywhere", entry.command_name),
+                })
+                .collect::<Vec<_>>();
+            ctx.say(format!("Disabled commands:\n{}", lines.join("\n")))
+                .await?;
+            return Ok(());
+        }
+        Some("disable") => true,
+        Some("enable") => false,
+        _ => {
+            ctx.say(usage_message(ctx, META.usage).await).await?;
+            return Ok(());
+        }
+    };
+
+    let Some(name) = args
+        .get(1)
+        .map(|value| value.trim_start_matches('!').to_ascii_lowercase())
+    else {
+        ctx.say(usage_message(ctx, META.usage).await).await?;
+        return Ok(());
+    };
+
+    if name != LLM_CHAT_COMMAND && command_meta(&name).is_none() {
+        ctx.say(format!("Unknown command: `{}`", name)).await?;
+        return Ok(());
+    }
+
+    if name == META.name {
+        ctx.say(format!(
+            "`{}{}` can't be disabled.",
+            command_prefix(ctx).await,
+            META.name
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let channel_id = match args.get(2) {
+        Some(raw_channel) => match parse_channel_id(raw_channel) {
+            Some(channel_id) => Some(channel_id),
+            None => {
+                ctx.say(usage_message(ctx, META.usage).await).await?;
+                return Ok(());
+            }
+        },
+        None => None,
+    };
+    let scope = match channel_id {
+        Some(channel_id) => format!("in <#{}>", channel_id),
+        None => "in this server".to_owned(),
+    };
+
+    if disable {
+        if disable_command(db, guild_id.get(), &name, channel_id).await? {
+            ctx.say(format!("`{}` is now **disabled** {}.", name, scope))
+                .await?;
+        } else {
+            ctx.say(format!("`{}` is already disabled {}.", name, scope))
+                .await?;
+        }
+    } else if enable_command(db, guild_id.get(), &name, channel_id).await? {
+        ctx.say(format!("`{}` is now **enabled** {}.", name, scope))
+            .await?;
+    } else {
+        ctx.say(format!("`{}` isn't disabled {}.", name, scope))
+            .await?;
+    }
+
+    Ok(())
+}
@@ -1,7 +1,8 @@
 use poise::serenity_prelude as serenity;
 
 use crate::CommandMeta;
-use crate::moderation::embeds::guild_only_message;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use crate::prefix::command_prefix;
 use autumn_core::{Context, Error};
 use autumn_database::impls::escalation::{
     get_escalation_config, set_escalation_enabled, set_timeout_window, set_warn_threshold,
@@ -10,13 +11,15 @@ use autumn_database::impls::escalation::{
 use autumn_utils::embed::DEFAULT_EMBED_COLOR;
 use autumn_utils::formatting::format_compact_duration;
 use autumn_utils::parse::parse_duration_seconds;
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "escalation",
     desc: "Configure automatic warn → timeout escalation.",
     category: "moderation",
     usage: "!escalation <enable|disable|set>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    channel_scoped: false,
+    examples: &["!escalation enable", "!escalation set warns 3"],
 };
 
 /// Configure automatic warn → timeout escalation.
@@ -32,17 +35,6 @@ pub async fn escalation(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let config = get_escalation_config(&ctx.data().db, guild_id.get()).await?;
 
     let (enabled, threshold, warn_window, timeout_window) = match &config {
@@ -93,17 +85,6 @@ pub async fn enable(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     set_escalation_enabled(&ctx.data().db, guild_id.get(), true).await?;
     ctx.say("Automatic escalation has been **enabled**.")
         .await?;
@@ -119,17 +100,6 @@ pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     set_escalation_enabled(&ctx.data().db, guild_id.get(), false).await?;
     ctx.say("Automatic escalation has been **disabled**.")
         .await?;
@@ -145,12 +115,13 @@ pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
     subcommands("warns", "warnwindow", "timeoutwindow")
 )]
 pub async fn set(ctx: Context<'_>) -> Result<(), Error> {
-    ctx.say(
+    let prefix = command_prefix(ctx).await;
+    ctx.say(format!(
         "Usage:\n\
-         `!escalation set warns <count>` — set warning threshold\n\
-         `!escalation set warnwindow <duration>` — set warn counting window (e.g. `24h`, `7d`)\n\
-         `!escalation set timeoutwindow <duration>` — set timeout escalation window (e.g. `7d`, `30d`)",
-    )
+         `{prefix}escalation set warns <count>` — set warning threshold\n\
+         `{prefix}escalation set warnwindow <duration>` — set warn counting window (e.g. `24h`, `7d`)\n\
+         `{prefix}escalation set timeoutwindow <duration>` — set timeout escalation window (e.g. `7d`, `30d`)",
+    ))
     .await?;
 
     Ok(())
@@ -169,26 +140,21 @@ pub async fn warns(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(raw) = input.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
-        ctx.say("Usage: `!escalation set warns <count>` (e.g. `3`)")
-            .await?;
+        ctx.say(format!(
+            "{} (e.g. `3`)",
+            usage_message(ctx, "!escalation set warns <count>").await
+        ))
+        .await?;
         return Ok(());
     };
 
     let Ok(count) = raw.parse::<i32>() else {
-        ctx.say("Invalid number. Usage: `!escalation set warns <count>` (e.g. `3`)")
-            .await?;
+        ctx.say(format!(
+            "Invalid number. {} (e.g. `3`)",
+            usage_message(ctx, "!escalation set warns <count>").await
+        ))
+        .await?;
         return Ok(());
     };
 
@@ -220,20 +186,12 @@ pub async fn warnwindow(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(raw) = input.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
-        ctx.say("Usage: `!escalation set warnwindow <duration>` (e.g. `24h`, `7d`)")
-            .await?;
+        ctx.say(format!(
+            "{} (e.g. `24h`, `7d`)",
+            usage_message(ctx, "!escalation set warnwindow <duration>").await
+        ))
+        .await?;
         return Ok(());
     };
 
@@ -267,20 +225,12 @@ pub async fn timeoutwindow(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(raw) = input.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
-        ctx.say("Usage: `!escalation set timeoutwindow <duration>` (e.g. `7d`, `30d`)")
-            .await?;
+        ctx.say(format!(
+            "{} (e.g. `7d`, `30d`)",
+            usage_message(ctx, "!escalation set timeoutwindow <duration>").await
+        ))
+        .await?;
         return Ok(());
     };
 
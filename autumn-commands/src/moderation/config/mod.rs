@@ -1,7 +1,15 @@
+pub mod aihistory;
+pub mod aimod;
+pub mod aiprompt;
 pub mod aitoggle;
+pub mod aiusage;
+pub mod antinuke;
+pub mod attachmentfilter;
+pub mod command;
 pub mod escalation;
 pub mod modlogchannel;
 pub mod permissions;
+pub mod safety;
 pub mod setup;
 pub mod userlogchannel;
 pub mod wordfilter;
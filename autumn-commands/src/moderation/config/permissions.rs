@@ -1,39 +1,67 @@
-use crate::CommandMeta;
+use poise::serenity_prelude as serenity;
+
+use crate::access::has_command_permission;
+use crate::moderation::embeds::usage_message;
+use crate::prefix::command_prefix;
+use crate::{CommandMeta, command_meta};
 use autumn_core::{Context, Error};
+use autumn_database::impls::command_acl::{
+    get_command_acl_rules, remove_command_acl_rule, set_command_acl_rule,
+};
+use autumn_database::model::command_acl::CommandAclTarget;
 use autumn_utils::pagination::paginate_embed_pages;
-use autumn_utils::permissions::{has_user_permission, permission_names, resolve_user_permissions};
+use autumn_utils::parse::{parse_channel_id, parse_user_id};
+use autumn_utils::permissions::{is_guild_role, permission_names, resolve_user_permissions};
 
 pub const META: CommandMeta = CommandMeta {
     name: "permissions",
-    desc: "Display your server permissions.",
+    desc: "Display your server permissions, or choose who can use a command.",
     category: "moderation",
-    usage: "!permissions [page]",
+    usage: "!permissions [page] | !perms command <name> [allow|deny|reset <@role|@user|#channel>]",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    channel_scoped: false,
+    examples: &["!permissions", "!perms command warn allow @Helper"],
 };
 
 const PERMISSIONS_PER_PAGE: usize = 10;
+const COMMAND_RULES_USAGE: &str = "!perms command <name> [allow|deny|reset <@role|@user|#channel>]";
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    aliases("perms")
+)]
 pub async fn permissions(
     ctx: Context<'_>,
-    #[description = "Starting page"] page: Option<usize>,
+    #[description = "Starting page, or command <name> [allow|deny|reset <target>]"]
+    #[rest]
+    input: Option<String>,
 ) -> Result<(), Error> {
     let Some(guild_id) = ctx.guild_id() else {
         ctx.say("This command only works in servers.").await?;
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        poise::serenity_prelude::Permissions::MANAGE_MESSAGES,
-    )
-    .await?
-    {
-        return Ok(());
+    let raw = input.as_deref().map(str::trim).unwrap_or_default();
+    let mut parts = raw.split_whitespace();
+    let first = parts.next();
+    if first.is_some_and(|first| first.eq_ignore_ascii_case("command")) {
+        let args = parts.collect::<Vec<_>>();
+        return command_rules(ctx, guild_id, &args).await;
     }
 
-    let perms = resolve_user_permissions(ctx.http(), guild_id, ctx.author().id).await?;
+    let page = match first.map(str::parse::<usize>) {
+        Some(Ok(page)) => Some(page),
+        Some(Err(_)) => {
+            ctx.say(usage_message(ctx, META.usage).await).await?;
+            return Ok(());
+        }
+        None => None,
+    };
+
+    let perms =
+        resolve_user_permissions(ctx, ctx.data().db.cache(), guild_id, ctx.author().id).await?;
     let names = permission_names(perms);
 
     if names.is_empty() {
@@ -68,6 +96,165 @@ pub async fn permissions(
     Ok(())
 }
 
+/// `!perms command ...`: list, set or remove a command's rules.
+async fn command_rules(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    args: &[&str],
+) -> Result<(), Error> {
+    // Rules can hand out moderation powers, so only administrators manage them.
+    if !has_command_permission(ctx, guild_id, serenity::Permissions::ADMINISTRATOR).await? {
+        return Ok(());
+    }
+
+    let Some(raw_name) = args.first() else {
+        ctx.say(usage_message(ctx, COMMAND_RULES_USAGE).await)
+            .await?;
+        return Ok(());
+    };
+
+    let prefix = command_prefix(ctx).await;
+    let name = raw_name
+        .strip_prefix(prefix.as_str())
+        .unwrap_or(raw_name)
+        .trim_start_matches('!')
+        .to_ascii_lowercase();
+    let Some(command) = command_meta(&name) else {
+        ctx.say(format!("Unknown command: `{}`", name)).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    let (Some(change), Some(raw_target)) = (args.get(1), args.get(2)) else {
+        let rules = get_command_acl_rules(db, guild_id.get()).await?;
+        let lines = rules
+            .iter()
+            .filter(|rule| rule.command_name == command.name)
+            .map(|rule| {
+                format!(
+                    "- **{}** {}",
+                    if rule.allow { "Allow" } else { "Deny" },
+                    rule.target.mention()
+                )
+            })
+            .collect::<Vec<_>>();
+        if lines.is_empty() {
+            ctx.say(format!(
+                "`{}{}` has no rules; it uses its default permissions.",
+                prefix, command.name,
+            ))
+            .await?;
+        } else {
+            ctx.say(format!(
+                "Rules for `{}{}`:\n{}",
+                prefix,
+                command.name,
+                lines.join("\n")
+            ))
+            .await?;
+        }
+        return Ok(());
+    };
+
+    let Some(target) = parse_rule_target(raw_target, guild_id.get()) else {
+        ctx.say(usage_message(ctx, COMMAND_RULES_USAGE).await)
+            .await?;
+        return Ok(());
+    };
+
+    // A bare ID could name a user or channel as well, so it must be a role.
+    if let CommandAclTarget::Role(role_id) = target
+        && raw_target.trim().parse::<u64>().is_ok()
+        && !is_guild_role(ctx, db.cache(), guild_id, role_id).await?
+    {
+        ctx.say(format!(
+            "`{}` isn't a role here. Mention the role, user or channel instead.",
+            role_id
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    match change.to_ascii_lowercase().as_str() {
+        change @ ("allow" | "deny") => {
+            let allow = change == "allow";
+            if allow
+                && command
+                    .required_permissions
+                    .is_some_and(|perms| perms.contains(serenity::Permissions::ADMINISTRATOR))
+            {
+                ctx.say(format!(
+                    "`{}{}` needs Administrator, which can't be granted by a rule.",
+                    prefix, command.name,
+                ))
+                .await?;
+                return Ok(());
+            }
+
+            set_command_acl_rule(db, guild_id.get(), command.name, target, allow).await?;
+            ctx.say(format!(
+                "`{}{}` is now **{}** for {}.",
+                prefix,
+                command.name,
+                if allow { "allowed" } else { "denied" },
+                target.mention()
+            ))
+            .await?;
+        }
+        "reset" => {
+            if remove_command_acl_rule(db, guild_id.get(), command.name, target).await? {
+                ctx.say(format!(
+                    "Removed the `{}{}` rule for {}.",
+                    prefix,
+                    command.name,
+                    target.mention()
+                ))
+                .await?;
+            } else {
+                ctx.say(format!(
+                    "`{}{}` has no rule for {}.",
+                    prefix,
+                    command.name,
+                    target.mention()
+                ))
+                .await?;
+            }
+        }
+        _ => {
+            ctx.say(usage_message(ctx, COMMAND_RULES_USAGE).await)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// A role, user or channel mention, or `@everyone`. A bare ID is taken as a
+/// role.
+fn parse_rule_target(raw: &str, guild_id: u64) -> Option<CommandAclTarget> {
+    let value = raw.trim();
+    if value == "@everyone" {
+        return Some(CommandAclTarget::Role(guild_id));
+    }
+
+    if let Some(id) = value
+        .strip_prefix("<@&")
+        .and_then(|rest| rest.strip_suffix('>'))
+    {
+        return id.parse().ok().map(CommandAclTarget::Role);
+    }
+
+    if let Ok(id) = value.parse::<u64>() {
+        return Some(CommandAclTarget::Role(id));
+    }
+
+    if value.starts_with("<#") {
+        return parse_channel_id(value).map(CommandAclTarget::Channel);
+    }
+
+    parse_user_id(value).map(CommandAclTarget::User)
+}
+
 fn total_pages(total_items: usize, per_page: usize) -> usize {
     let per_page = per_page.max(1);
     let pages = total_items.div_ceil(per_page);
@@ -0,0 +1,98 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use autumn_core::{Context, Error};
+use autumn_database::impls::moderator_safety::{
+    clear_moderator_pause, get_moderator_safety_config, set_destructive_limit,
+};
+use autumn_utils::parse::parse_user_id;
+use autumn_utils::time::now_unix_secs;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "safety",
+    desc: "Limit how many bans and kicks a moderator can make per hour.",
+    category: "moderation",
+    usage: "!safety <status|limit <per hour|off>|unpause <user>>",
+    required_permissions: Some(serenity::Permissions::ADMINISTRATOR),
+    channel_scoped: false,
+    examples: &["!safety limit 10", "!safety unpause @user"],
+};
+
+#[poise::command(prefix_command, slash_command, category = "Moderation")]
+pub async fn safety(
+    ctx: Context<'_>,
+    #[description = "status, limit <per hour|off>, or unpause <user>"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let raw = input.as_deref().map(str::trim).unwrap_or_default();
+    let mut parts = raw.split_whitespace();
+    let subcommand = parts.next().map(str::to_ascii_lowercase);
+    let value = parts.next();
+    let db = &ctx.data().db;
+
+    match subcommand.as_deref() {
+        Some("status") => {
+            let config = get_moderator_safety_config(db, guild_id.get()).await?;
+            let status = if config.destructive_limit_per_hour == 0 {
+                "The destructive-action limit is **off** for this server.".to_owned()
+            } else {
+                format!(
+                    "Moderators can make at most **{}** bans/kicks per hour. Going over pauses them for an hour and alerts the modlog channel.",
+                    config.destructive_limit_per_hour
+                )
+            };
+            ctx.say(status).await?;
+        }
+        Some("limit") => {
+            let limit = match value.map(str::to_ascii_lowercase).as_deref() {
+                Some("off") => Some(0),
+                Some(value) => value.parse::<u32>().ok().filter(|limit| *limit > 0),
+                None => None,
+            };
+            let Some(limit) = limit else {
+                ctx.say(usage_message(ctx, "!safety limit <per hour|off>").await)
+                    .await?;
+                return Ok(());
+            };
+
+            set_destructive_limit(db, guild_id.get(), limit).await?;
+            if limit == 0 {
+                ctx.say("The destructive-action limit is now **off** for this server.")
+                    .await?;
+            } else {
+                ctx.say(format!(
+                    "Moderators can now make at most **{}** bans/kicks per hour.",
+                    limit
+                ))
+                .await?;
+            }
+        }
+        Some("unpause") => {
+            let Some(user_id) = value.and_then(parse_user_id) else {
+                ctx.say(usage_message(ctx, "!safety unpause <user>").await)
+                    .await?;
+                return Ok(());
+            };
+
+            let now = i64::try_from(now_unix_secs()).unwrap_or(i64::MAX);
+            if clear_moderator_pause(db, guild_id.get(), user_id, now).await? {
+                ctx.say(format!("<@{}> can ban and kick again.", user_id))
+                    .await?;
+            } else {
+                ctx.say(format!("<@{}> isn't paused.", user_id)).await?;
+            }
+        }
+        _ => {
+            ctx.say(usage_message(ctx, META.usage).await).await?;
+        }
+    }
+
+    Ok(())
+}
@@ -2,6 +2,7 @@ use poise::serenity_prelude as serenity;
 
 use crate::CommandMeta;
 use crate::moderation::embeds::guild_only_message;
+use crate::prefix::command_prefix;
 use autumn_core::{Context, Error};
 use autumn_database::impls::escalation::{
     set_escalation_enabled, set_timeout_window, set_warn_threshold, set_warn_window,
@@ -12,13 +13,15 @@ use autumn_database::impls::word_filter::{
     load_preset_words, set_word_filter_action, set_word_filter_enabled,
 };
 use autumn_utils::embed::DEFAULT_EMBED_COLOR;
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "setup",
     desc: "Apply a moderation configuration preset to this server.",
     category: "moderation",
     usage: "!setup <basic|standard|strict> [#modlog-channel] [#userlog-channel]",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    channel_scoped: false,
+    examples: &["!setup standard #mod-log #user-log"],
 };
 
 enum SetupPreset {
@@ -90,7 +93,12 @@ impl SetupPreset {
 }
 
 /// Apply a moderation configuration preset to this server.
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    guild_cooldown = 30
+)]
 pub async fn setup(
     ctx: Context<'_>,
     #[description = "Preset: basic, standard, or strict"] preset: Option<String>,
@@ -105,34 +113,24 @@ pub async fn setup(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
+    let prefix = command_prefix(ctx).await;
     let Some(raw_preset) = preset.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
-        ctx.say(
+        ctx.say(format!(
             "**Autumn Setup Presets**\n\n\
              \u{2022} `basic` \u{2014} Modlog + userlog channels. Escalation and word filter disabled.\n\
              \u{2022} `standard` \u{2014} Modlog + userlog + escalation (3 warns in 24h \u{2192} auto-timeout, 7d tier window) + word filter (warn action).\n\
              \u{2022} `strict` \u{2014} Modlog + userlog + escalation (2 warns in 7d \u{2192} auto-timeout, 30d tier window) + word filter (timeout action) + preset word list loaded.\n\n\
-             **Usage:** `!setup <basic|standard|strict> [#modlog-channel] [#userlog-channel]`\n\
+             **Usage:** `{prefix}setup <basic|standard|strict> [#modlog-channel] [#userlog-channel]`\n\
              If channels are omitted, Autumn finds or creates `mod-logs` and `user-logs`.",
-        )
+        ))
         .await?;
         return Ok(());
     };
 
     let Some(preset) = SetupPreset::from_str(raw_preset) else {
-        ctx.say(
-            "Unknown preset. Use `basic`, `standard`, or `strict`.\n\nRun `!setup` for details.",
-        )
+        ctx.say(format!(
+            "Unknown preset. Use `basic`, `standard`, or `strict`.\n\nRun `{prefix}setup` for details.",
+        ))
         .await?;
         return Ok(());
     };
@@ -150,7 +148,7 @@ pub async fn setup(
             Err(e) => {
                 ctx.say(format!(
                     "Could not resolve or create `mod-logs` channel: {e}\n\
-                 Pass the channel explicitly: `!setup {raw_preset} #mod-logs #user-logs`"
+                 Pass the channel explicitly: `{prefix}setup {raw_preset} #mod-logs #user-logs`"
                 ))
                 .await?;
                 return Ok(());
@@ -169,7 +167,7 @@ pub async fn setup(
         Err(e) => {
             ctx.say(format!(
                 "Could not resolve or create `user-logs` channel: {e}\n\
-                 Pass the channel explicitly: `!setup {raw_preset} #mod-logs #user-logs`"
+                 Pass the channel explicitly: `{prefix}setup {raw_preset} #mod-logs #user-logs`"
             ))
             .await?;
             return Ok(());
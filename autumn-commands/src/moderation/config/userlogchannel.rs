@@ -6,13 +6,15 @@ use autumn_core::{Context, Error};
 use autumn_database::impls::userlog_config::{
     clear_userlog_channel_id, get_userlog_channel_id, set_userlog_channel_id,
 };
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "userlogchannel",
     desc: "Set or view the user activity log channel.",
     category: "moderation",
     usage: "!userlogchannel [#channel|channel_id|clear]",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    channel_scoped: false,
+    examples: &["!userlogchannel #user-logs"],
 };
 
 #[poise::command(prefix_command, slash_command, category = "Moderation")]
@@ -27,17 +29,6 @@ pub async fn userlogchannel(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     if let Some(input) = input
         .as_deref()
         .map(str::trim)
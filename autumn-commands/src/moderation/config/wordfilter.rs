@@ -1,29 +1,51 @@
 use poise::serenity_prelude as serenity;
 
 use crate::CommandMeta;
-use crate::moderation::embeds::guild_only_message;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use crate::prefix::command_prefix;
 use autumn_core::{Context, Error};
+use autumn_database::impls::modlog_config::get_modlog_channel_id;
 use autumn_database::impls::word_filter::{
-    add_filter_word, clear_preset_words, get_word_filter_config, list_filter_words,
-    load_preset_words, remove_filter_word, set_word_filter_action, set_word_filter_enabled,
+    add_filter_word, clear_preset_words, get_all_filter_words_for_guild, get_word_filter_config,
+    list_filter_words, list_word_filter_automod_rules, load_preset_words, remove_filter_word,
+    set_word_filter_action, set_word_filter_automod_rules, set_word_filter_enabled,
 };
 use autumn_utils::embed::DEFAULT_EMBED_COLOR;
 use autumn_utils::pagination::paginate_embed_pages;
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "wordfilter",
     desc: "Manage the word filter for this server.",
     category: "moderation",
-    usage: "!wordfilter <enable|disable|action|preset|add|remove|list>",
+    usage: "!wordfilter <enable|disable|action|preset|add|remove|list|sync-automod>",
+    required_permissions: Some(serenity::Permissions::MANAGE_GUILD),
+    channel_scoped: false,
+    examples: &["!wordfilter add badword", "!wordfilter action warn"],
 };
 
+/// Discord caps keyword rules at 1000 keywords of at most 60 characters each,
+/// and allows at most 6 keyword rules per guild.
+const AUTOMOD_MAX_KEYWORDS_PER_RULE: usize = 1000;
+const AUTOMOD_MAX_KEYWORD_CHARS: usize = 60;
+const AUTOMOD_MAX_KEYWORD_RULES: usize = 6;
+const AUTOMOD_RULE_NAME: &str = "Autumn Word Filter";
+const AUTOMOD_BLOCK_MESSAGE: &str = "This message was blocked by the server's word filter.";
+
 /// Manage the word filter for this server.
 #[poise::command(
     prefix_command,
     slash_command,
     category = "Moderation",
-    subcommands("enable", "disable", "action", "preset", "add", "remove", "list")
+    subcommands(
+        "enable",
+        "disable",
+        "action",
+        "preset",
+        "add",
+        "remove",
+        "list",
+        "sync_automod"
+    )
 )]
 pub async fn wordfilter(ctx: Context<'_>) -> Result<(), Error> {
     let Some(guild_id) = ctx.guild_id() else {
@@ -31,17 +53,6 @@ pub async fn wordfilter(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let config = get_word_filter_config(&ctx.data().db, guild_id.get()).await?;
 
     let (enabled, action_label) = match &config {
@@ -59,7 +70,7 @@ pub async fn wordfilter(ctx: Context<'_>) -> Result<(), Error> {
         ))
         .color(DEFAULT_EMBED_COLOR)
         .footer(serenity::CreateEmbedFooter::new(
-            "Subcommands: enable, disable, action, preset, add, remove, list",
+            "Subcommands: enable, disable, action, preset, add, remove, list, sync-automod",
         ));
 
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
@@ -75,17 +86,6 @@ pub async fn enable(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     set_word_filter_enabled(&ctx.data().db, guild_id.get(), true).await?;
     ctx.say("Word filter has been **enabled**.").await?;
 
@@ -100,17 +100,6 @@ pub async fn disable(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     set_word_filter_enabled(&ctx.data().db, guild_id.get(), false).await?;
     ctx.say("Word filter has been **disabled**.").await?;
 
@@ -130,25 +119,15 @@ pub async fn action(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(raw) = input.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
-        ctx.say(
-            "Usage: `!wordfilter action <log|delete|warn|timeout>`\n\
+        ctx.say(format!(
+            "{}\n\
              • `log` — Only log the violation\n\
              • `delete` — Delete message and log\n\
              • `warn` — Warn user, delete message, and log\n\
              • `timeout` — Timeout user, delete message, and log",
-        )
+            usage_message(ctx, "!wordfilter action <log|delete|warn|timeout>").await
+        ))
         .await?;
         return Ok(());
     };
@@ -195,19 +174,9 @@ pub async fn preset(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(raw) = input.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
-        ctx.say("Usage: `!wordfilter preset <load|clear>`").await?;
+        ctx.say(usage_message(ctx, "!wordfilter preset <load|clear>").await)
+            .await?;
         return Ok(());
     };
 
@@ -229,8 +198,11 @@ pub async fn preset(
             .await?;
         }
         _ => {
-            ctx.say("Invalid option. Use `load` or `clear`.\n\nUsage: `!wordfilter preset <load|clear>`")
-                .await?;
+            ctx.say(format!(
+                "Invalid option. Use `load` or `clear`.\n\n{}",
+                usage_message(ctx, "!wordfilter preset <load|clear>").await
+            ))
+            .await?;
         }
     }
 
@@ -250,19 +222,9 @@ pub async fn add(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(raw) = word.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
-        ctx.say("Usage: `!wordfilter add <word>`").await?;
+        ctx.say(usage_message(ctx, "!wordfilter add <word>").await)
+            .await?;
         return Ok(());
     };
 
@@ -293,19 +255,9 @@ pub async fn remove(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(raw) = word.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
-        ctx.say("Usage: `!wordfilter remove <word>`").await?;
+        ctx.say(usage_message(ctx, "!wordfilter remove <word>").await)
+            .await?;
         return Ok(());
     };
 
@@ -331,17 +283,6 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_GUILD,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let words = list_filter_words(&ctx.data().db, guild_id.get()).await?;
 
     if words.is_empty() {
@@ -369,6 +310,208 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+/// Create or update Discord AutoMod keyword rules from the word filter list.
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "sync-automod",
+    category = "Moderation"
+)]
+pub async fn sync_automod(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let db = &ctx.data().db;
+    let config = get_word_filter_config(db, guild_id.get()).await?;
+    let (enabled, action) = match &config {
+        Some(cfg) => (cfg.enabled, cfg.action.as_str()),
+        None => (false, "log_only"),
+    };
+
+    let words = get_all_filter_words_for_guild(db, guild_id.get()).await?;
+    let (chunks, too_long) = chunk_automod_keywords(&words);
+
+    // Only reuse tracked rules that still exist; anything deleted by hand in
+    // Discord is recreated. Other keyword rules count toward the guild limit.
+    let existing_rules = guild_id.automod_rules(ctx.http()).await?;
+    let tracked = list_word_filter_automod_rules(db, guild_id.get()).await?;
+    let reusable: Vec<serenity::RuleId> = tracked
+        .iter()
+        .filter_map(|id| {
+            existing_rules
+                .iter()
+                .find(|rule| rule.id.get() == *id)
+                .map(|rule| rule.id)
+        })
+        .collect();
+    let foreign_keyword_rules = existing_rules
+        .iter()
+        .filter(|rule| {
+            matches!(rule.trigger, serenity::Trigger::Keyword { .. })
+                && !tracked.contains(&rule.id.get())
+        })
+        .count();
+    let available_rules = AUTOMOD_MAX_KEYWORD_RULES.saturating_sub(foreign_keyword_rules);
+
+    let dropped_words: usize = chunks
+        .iter()
+        .skip(available_rules)
+        .map(|chunk| chunk.len())
+        .sum();
+    let chunks: Vec<Vec<String>> = chunks.into_iter().take(available_rules).collect();
+    let total_rules = chunks.len();
+
+    // A log-only filter leaves messages up, so its rules only alert the
+    // modlog channel; the message itself is still logged as a case when the
+    // bot sees it.
+    let actions = if action == "log_only" {
+        let Some(modlog_channel_id) = get_modlog_channel_id(db, guild_id.get()).await? else {
+            ctx.say(format!(
+                "The word filter only logs, so its AutoMod rules alert the modlog channel \
+                 instead of blocking. Set one with `{}modlogchannel` first.",
+                command_prefix(ctx).await
+            ))
+            .await?;
+            return Ok(());
+        };
+        vec![serenity::Action::Alert(serenity::ChannelId::new(
+            modlog_channel_id,
+        ))]
+    } else {
+        let mut actions = vec![serenity::Action::BlockMessage {
+            custom_message: Some(AUTOMOD_BLOCK_MESSAGE.to_owned()),
+        }];
+        if action == "timeout_delete_and_log" {
+            actions.push(serenity::Action::Timeout(std::time::Duration::from_secs(
+                300,
+            )));
+        }
+        actions
+    };
+
+    let audit_reason = format!("Word filter sync by {}", ctx.author().name);
+    let mut synced_rule_ids = Vec::with_capacity(total_rules);
+
+    let synced = async {
+        for (index, keywords) in chunks.into_iter().enumerate() {
+            let name = if total_rules > 1 {
+                format!("{} ({}/{})", AUTOMOD_RULE_NAME, index + 1, total_rules)
+            } else {
+                AUTOMOD_RULE_NAME.to_owned()
+            };
+
+            let builder = serenity::EditAutoModRule::new()
+                .name(name)
+                .event_type(serenity::AutomodEventType::MessageSend)
+                .trigger(serenity::Trigger::Keyword {
+                    strings: keywords,
+                    regex_patterns: Vec::new(),
+                    allow_list: Vec::new(),
+                })
+                .actions(actions.clone())
+                .enabled(enabled)
+                .audit_log_reason(&audit_reason);
+
+            let rule = match reusable.get(index) {
+                Some(rule_id) => {
+                    guild_id
+                        .edit_automod_rule(ctx.http(), *rule_id, builder)
+                        .await?
+                }
+                None => guild_id.create_automod_rule(ctx.http(), builder).await?,
+            };
+
+            synced_rule_ids.push(rule.id.get());
+        }
+
+        // Remove rules left over from a previously longer word list.
+        for rule_id in reusable.iter().skip(total_rules) {
+            guild_id.delete_automod_rule(ctx.http(), *rule_id).await?;
+        }
+
+        Ok::<_, serenity::Error>(())
+    }
+    .await;
+
+    if let Err(source) = synced {
+        // Keep tracking every rule that is ours, including ones created before
+        // the failure, so the next sync reuses them instead of counting them
+        // against the limit as someone else's.
+        let mut owned = synced_rule_ids.clone();
+        owned.extend(reusable.iter().skip(owned.len()).map(|id| id.get()));
+        set_word_filter_automod_rules(db, guild_id.get(), &owned).await?;
+        return Err(source.into());
+    }
+
+    set_word_filter_automod_rules(db, guild_id.get(), &synced_rule_ids).await?;
+
+    let synced_words = words.len() - too_long - dropped_words;
+    let mut lines = vec![
+        format!("**Rules :** {}", total_rules),
+        format!("**Keywords Synced :** {}", synced_words),
+        format!(
+            "**Rule Status :** {}",
+            if enabled { "Enabled" } else { "Disabled" }
+        ),
+    ];
+
+    if too_long > 0 {
+        lines.push(format!(
+            "**Skipped :** {} word(s) longer than {} characters",
+            too_long, AUTOMOD_MAX_KEYWORD_CHARS
+        ));
+    }
+
+    if dropped_words > 0 {
+        lines.push(format!(
+            "**Not Synced :** {} word(s) — this server has reached the AutoMod keyword rule limit",
+            dropped_words
+        ));
+    }
+
+    if words.is_empty() {
+        lines.push(String::new());
+        lines.push("The word filter list is empty, so no AutoMod rules are needed.".to_owned());
+    } else if !enabled {
+        lines.push(String::new());
+        lines.push(
+            "The word filter is disabled, so the AutoMod rules were synced in a disabled state."
+                .to_owned(),
+        );
+    }
+
+    let embed = serenity::CreateEmbed::new()
+        .title("Word Filter AutoMod Sync")
+        .description(lines.join("\n"))
+        .color(DEFAULT_EMBED_COLOR);
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Split the word list into AutoMod-sized keyword chunks. Returns the chunks
+/// and how many words were skipped for exceeding the keyword length limit.
+fn chunk_automod_keywords(words: &[String]) -> (Vec<Vec<String>>, usize) {
+    let mut keywords: Vec<String> = words
+        .iter()
+        .filter(|word| word.chars().count() <= AUTOMOD_MAX_KEYWORD_CHARS)
+        .cloned()
+        .collect();
+    let too_long = words.len() - keywords.len();
+
+    keywords.sort();
+
+    let chunks = keywords
+        .chunks(AUTOMOD_MAX_KEYWORDS_PER_RULE)
+        .map(<[String]>::to_vec)
+        .collect();
+
+    (chunks, too_long)
+}
+
 fn action_display(action: &str) -> &str {
     match action {
         "log_only" => "Only Log",
@@ -378,3 +521,31 @@ fn action_display(action: &str) -> &str {
         _ => "Unknown",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn automod_keywords_are_filtered_and_chunked() {
+        let mut words: Vec<String> = (0..2_500).map(|i| format!("word{i:04}")).collect();
+        words.push("x".repeat(AUTOMOD_MAX_KEYWORD_CHARS));
+        words.push("y".repeat(AUTOMOD_MAX_KEYWORD_CHARS + 1));
+        words.push("z".repeat(AUTOMOD_MAX_KEYWORD_CHARS + 20));
+
+        let (chunks, too_long) = chunk_automod_keywords(&words);
+
+        assert_eq!(too_long, 2);
+        assert_eq!(
+            chunks.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![1_000, 1_000, 501]
+        );
+        assert!(
+            chunks
+                .iter()
+                .flatten()
+                .all(|word| word.chars().count() <= AUTOMOD_MAX_KEYWORD_CHARS)
+        );
+        assert_eq!(chunks[0][0], "word0000");
+    }
+}
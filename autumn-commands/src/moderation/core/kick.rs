@@ -9,18 +9,26 @@ use crate::moderation::embeds::{
     usage_message,
 };
 use crate::moderation::logging::create_case_and_publish;
+use crate::moderation::safety_check::allow_destructive_action;
 use autumn_core::{Context, Error};
 use autumn_database::impls::cases::NewCase;
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "kick",
     desc: "Kick a user from the server.",
     category: "moderation",
     usage: "!kick <user> [reason]",
+    required_permissions: Some(serenity::Permissions::KICK_MEMBERS),
+    channel_scoped: false,
+    examples: &["!kick @user"],
 };
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    member_cooldown = 3
+)]
 pub async fn kick(
     ctx: Context<'_>,
     #[description = "The user to kick"] user: Option<serenity::User>,
@@ -33,19 +41,8 @@ pub async fn kick(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::KICK_MEMBERS,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(user) = user else {
-        ctx.say(usage_message(META.usage)).await?;
+        ctx.say(usage_message(ctx, META.usage).await).await?;
         return Ok(());
     };
 
@@ -59,6 +56,10 @@ pub async fn kick(
         return Ok(());
     }
 
+    if !allow_destructive_action(ctx, guild_id).await? {
+        return Ok(());
+    }
+
     let kick_result = guild_id
         .kick_with_reason(
             ctx.http(),
@@ -4,22 +4,29 @@ use tracing::error;
 use poise::serenity_prelude as serenity;
 
 use crate::CommandMeta;
-use crate::moderation::embeds::is_missing_permissions_error;
+use crate::moderation::embeds::{is_missing_permissions_error, usage_message};
 use crate::moderation::logging::create_case_and_publish;
 use autumn_core::{Context, Error};
 use autumn_database::impls::cases::NewCase;
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "purge",
     desc: "Delete the latest messages in this channel.",
     category: "moderation",
     usage: "!purge <amount>",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    channel_scoped: true,
+    examples: &["!purge 50"],
 };
 
 const MAX_PURGE: u16 = 100;
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    channel_cooldown = 5
+)]
 pub async fn purge(
     ctx: Context<'_>,
     #[description = "Amount of messages to purge"] amount: Option<u16>,
@@ -30,7 +37,7 @@ pub async fn purge(
     };
 
     let Some(requested) = amount else {
-        ctx.say(format!("Usage: `{}`", META.usage)).await?;
+        ctx.say(usage_message(ctx, META.usage).await).await?;
         return Ok(());
     };
 
@@ -39,17 +46,6 @@ pub async fn purge(
         return Ok(());
     }
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_MESSAGES,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let delete_count = requested.saturating_add(1).min(MAX_PURGE);
 
     let channel_id = ctx.channel_id();
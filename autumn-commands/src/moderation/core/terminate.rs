@@ -11,24 +11,34 @@ use crate::moderation::embeds::{
     usage_message,
 };
 use crate::moderation::logging::create_case_and_publish;
+use crate::moderation::safety_check::allow_destructive_action;
 use autumn_core::{Context, Error};
 use autumn_database::impls::cases::NewCase;
 use autumn_utils::confirmation::{ConfirmationResult, prompt_confirm_decline};
 use autumn_utils::parse::parse_duration_seconds;
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "terminate",
     desc: "Ban a user and purge their messages (DANGER)",
     category: "moderation",
     usage: "!terminate <user> [period] [reason]",
+    required_permissions: Some(
+        serenity::Permissions::BAN_MEMBERS.union(serenity::Permissions::MANAGE_MESSAGES),
+    ),
+    channel_scoped: false,
+    examples: &["!terminate @user", "!terminate @user 1d raid account"],
 };
 
 const SECONDS_PER_DAY: u64 = 86_400;
 const MAX_NATIVE_BAN_DELETE_DAYS: u8 = 7;
 const TERMINATE_CONFIRM_TIMEOUT_SECS: u64 = 30;
 
-#[poise::command(prefix_command, slash_command, category = "Moderation")]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Moderation",
+    member_cooldown = 30
+)]
 pub async fn terminate(
     ctx: Context<'_>,
     #[description = "The user to terminate"] user: Option<serenity::User>,
@@ -42,14 +52,8 @@ pub async fn terminate(
         return Ok(());
     };
 
-    let required_permissions =
-        serenity::Permissions::BAN_MEMBERS | serenity::Permissions::MANAGE_MESSAGES;
-    if !has_user_permission(ctx.http(), guild_id, ctx.author().id, required_permissions).await? {
-        return Ok(());
-    }
-
     let Some(user) = user else {
-        ctx.say(usage_message(META.usage)).await?;
+        ctx.say(usage_message(ctx, META.usage).await).await?;
         return Ok(());
     };
 
@@ -68,8 +72,8 @@ pub async fn terminate(
         Some(first) => {
             let Some(duration_secs) = parse_duration_seconds(first) else {
                 ctx.say(format!(
-                    "Invalid purge period. Usage: `{}` (examples: 30s, 10m, 2h, 7d)",
-                    META.usage
+                    "Invalid purge period. {} (examples: 30s, 10m, 2h, 7d)",
+                    usage_message(ctx, META.usage).await
                 ))
                 .await?;
                 return Ok(());
@@ -77,8 +81,8 @@ pub async fn terminate(
 
             if duration_secs > u64::from(MAX_NATIVE_BAN_DELETE_DAYS) * SECONDS_PER_DAY {
                 ctx.say(format!(
-                    "Invalid purge period. Max is 7d. Usage: `{}` (examples: 30s, 10m, 2h, 7d)",
-                    META.usage
+                    "Invalid purge period. Max is 7d. {} (examples: 30s, 10m, 2h, 7d)",
+                    usage_message(ctx, META.usage).await
                 ))
                 .await?;
                 return Ok(());
@@ -97,6 +101,10 @@ pub async fn terminate(
         .div_ceil(SECONDS_PER_DAY)
         .min(u64::from(MAX_NATIVE_BAN_DELETE_DAYS)) as u8;
 
+    if !allow_destructive_action(ctx, guild_id).await? {
+        return Ok(());
+    }
+
     let target_profile = target_profile_from_user(&user);
     let confirmation_embed = moderation_action_embed(
         &target_profile,
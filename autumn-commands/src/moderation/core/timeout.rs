@@ -15,13 +15,15 @@ use autumn_core::{Context, Error};
 use autumn_database::impls::cases::NewCase;
 use autumn_utils::formatting::format_compact_duration;
 use autumn_utils::parse::{has_duration_unit, parse_duration_seconds};
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "timeout",
     desc: "Timeout a user for a duration (default: 10m).",
     category: "moderation",
     usage: "!timeout <user> [duration] [reason]",
+    required_permissions: Some(serenity::Permissions::MODERATE_MEMBERS),
+    channel_scoped: false,
+    examples: &["!timeout @user 10m", "!timeout @user 1h spamming"],
 };
 
 const DEFAULT_TIMEOUT_SECS: u64 = 10 * 60;
@@ -90,19 +92,8 @@ pub async fn timeout(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MODERATE_MEMBERS,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(user) = user else {
-        ctx.say(usage_message(META.usage)).await?;
+        ctx.say(usage_message(ctx, META.usage).await).await?;
         return Ok(());
     };
 
@@ -123,8 +114,8 @@ pub async fn timeout(
         Some(raw) if !raw.is_empty() => {
             let Some(seconds) = parse_duration_seconds(raw) else {
                 ctx.say(format!(
-                    "Invalid duration. Usage: `{}` (examples: 30s, 10m, 2h, 1d)",
-                    META.usage
+                    "Invalid duration. {} (examples: 30s, 10m, 2h, 1d)",
+                    usage_message(ctx, META.usage).await
                 ))
                 .await?;
                 return Ok(());
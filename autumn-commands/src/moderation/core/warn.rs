@@ -10,13 +10,15 @@ use crate::moderation::logging::create_case_and_publish;
 use autumn_core::{Context, Error};
 use autumn_database::impls::cases::NewCase;
 use autumn_database::impls::warnings::record_warning;
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "warn",
     desc: "Issue a warning to a user.",
     category: "moderation",
     usage: "!warn <user> [reason]",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    channel_scoped: false,
+    examples: &["!warn @user please keep it civil"],
 };
 
 #[poise::command(prefix_command, slash_command, category = "Moderation")]
@@ -32,19 +34,8 @@ pub async fn warn(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_MESSAGES,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(user) = user else {
-        ctx.say(usage_message(META.usage)).await?;
+        ctx.say(usage_message(ctx, META.usage).await).await?;
         return Ok(());
     };
 
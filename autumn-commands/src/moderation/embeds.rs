@@ -1,5 +1,7 @@
 use poise::serenity_prelude as serenity;
 
+use crate::prefix::{command_prefix, with_prefix};
+use autumn_core::Context;
 use autumn_utils::embed::DEFAULT_EMBED_COLOR;
 
 #[derive(Clone, Debug)]
@@ -158,8 +160,10 @@ pub async fn send_moderation_target_dm_for_guild(
     .await
 }
 
-pub fn usage_message(usage: &str) -> String {
-    format!("Usage: `{usage}`")
+/// `usage` is written with the default prefix; it's shown with the guild's.
+pub async fn usage_message(ctx: Context<'_>, usage: &str) -> String {
+    let prefix = command_prefix(ctx).await;
+    format!("Usage: `{}`", with_prefix(&prefix, usage))
 }
 
 pub fn guild_only_message() -> &'static str {
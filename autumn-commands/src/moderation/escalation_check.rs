@@ -19,7 +19,7 @@ use autumn_database::impls::modlog_config::get_modlog_channel_id;
 use autumn_utils::embed::DEFAULT_EMBED_COLOR;
 use autumn_utils::formatting::{format_case_label, format_compact_duration};
 
-use crate::moderation::embeds::send_moderation_target_dm;
+use crate::moderation::embeds::{is_missing_permissions_error, send_moderation_target_dm};
 
 /// Result of an escalation check.
 pub struct EscalationResult {
@@ -116,7 +116,7 @@ pub async fn check_and_escalate(
     if let Ok(until) = serenity::Timestamp::from_unix_timestamp(until_unix) {
         let edit = serenity::EditMember::new().disable_communication_until_datetime(until);
         if let Err(source) = guild_id.edit_member(http, target_user.id, edit).await {
-            if is_missing_permissions(&source) {
+            if is_missing_permissions_error(&source) {
                 warn!(
                     user_id = %target_user.id,
                     "missing permissions to auto-timeout user (check role hierarchy)"
@@ -236,11 +236,3 @@ async fn publish_auto_timeout_to_modlog(
 
     Ok(())
 }
-
-fn is_missing_permissions(source: &serenity::Error) -> bool {
-    matches!(
-        source,
-        serenity::Error::Http(serenity::HttpError::UnsuccessfulRequest(response))
-            if response.status_code.as_u16() == 403 || response.error.code == 50013
-    )
-}
@@ -7,14 +7,16 @@ mod core_group;
 #[path = "reversals/mod.rs"]
 mod reversals_group;
 
-pub use case_group::{case, modlogs, notes, userlogs, warnings};
+pub use case_group::{case, modlogs, notes, summarize, userlogs, warnings};
 pub use config_group::{
-    aitoggle, escalation, modlogchannel, permissions, setup, userlogchannel, wordfilter,
+    aihistory, aimod, aiprompt, aitoggle, aiusage, antinuke, attachmentfilter, command, escalation,
+    modlogchannel, permissions, safety, setup, userlogchannel, wordfilter,
 };
 pub use core_group::{ban, kick, purge, terminate, timeout, warn};
-pub use embeds::send_moderation_target_dm_for_guild;
+pub use embeds::{is_missing_permissions_error, send_moderation_target_dm_for_guild};
 pub use reversals_group::{unban, untimeout, unwarn};
 
 pub(crate) mod embeds;
 pub mod escalation_check;
 mod logging;
+pub(crate) mod safety_check;
@@ -11,13 +11,15 @@ use crate::moderation::embeds::{
 use crate::moderation::logging::create_case_and_publish;
 use autumn_core::{Context, Error};
 use autumn_database::impls::cases::NewCase;
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "unban",
     desc: "Unban a user from the server.",
     category: "moderation",
     usage: "!unban <user> [reason]",
+    required_permissions: Some(serenity::Permissions::BAN_MEMBERS),
+    channel_scoped: false,
+    examples: &["!unban 123456789012345678 appealed"],
 };
 
 #[poise::command(prefix_command, slash_command, category = "Moderation")]
@@ -33,19 +35,8 @@ pub async fn unban(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::BAN_MEMBERS,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(user) = user else {
-        ctx.say(usage_message(META.usage)).await?;
+        ctx.say(usage_message(ctx, META.usage).await).await?;
         return Ok(());
     };
 
@@ -8,13 +8,15 @@ use autumn_core::{Context, Error};
 use autumn_database::impls::cases::NewCase;
 use autumn_database::impls::warnings::{clear_warnings, remove_warning_by_number};
 use autumn_utils::confirmation::{prompt_confirm_decline, resolve_confirmation_result};
-use autumn_utils::permissions::has_user_permission;
 
 pub const META: CommandMeta = CommandMeta {
     name: "unwarn",
     desc: "Remove a warning from a user.",
     category: "moderation",
     usage: "!unwarn <user> <warn_number|all>",
+    required_permissions: Some(serenity::Permissions::MANAGE_MESSAGES),
+    channel_scoped: false,
+    examples: &["!unwarn @user 2", "!unwarn @user all"],
 };
 
 const UNWARN_ALL_CONFIRM_TIMEOUT_SECS: u64 = 30;
@@ -30,19 +32,8 @@ pub async fn unwarn(
         return Ok(());
     };
 
-    if !has_user_permission(
-        ctx.http(),
-        guild_id,
-        ctx.author().id,
-        serenity::Permissions::MANAGE_MESSAGES,
-    )
-    .await?
-    {
-        return Ok(());
-    }
-
     let Some(user) = user else {
-        ctx.say(usage_message(META.usage)).await?;
+        ctx.say(usage_message(ctx, META.usage).await).await?;
         return Ok(());
     };
 
@@ -54,7 +45,7 @@ pub async fn unwarn(
     let target_label = user.global_name.as_deref().unwrap_or(&user.name).to_owned();
 
     let Some(selector) = selector.as_deref() else {
-        ctx.say(usage_message(META.usage)).await?;
+        ctx.say(usage_message(ctx, META.usage).await).await?;
         return Ok(());
     };
 
@@ -0,0 +1,152 @@
+//! Per-moderator limit on destructive actions.
+//!
+//! Called before a ban, kick or termination. A moderator who goes over the
+//! guild's hourly limit is paused from further destructive actions, and the
+//! modlog channel (or the owner, without one) is alerted. This contains the
+//! damage a runaway script or a compromised moderator account can do.
+
+use poise::serenity_prelude as serenity;
+use tracing::error;
+
+use crate::prefix::command_prefix;
+use autumn_core::{Context, Error};
+use autumn_database::impls::moderator_safety::{
+    count_destructive_actions_since, get_moderator_pause, get_moderator_safety_config,
+    pause_moderator,
+};
+use autumn_database::impls::modlog_config::get_modlog_channel_id;
+use autumn_utils::embed::DEFAULT_EMBED_COLOR;
+use autumn_utils::time::now_unix_secs;
+
+/// Window the limit applies to, and how long an offending moderator is paused.
+const SAFETY_WINDOW_SECS: i64 = 3_600;
+
+/// Whether the invoking moderator may take another destructive action. Replies
+/// with the reason and returns `false` when they may not.
+pub(crate) async fn allow_destructive_action(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+) -> Result<bool, Error> {
+    let db = &ctx.data().db;
+    let moderator_id = ctx.author().id;
+    let now = i64::try_from(now_unix_secs()).unwrap_or(i64::MAX);
+
+    let pause = get_moderator_pause(db, guild_id.get(), moderator_id.get()).await?;
+    if let Some(pause) = pause.filter(|pause| pause.is_active(now)) {
+        ctx.say(paused_message(ctx, pause.paused_until).await)
+            .await?;
+        return Ok(false);
+    }
+
+    let config = get_moderator_safety_config(db, guild_id.get()).await?;
+    if config.destructive_limit_per_hour == 0 {
+        return Ok(true);
+    }
+
+    // The owner can't be paused by anyone, so don't pretend to.
+    if is_guild_owner(ctx, guild_id, moderator_id).await {
+        return Ok(true);
+    }
+
+    // Actions from before the last pause ended were already dealt with.
+    let since = pause.map_or(now - SAFETY_WINDOW_SECS, |pause| {
+        pause.paused_until.max(now - SAFETY_WINDOW_SECS)
+    });
+    let recent =
+        count_destructive_actions_since(db, guild_id.get(), moderator_id.get(), since).await?;
+
+    if recent < i64::from(config.destructive_limit_per_hour) {
+        return Ok(true);
+    }
+
+    let paused_until = now + SAFETY_WINDOW_SECS;
+    pause_moderator(db, guild_id.get(), moderator_id.get(), now, paused_until).await?;
+    alert_admins(ctx, guild_id, moderator_id, recent, paused_until).await;
+
+    ctx.say(paused_message(ctx, paused_until).await).await?;
+    Ok(false)
+}
+
+async fn paused_message(ctx: Context<'_>, paused_until: i64) -> String {
+    let prefix = command_prefix(ctx).await;
+    format!(
+        "You've reached this server's limit on bans and kicks, so they're paused for you until <t:{}:t> (<t:{}:R>). An administrator can lift this with `{}safety unpause`.",
+        paused_until, paused_until, prefix
+    )
+}
+
+pub(crate) async fn is_guild_owner(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    user_id: serenity::UserId,
+) -> bool {
+    if let Some(guild) = ctx.guild() {
+        return guild.owner_id == user_id;
+    }
+
+    match guild_id.to_partial_guild(ctx.http()).await {
+        Ok(guild) => guild.owner_id == user_id,
+        Err(source) => {
+            error!(?source, "failed to look up guild owner for safety limit");
+            false
+        }
+    }
+}
+
+/// Post the pause to the modlog channel, or DM the owner when there isn't one.
+async fn alert_admins(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    moderator_id: serenity::UserId,
+    recent: i64,
+    paused_until: i64,
+) {
+    let prefix = command_prefix(ctx).await;
+    let embed = serenity::CreateEmbed::new()
+        .color(DEFAULT_EMBED_COLOR)
+        .title("Moderator Paused")
+        .description(format!(
+            "**Moderator :** <@{}>\n\
+             **Actions :** {} bans/kicks within the hour\n\
+             **Paused Until :** <t:{}:f>\n\n\
+             Lift the pause with `{}safety unpause {}`.",
+            moderator_id, recent, paused_until, prefix, moderator_id
+        ));
+
+    let modlog_channel_id = match get_modlog_channel_id(&ctx.data().db, guild_id.get()).await {
+        Ok(channel_id) => channel_id,
+        Err(source) => {
+            error!(?source, "failed to read modlog channel for safety alert");
+            None
+        }
+    };
+
+    if let Some(channel_id) = modlog_channel_id {
+        if let Err(source) = serenity::ChannelId::new(channel_id)
+            .send_message(ctx.http(), serenity::CreateMessage::new().embed(embed))
+            .await
+        {
+            error!(?source, "failed to post safety alert to modlog channel");
+        }
+        return;
+    }
+
+    let owner_id = match guild_id.to_partial_guild(ctx.http()).await {
+        Ok(guild) => guild.owner_id,
+        Err(source) => {
+            error!(?source, "failed to look up guild owner for safety alert");
+            return;
+        }
+    };
+
+    let dm = async {
+        owner_id
+            .create_dm_channel(ctx.http())
+            .await?
+            .send_message(ctx.http(), serenity::CreateMessage::new().embed(embed))
+            .await
+    };
+    if let Err(source) = dm.await {
+        error!(?source, "failed to DM guild owner safety alert");
+    }
+}
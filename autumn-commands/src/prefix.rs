@@ -0,0 +1,77 @@
+//! Per-guild message-command prefixes.
+//!
+//! Guilds can replace the default `!` with `!prefix set`. Usage strings and
+//! examples are written with the default prefix and rewritten with
+//! [`with_prefix`] when shown. Mentioning the bot always works as a prefix,
+//! so a forgotten prefix can be looked up with `@Autumn prefix`.
+
+use poise::serenity_prelude as serenity;
+use tracing::error;
+
+use autumn_core::{Context, Data, Error};
+use autumn_database::impls::guild_prefix::get_guild_prefix;
+use autumn_utils::COMMAND_PREFIX;
+
+pub const MAX_PREFIX_CHARS: usize = 5;
+
+/// The prefix commands use in `guild_id`, or the default outside guilds or
+/// when the guild's can't be read.
+pub async fn resolve_prefix(data: &Data, guild_id: Option<serenity::GuildId>) -> String {
+    let Some(guild_id) = guild_id else {
+        return COMMAND_PREFIX.to_string();
+    };
+
+    match get_guild_prefix(&data.db, guild_id.get()).await {
+        Ok(prefix) => prefix.unwrap_or_else(|| COMMAND_PREFIX.to_string()),
+        Err(source) => {
+            error!(?source, "failed to read guild prefix");
+            COMMAND_PREFIX.to_string()
+        }
+    }
+}
+
+/// The prefix for the invoking guild.
+pub async fn command_prefix(ctx: Context<'_>) -> String {
+    resolve_prefix(ctx.data(), ctx.guild_id()).await
+}
+
+/// poise `dynamic_prefix` callback.
+pub async fn dynamic_prefix(
+    ctx: poise::PartialContext<'_, Data, Error>,
+) -> Result<Option<String>, Error> {
+    Ok(Some(resolve_prefix(ctx.data, ctx.guild_id).await))
+}
+
+/// Rewrite a usage string or example written with the default prefix.
+pub fn with_prefix(prefix: &str, text: &str) -> String {
+    match text.strip_prefix(COMMAND_PREFIX) {
+        Some(rest) => format!("{prefix}{rest}"),
+        None => text.to_owned(),
+    }
+}
+
+/// Short, no whitespace, and no backticks so it renders inside code spans.
+pub fn is_valid_prefix(prefix: &str) -> bool {
+    !prefix.is_empty()
+        && prefix.chars().count() <= MAX_PREFIX_CHARS
+        && !prefix.chars().any(|c| c.is_whitespace() || c == '`')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrites_default_prefix_and_validates() {
+        assert_eq!(with_prefix("?", "!ban <user>"), "?ban <user>");
+        assert_eq!(with_prefix("a!", "!help"), "a!help");
+        assert_eq!(with_prefix("?", "ban"), "ban");
+
+        assert!(is_valid_prefix("?"));
+        assert!(is_valid_prefix("au!"));
+        assert!(!is_valid_prefix(""));
+        assert!(!is_valid_prefix("a b"));
+        assert!(!is_valid_prefix("`"));
+        assert!(!is_valid_prefix("toolong"));
+    }
+}
@@ -28,7 +28,7 @@ pub fn page_out_of_range_message(requested_page: usize, total_pages: usize) -> S
     )
 }
 
-pub fn grouped_help_description(commands: &[&CommandMeta]) -> String {
+pub fn grouped_help_description(commands: &[&CommandMeta], prefix: &str) -> String {
     let mut out = String::new();
     let mut current_category: Option<&str> = None;
 
@@ -41,7 +41,7 @@ pub fn grouped_help_description(commands: &[&CommandMeta]) -> String {
             current_category = Some(command.category);
         }
 
-        out.push_str(&format!("`{}`: {}\n", command.name, command.desc));
+        out.push_str(&format!("`{}{}`: {}\n", prefix, command.name, command.desc));
     }
 
     if out.is_empty() {
@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use autumn_core::{Context, Error};
+use autumn_database::impls::llm_chat::delete_llm_user_history;
+use autumn_utils::confirmation::{prompt_confirm_decline, resolve_confirmation_result};
+
+pub const META: CommandMeta = CommandMeta {
+    name: "forgetme",
+    desc: "Delete everything you've said to the AI in this server.",
+    category: "utility",
+    usage: "!forgetme",
+    required_permissions: None,
+    channel_scoped: false,
+    examples: &[],
+};
+
+const FORGETME_CONFIRM_TIMEOUT_SECS: u64 = 30;
+
+#[poise::command(prefix_command, slash_command, category = "Utility")]
+pub async fn forgetme(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say("This command only works in servers.").await?;
+        return Ok(());
+    };
+
+    let confirm_embed = serenity::CreateEmbed::new().description(
+        "This deletes every message you've sent to the AI in this server and its replies \
+         to you, along with the conversation summaries of the channels you talked in.",
+    );
+
+    let confirmation = prompt_confirm_decline(
+        ctx,
+        "Confirm AI history deletion",
+        confirm_embed,
+        Duration::from_secs(FORGETME_CONFIRM_TIMEOUT_SECS),
+    )
+    .await?;
+
+    let Some(interaction) = resolve_confirmation_result(
+        ctx,
+        confirmation,
+        "Timed out",
+        "Nothing was deleted.",
+        "Deleting your AI history...",
+    )
+    .await?
+    else {
+        return Ok(());
+    };
+
+    let removed =
+        delete_llm_user_history(&ctx.data().db, guild_id.get(), ctx.author().id.get()).await?;
+
+    let content = if removed == 0 {
+        "There was no AI history of yours to delete.".to_owned()
+    } else {
+        format!(
+            "Deleted **{}** AI message{} from your conversations.",
+            removed,
+            if removed == 1 { "" } else { "s" }
+        )
+    };
+
+    interaction
+        .edit_response(
+            ctx.http(),
+            serenity::EditInteractionResponse::new().content(content),
+        )
+        .await?;
+
+    Ok(())
+}
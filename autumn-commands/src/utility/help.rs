@@ -1,3 +1,5 @@
+use crate::access::CommandAccess;
+use crate::prefix::command_prefix;
 use crate::utility::embeds::{
     grouped_help_description, no_commands_message, page_out_of_range_message,
     unknown_category_message,
@@ -5,14 +7,15 @@ use crate::utility::embeds::{
 use crate::{COMMANDS, CommandMeta};
 use autumn_core::{Context, Error};
 use autumn_utils::pagination::{page_window, paginate_embed_pages, total_pages};
-use autumn_utils::permissions::has_user_permission;
-use poise::serenity_prelude as serenity;
 
 pub const META: CommandMeta = CommandMeta {
     name: "help",
     desc: "Lists out all available commands.",
     category: "utility",
     usage: "!help [page|category]",
+    required_permissions: None,
+    channel_scoped: false,
+    examples: &["!help", "!help moderation"],
 };
 
 const HELP_COMMANDS_PER_PAGE: usize = 20;
@@ -33,26 +36,19 @@ pub async fn help(
         _ => None,
     };
 
-    let can_view_moderation = match ctx.guild_id() {
-        Some(guild_id) => {
-            has_user_permission(
-                ctx.http(),
-                guild_id,
-                ctx.author().id,
-                serenity::Permissions::MANAGE_MESSAGES,
-            )
-            .await?
-        }
-        None => false,
+    // Only list what the author could actually run here.
+    let access = match ctx.guild_id() {
+        Some(guild_id) => Some(CommandAccess::load(ctx, guild_id).await?),
+        None => None,
+    };
+    let can_use = |command: &CommandMeta| match &access {
+        Some(access) => access.can_use(command),
+        None => command.required_permissions.is_none(),
     };
-
-    if category.as_deref() == Some("moderation") && !can_view_moderation {
-        return Ok(());
-    }
 
     let mut categories: Vec<&str> = COMMANDS
         .iter()
-        .filter(|cmd| cmd.category != "moderation" || can_view_moderation)
+        .filter(|cmd| can_use(cmd))
         .map(|c| c.category)
         .collect();
     categories.sort_unstable();
@@ -66,7 +62,10 @@ pub async fn help(
         return Ok(());
     }
 
-    let commands = sorted_commands(category.as_deref());
+    let commands: Vec<&CommandMeta> = sorted_commands(category.as_deref())
+        .into_iter()
+        .filter(|cmd| can_use(cmd))
+        .collect();
     if commands.is_empty() {
         ctx.say(no_commands_message(category.as_deref())).await?;
         return Ok(());
@@ -81,10 +80,11 @@ pub async fn help(
         return Ok(());
     }
 
+    let prefix = command_prefix(ctx).await;
     let pages = (1..=total)
         .map(|page| {
             let (start, end) = page_window(commands.len(), HELP_COMMANDS_PER_PAGE, page);
-            grouped_help_description(&commands[start..end])
+            grouped_help_description(&commands[start..end], &prefix)
         })
         .collect::<Vec<_>>();
     paginate_embed_pages(ctx, "Available Commands", &pages, requested_page).await?;
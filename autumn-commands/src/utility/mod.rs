@@ -1,6 +1,8 @@
 mod embeds;
+pub mod forgetme;
 pub mod help;
 pub mod pagetest;
 pub mod ping;
+pub mod prefix;
 pub mod universe;
 pub mod usage;
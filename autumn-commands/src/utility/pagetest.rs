@@ -8,6 +8,9 @@ pub const META: CommandMeta = CommandMeta {
     desc: "Test embed pagination behavior.",
     category: "utility",
     usage: "!pagetest [page]",
+    required_permissions: None,
+    channel_scoped: false,
+    examples: &["!pagetest 2"],
 };
 
 const ITEMS_PER_PAGE: usize = 5;
@@ -7,6 +7,9 @@ pub const META: CommandMeta = CommandMeta {
     desc: "Replies with Pong!",
     category: "utility",
     usage: "!ping",
+    required_permissions: None,
+    channel_scoped: false,
+    examples: &[],
 };
 
 #[poise::command(prefix_command, slash_command, category = "Utility")]
@@ -0,0 +1,85 @@
+use poise::serenity_prelude as serenity;
+
+use crate::CommandMeta;
+use crate::access::has_command_permission;
+use crate::moderation::embeds::{guild_only_message, usage_message};
+use crate::prefix::{MAX_PREFIX_CHARS, command_prefix, is_valid_prefix};
+use autumn_core::{Context, Error};
+use autumn_database::impls::guild_prefix::{clear_guild_prefix, set_guild_prefix};
+use autumn_utils::COMMAND_PREFIX;
+
+pub const META: CommandMeta = CommandMeta {
+    name: "prefix",
+    desc: "Show or change this server's command prefix.",
+    category: "utility",
+    usage: "!prefix [set <prefix>|reset]",
+    required_permissions: None,
+    channel_scoped: false,
+    examples: &["!prefix", "!prefix set ?", "!prefix reset"],
+};
+
+#[poise::command(prefix_command, slash_command, category = "Utility")]
+pub async fn prefix(
+    ctx: Context<'_>,
+    #[description = "set <prefix> or reset"]
+    #[rest]
+    input: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        ctx.say(guild_only_message()).await?;
+        return Ok(());
+    };
+
+    let raw = input.as_deref().map(str::trim).unwrap_or_default();
+    let mut parts = raw.split_whitespace();
+    let subcommand = parts.next().map(str::to_ascii_lowercase);
+    let value = parts.next();
+
+    let Some(subcommand) = subcommand else {
+        let bot_id = ctx.cache().current_user().id;
+        ctx.say(format!(
+            "This server's prefix is `{}`. Mentioning <@{}> works too.",
+            command_prefix(ctx).await,
+            bot_id
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    // Anyone can look the prefix up; changing it needs Manage Server.
+    if !has_command_permission(ctx, guild_id, serenity::Permissions::MANAGE_GUILD).await? {
+        return Ok(());
+    }
+
+    let db = &ctx.data().db;
+    match subcommand.as_str() {
+        "set" => {
+            let Some(prefix) = value.filter(|_| parts.next().is_none()) else {
+                ctx.say(usage_message(ctx, "!prefix set <prefix>").await)
+                    .await?;
+                return Ok(());
+            };
+            if !is_valid_prefix(prefix) {
+                ctx.say(format!(
+                    "A prefix is 1-{} characters, without spaces or backticks.",
+                    MAX_PREFIX_CHARS
+                ))
+                .await?;
+                return Ok(());
+            }
+
+            set_guild_prefix(db, guild_id.get(), prefix).await?;
+            ctx.say(format!("The prefix is now `{}`.", prefix)).await?;
+        }
+        "reset" => {
+            clear_guild_prefix(db, guild_id.get()).await?;
+            ctx.say(format!("The prefix is back to `{}`.", COMMAND_PREFIX))
+                .await?;
+        }
+        _ => {
+            ctx.say(usage_message(ctx, META.usage).await).await?;
+        }
+    }
+
+    Ok(())
+}
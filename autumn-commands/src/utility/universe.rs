@@ -7,6 +7,9 @@ pub const META: CommandMeta = CommandMeta {
     desc: "The answer to the universe.",
     category: "utility",
     usage: "!universe",
+    required_permissions: None,
+    channel_scoped: false,
+    examples: &[],
 };
 
 #[poise::command(prefix_command, slash_command, category = "Utility")]
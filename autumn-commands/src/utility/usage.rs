@@ -1,14 +1,17 @@
 use autumn_core::{Context, Error};
-use autumn_utils::permissions::has_user_permission;
-use poise::serenity_prelude as serenity;
 
-use crate::{COMMANDS, CommandMeta};
+use crate::access::CommandAccess;
+use crate::prefix::{command_prefix, with_prefix};
+use crate::{CommandMeta, command_meta};
 
 pub const META: CommandMeta = CommandMeta {
     name: "usage",
     desc: "Show usage syntax for a specific command.",
     category: "utility",
     usage: "!usage <command>",
+    required_permissions: None,
+    channel_scoped: false,
+    examples: &["!usage ban"],
 };
 
 #[poise::command(prefix_command, slash_command, category = "Utility")]
@@ -16,44 +19,42 @@ pub async fn usage(
     ctx: Context<'_>,
     #[description = "Command name"] command: Option<String>,
 ) -> Result<(), Error> {
+    let prefix = command_prefix(ctx).await;
     let Some(raw_name) = command.as_deref() else {
-        ctx.say(format!("Usage: `{}`", META.usage)).await?;
+        ctx.say(format!("Usage: `{}`", with_prefix(&prefix, META.usage)))
+            .await?;
         return Ok(());
     };
 
-    let lookup = raw_name.trim().trim_start_matches('!').to_ascii_lowercase();
+    let lookup = raw_name.trim();
+    let lookup = lookup
+        .strip_prefix(prefix.as_str())
+        .unwrap_or(lookup)
+        .trim_start_matches('!')
+        .to_ascii_lowercase();
 
-    let Some(command) = COMMANDS.iter().find(|command| command.name == lookup) else {
+    let Some(command) = command_meta(&lookup) else {
         ctx.say(format!("Unknown command: `{}`", lookup)).await?;
         return Ok(());
     };
 
-    if let (Some(guild_id), Some(required_permissions)) = (
-        ctx.guild_id(),
-        required_permissions_for_command(command.name),
-    ) && !has_user_permission(ctx.http(), guild_id, ctx.author().id, required_permissions)
-        .await?
+    if let Some(guild_id) = ctx.guild_id()
+        && !CommandAccess::load(ctx, guild_id).await?.can_use(command)
     {
         return Ok(());
     }
 
-    ctx.say(format!("Usage: `{}`", command.usage)).await?;
+    ctx.say(usage_with_examples(command, &prefix)).await?;
     Ok(())
 }
 
-fn required_permissions_for_command(command_name: &str) -> Option<serenity::Permissions> {
-    match command_name {
-        "ban" | "unban" => Some(serenity::Permissions::BAN_MEMBERS),
-        "kick" => Some(serenity::Permissions::KICK_MEMBERS),
-        "timeout" | "untimeout" => Some(serenity::Permissions::MODERATE_MEMBERS),
-        "warn" | "warnings" | "unwarn" | "purge" | "permissions" | "modlogs" | "userlogs"
-        | "case" | "notes" => Some(serenity::Permissions::MANAGE_MESSAGES),
-        "modlogchannel" | "userlogchannel" | "aitoggle" | "setup" => {
-            Some(serenity::Permissions::MANAGE_GUILD)
+fn usage_with_examples(command: &CommandMeta, prefix: &str) -> String {
+    let mut out = format!("Usage: `{}`", with_prefix(prefix, command.usage));
+    if !command.examples.is_empty() {
+        out.push_str("\nExamples:");
+        for example in command.examples {
+            out.push_str(&format!("\n`{}`", with_prefix(prefix, example)));
         }
-        "terminate" => {
-            Some(serenity::Permissions::BAN_MEMBERS | serenity::Permissions::MANAGE_MESSAGES)
-        }
-        _ => None,
     }
+    out
 }
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS attachment_filter_channel_rules;
+DROP TABLE IF EXISTS attachment_filter_extensions;
+DROP TABLE IF EXISTS attachment_filter_config;
@@ -0,0 +1,31 @@
+-- Attachment filter configuration per guild
+CREATE TABLE IF NOT EXISTS attachment_filter_config (
+    guild_id        BIGINT PRIMARY KEY,
+    enabled         BOOLEAN NOT NULL DEFAULT FALSE,
+    action          TEXT    NOT NULL DEFAULT 'delete_and_log',
+    -- action values: 'log_only', 'delete_and_log', 'warn_and_log', 'timeout_delete_and_log'
+    extension_mode  TEXT    NOT NULL DEFAULT 'deny',
+    -- extension_mode values: 'deny' (block listed), 'allow' (block everything not listed)
+    max_size_bytes  BIGINT
+);
+
+-- Extension deny/allow list per guild (stored lowercase, without the leading dot)
+CREATE TABLE IF NOT EXISTS attachment_filter_extensions (
+    id         BIGSERIAL PRIMARY KEY,
+    guild_id   BIGINT NOT NULL,
+    extension  TEXT   NOT NULL,
+    created_at BIGINT NOT NULL DEFAULT (EXTRACT(EPOCH FROM NOW())::BIGINT),
+    UNIQUE (guild_id, extension)
+);
+
+CREATE INDEX IF NOT EXISTS idx_attachment_filter_extensions_guild
+    ON attachment_filter_extensions (guild_id);
+
+-- Per-channel media rules (channels without a row allow both images and videos)
+CREATE TABLE IF NOT EXISTS attachment_filter_channel_rules (
+    guild_id     BIGINT  NOT NULL,
+    channel_id   BIGINT  NOT NULL,
+    allow_images BOOLEAN NOT NULL DEFAULT TRUE,
+    allow_videos BOOLEAN NOT NULL DEFAULT TRUE,
+    PRIMARY KEY (guild_id, channel_id)
+);
@@ -0,0 +1,11 @@
+-- Discord AutoMod keyword rules created by `!wordfilter sync-automod`.
+-- A guild's word list may be split across several rules.
+CREATE TABLE IF NOT EXISTS word_filter_automod_rules (
+    rule_id     BIGINT PRIMARY KEY,
+    guild_id    BIGINT NOT NULL,
+    chunk_index INTEGER NOT NULL,
+    created_at  BIGINT NOT NULL DEFAULT (EXTRACT(EPOCH FROM NOW())::BIGINT),
+    UNIQUE (guild_id, chunk_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_word_filter_automod_rules_guild ON word_filter_automod_rules (guild_id);
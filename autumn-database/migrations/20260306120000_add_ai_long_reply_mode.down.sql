@@ -0,0 +1,2 @@
+ALTER TABLE guild_ai_config
+    DROP COLUMN IF EXISTS long_reply_mode;
@@ -0,0 +1,4 @@
+-- How LLM replies over Discord's 2000-character limit are delivered:
+-- 'split' sends a reply chain, 'attachment' attaches the full answer as a .md file.
+ALTER TABLE guild_ai_config
+    ADD COLUMN IF NOT EXISTS long_reply_mode TEXT NOT NULL DEFAULT 'split';
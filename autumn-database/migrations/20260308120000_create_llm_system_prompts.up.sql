@@ -0,0 +1,16 @@
+-- LLM system prompts per guild. A NULL channel_id is the guild-wide prompt;
+-- a row with a channel_id overrides it for that channel.
+CREATE TABLE IF NOT EXISTS llm_system_prompts (
+    id         BIGSERIAL PRIMARY KEY,
+    guild_id   BIGINT NOT NULL,
+    channel_id BIGINT NULL,
+    prompt     TEXT   NOT NULL,
+    updated_by BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL DEFAULT (EXTRACT(EPOCH FROM NOW())::BIGINT)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_llm_system_prompts_guild
+    ON llm_system_prompts (guild_id) WHERE channel_id IS NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_llm_system_prompts_channel
+    ON llm_system_prompts (guild_id, channel_id) WHERE channel_id IS NOT NULL;
@@ -0,0 +1,9 @@
+-- Rolling summary of LLM chat history that has fallen out of the context window.
+CREATE TABLE IF NOT EXISTS llm_channel_memory (
+    guild_id              BIGINT NOT NULL,
+    channel_id            BIGINT NOT NULL,
+    summary               TEXT   NOT NULL,
+    summarized_through_id BIGINT NOT NULL,
+    updated_at            BIGINT NOT NULL,
+    PRIMARY KEY (guild_id, channel_id)
+);
@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS llm_threads;
+
+ALTER TABLE guild_ai_config
+    DROP COLUMN IF EXISTS thread_mode;
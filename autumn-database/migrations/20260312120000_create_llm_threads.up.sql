@@ -0,0 +1,14 @@
+-- Opt-in: start a thread for each new AI conversation instead of replying inline.
+ALTER TABLE guild_ai_config
+    ADD COLUMN IF NOT EXISTS thread_mode BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Threads Autumn created for AI conversations. Every message in them is a prompt.
+CREATE TABLE IF NOT EXISTS llm_threads (
+    thread_id         BIGINT PRIMARY KEY,
+    guild_id          BIGINT NOT NULL,
+    parent_channel_id BIGINT NOT NULL,
+    created_by        BIGINT NOT NULL,
+    created_at        BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_llm_threads_guild ON llm_threads (guild_id);
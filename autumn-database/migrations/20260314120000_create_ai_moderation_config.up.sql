@@ -0,0 +1,12 @@
+-- Opt-in AI classification of messages. Flagged messages become `review`
+-- cases for a moderator to approve or dismiss; nothing is actioned automatically.
+CREATE TABLE IF NOT EXISTS ai_moderation_config (
+    guild_id       BIGINT   PRIMARY KEY,
+    enabled        BOOLEAN  NOT NULL DEFAULT FALSE,
+    -- Minimum model confidence (0.0-1.0) for a message to be flagged.
+    threshold      REAL     NOT NULL DEFAULT 0.85,
+    -- Percentage of eligible messages that are classified at all.
+    sample_percent SMALLINT NOT NULL DEFAULT 100,
+    -- Most classifications per guild per minute.
+    max_per_minute INTEGER  NOT NULL DEFAULT 30
+);
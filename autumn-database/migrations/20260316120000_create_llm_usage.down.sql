@@ -0,0 +1,5 @@
+ALTER TABLE guild_ai_config
+    DROP COLUMN IF EXISTS daily_guild_token_quota,
+    DROP COLUMN IF EXISTS daily_user_token_quota;
+
+DROP TABLE IF EXISTS llm_usage;
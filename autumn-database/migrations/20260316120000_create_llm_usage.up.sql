@@ -0,0 +1,22 @@
+-- One row per LLM request, for usage reporting and daily quotas.
+CREATE TABLE IF NOT EXISTS llm_usage (
+    id                BIGSERIAL PRIMARY KEY,
+    guild_id          BIGINT  NOT NULL,
+    channel_id        BIGINT  NOT NULL,
+    user_id           BIGINT  NOT NULL,
+    kind              TEXT    NOT NULL,
+    prompt_tokens     INTEGER NOT NULL DEFAULT 0,
+    completion_tokens INTEGER NOT NULL DEFAULT 0,
+    latency_ms        INTEGER NOT NULL DEFAULT 0,
+    succeeded         BOOLEAN NOT NULL,
+    created_at        BIGINT  NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_llm_usage_guild_created ON llm_usage (guild_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_llm_usage_guild_user_created
+    ON llm_usage (guild_id, user_id, created_at);
+
+-- Daily token quotas; NULL means unlimited.
+ALTER TABLE guild_ai_config
+    ADD COLUMN IF NOT EXISTS daily_user_token_quota BIGINT,
+    ADD COLUMN IF NOT EXISTS daily_guild_token_quota BIGINT;
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS moderator_pauses;
+DROP TABLE IF EXISTS moderator_safety_config;
@@ -0,0 +1,15 @@
+-- Cap on destructive actions (bans, kicks, terminations) a single moderator
+-- can take per hour. Going over it pauses the moderator until an
+-- administrator lifts the pause or it expires. 0 disables the limit.
+CREATE TABLE IF NOT EXISTS moderator_safety_config (
+    guild_id                   BIGINT  PRIMARY KEY,
+    destructive_limit_per_hour INTEGER NOT NULL DEFAULT 10
+);
+
+CREATE TABLE IF NOT EXISTS moderator_pauses (
+    guild_id     BIGINT NOT NULL,
+    user_id      BIGINT NOT NULL,
+    paused_at    BIGINT NOT NULL,
+    paused_until BIGINT NOT NULL,
+    PRIMARY KEY (guild_id, user_id)
+);
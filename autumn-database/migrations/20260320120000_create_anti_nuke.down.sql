@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS anti_nuke_trusted_users;
+DROP TABLE IF EXISTS anti_nuke_config;
@@ -0,0 +1,17 @@
+-- Anti-nuke: a single actor making more than `max_actions` destructive
+-- changes (bans, kicks, channel/role deletions, permission changes) within
+-- `window_seconds` has their dangerous roles stripped.
+CREATE TABLE IF NOT EXISTS anti_nuke_config (
+    guild_id       BIGINT  PRIMARY KEY,
+    enabled        BOOLEAN NOT NULL DEFAULT FALSE,
+    max_actions    INTEGER NOT NULL DEFAULT 5,
+    window_seconds INTEGER NOT NULL DEFAULT 60
+);
+
+-- Actors anti-nuke never acts against, e.g. other bots that legitimately
+-- make bulk changes.
+CREATE TABLE IF NOT EXISTS anti_nuke_trusted_users (
+    guild_id BIGINT NOT NULL,
+    user_id  BIGINT NOT NULL,
+    PRIMARY KEY (guild_id, user_id)
+);
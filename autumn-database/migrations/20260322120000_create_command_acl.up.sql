@@ -0,0 +1,12 @@
+-- Per-guild command overrides. A role or user rule allows or denies a command
+-- regardless of the Discord permission it normally needs; channel rules
+-- restrict where it can be used.
+CREATE TABLE IF NOT EXISTS command_acl (
+    guild_id     BIGINT  NOT NULL,
+    command_name TEXT    NOT NULL,
+    -- 'role', 'user' or 'channel'.
+    target_kind  TEXT    NOT NULL,
+    target_id    BIGINT  NOT NULL,
+    allow        BOOLEAN NOT NULL,
+    PRIMARY KEY (guild_id, command_name, target_kind, target_id)
+);
@@ -0,0 +1,15 @@
+-- Commands turned off per guild. A NULL channel_id disables the command
+-- everywhere in the guild; a row with a channel_id disables it in that
+-- channel only.
+CREATE TABLE IF NOT EXISTS disabled_commands (
+    id           BIGSERIAL PRIMARY KEY,
+    guild_id     BIGINT NOT NULL,
+    command_name TEXT   NOT NULL,
+    channel_id   BIGINT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_disabled_commands_guild
+    ON disabled_commands (guild_id, command_name) WHERE channel_id IS NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_disabled_commands_channel
+    ON disabled_commands (guild_id, command_name, channel_id) WHERE channel_id IS NOT NULL;
@@ -0,0 +1,5 @@
+-- Message-command prefix per guild. Guilds without a row use the default.
+CREATE TABLE IF NOT EXISTS guild_prefixes (
+    guild_id BIGINT PRIMARY KEY,
+    prefix   TEXT   NOT NULL
+);
@@ -0,0 +1,2 @@
+ALTER TABLE llm_chat_history
+DROP COLUMN IF EXISTS reply_to_user_id;
@@ -0,0 +1,4 @@
+-- For assistant replies, the user whose prompt they answer, so forgetting
+-- a user also removes the replies that quote them.
+ALTER TABLE llm_chat_history
+ADD COLUMN IF NOT EXISTS reply_to_user_id BIGINT;
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS llm_channel_memory_resets;
@@ -0,0 +1,8 @@
+-- When a channel's AI history was last cleared or forgotten. A memory
+-- summary built from history read before then is stale and is not saved.
+CREATE TABLE IF NOT EXISTS llm_channel_memory_resets (
+    guild_id   BIGINT NOT NULL,
+    channel_id BIGINT NOT NULL,
+    reset_at   BIGINT NOT NULL,
+    PRIMARY KEY (guild_id, channel_id)
+);
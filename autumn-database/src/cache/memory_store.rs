@@ -0,0 +1,352 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::rate_limit::{
+    RateLimitDecision, TokenBucketState, sliding_window_step, token_bucket_step,
+};
+
+pub const DEFAULT_MEMORY_CACHE_MAX_ENTRIES: usize = 10_000;
+
+/// How often expired rate limit state is swept out.
+const RATE_LIMIT_PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// In-process cache, used as the whole backend for single-instance
+/// deployments without Redis and as the local tier in front of Redis. Entries
+/// expire after their TTL, and the least recently used entry is evicted once
+/// the store holds `max_entries`.
+///
+/// Rate limit state (counters, sliding windows, token buckets) lives apart
+/// from cached values and is never evicted, only dropped once it expires:
+/// evicting it would quietly reset limits and cooldowns under load.
+#[derive(Clone, Debug)]
+pub struct MemoryCacheStore {
+    inner: Arc<Mutex<MemoryCacheInner>>,
+    evictions: Arc<AtomicU64>,
+}
+
+#[derive(Debug)]
+struct MemoryCacheInner {
+    entries: HashMap<String, MemoryEntry>,
+    /// Recency order: access tick to key, oldest first.
+    recency: BTreeMap<u64, String>,
+    next_tick: u64,
+    max_entries: usize,
+    rate_limits: HashMap<String, RateLimitEntry>,
+    next_rate_limit_prune: Instant,
+}
+
+#[derive(Debug)]
+struct MemoryEntry {
+    value: Vec<u8>,
+    expires_at: Instant,
+    tick: u64,
+}
+
+#[derive(Debug)]
+struct RateLimitEntry {
+    value: Vec<u8>,
+    expires_at: Instant,
+}
+
+impl MemoryCacheInner {
+    fn touch(&mut self, key: &str) {
+        let tick = self.next_tick;
+        self.next_tick += 1;
+
+        if let Some(entry) = self.entries.get_mut(key) {
+            self.recency.remove(&entry.tick);
+            entry.tick = tick;
+            self.recency.insert(tick, key.to_owned());
+        }
+    }
+
+    /// The entry for `key`, dropping it first if it has expired.
+    fn live_entry(&mut self, key: &str, now: Instant) -> Option<&mut MemoryEntry> {
+        if self
+            .entries
+            .get(key)
+            .is_some_and(|entry| entry.expires_at <= now)
+        {
+            self.remove(key);
+        }
+
+        self.entries.get_mut(key)
+    }
+
+    fn insert(&mut self, key: &str, value: Vec<u8>, expires_at: Instant) -> u64 {
+        self.remove(key);
+
+        let tick = self.next_tick;
+        self.next_tick += 1;
+        self.entries.insert(
+            key.to_owned(),
+            MemoryEntry {
+                value,
+                expires_at,
+                tick,
+            },
+        );
+        self.recency.insert(tick, key.to_owned());
+
+        self.evict_over_capacity()
+    }
+
+    /// The live rate limit state at `key`, sweeping expired state first when
+    /// a sweep is due.
+    fn live_rate_limit(&mut self, key: &str, now: Instant) -> Option<&mut RateLimitEntry> {
+        if now >= self.next_rate_limit_prune {
+            self.rate_limits.retain(|_, entry| entry.expires_at > now);
+            self.next_rate_limit_prune = now + RATE_LIMIT_PRUNE_INTERVAL;
+        }
+
+        if self
+            .rate_limits
+            .get(key)
+            .is_some_and(|entry| entry.expires_at <= now)
+        {
+            self.rate_limits.remove(key);
+        }
+
+        self.rate_limits.get_mut(key)
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.recency.remove(&entry.tick);
+        }
+    }
+
+    /// Drop least recently used entries until the store fits. Returns how
+    /// many were evicted.
+    fn evict_over_capacity(&mut self) -> u64 {
+        let mut evicted = 0;
+        while self.entries.len() > self.max_entries {
+            let Some((_, key)) = self.recency.pop_first() else {
+                break;
+            };
+            self.entries.remove(&key);
+            evicted += 1;
+        }
+        evicted
+    }
+}
+
+impl MemoryCacheStore {
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(MemoryCacheInner {
+                entries: HashMap::new(),
+                recency: BTreeMap::new(),
+                next_tick: 0,
+                max_entries: max_entries.max(1),
+                rate_limits: HashMap::new(),
+                next_rate_limit_prune: Instant::now() + RATE_LIMIT_PRUNE_INTERVAL,
+            })),
+            evictions: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Entries dropped to stay under the size bound. Expired entries are not
+    /// counted.
+    pub fn evictions(&self) -> u64 {
+        self.evictions.load(Ordering::Relaxed)
+    }
+
+    pub async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        let mut inner = self.lock()?;
+        let value = inner
+            .live_entry(key, Instant::now())
+            .map(|entry| entry.value.clone());
+        if value.is_some() {
+            inner.touch(key);
+        }
+
+        Ok(value)
+    }
+
+    pub async fn set(&self, key: &str, value: Vec<u8>, ttl_seconds: u64) -> anyhow::Result<()> {
+        let expires_at = Instant::now() + Duration::from_secs(ttl_seconds);
+        let evicted = self.lock()?.insert(key, value, expires_at);
+        self.evictions.fetch_add(evicted, Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    pub async fn del(&self, key: &str) -> anyhow::Result<()> {
+        self.lock()?.remove(key);
+        Ok(())
+    }
+
+    pub async fn clear(&self) -> anyhow::Result<()> {
+        let mut inner = self.lock()?;
+        inner.entries.clear();
+        inner.recency.clear();
+        inner.rate_limits.clear();
+        Ok(())
+    }
+
+    /// Same semantics as Redis `INCR` followed by `EXPIRE` on the first hit:
+    /// the window starts with the first increment and is not extended.
+    pub async fn increment_with_window(
+        &self,
+        key: &str,
+        window_seconds: u64,
+    ) -> anyhow::Result<u64> {
+        let now = Instant::now();
+        let mut inner = self.lock()?;
+
+        if let Some(entry) = inner.live_rate_limit(key, now) {
+            let count = std::str::from_utf8(&entry.value)
+                .ok()
+                .and_then(|value| value.parse::<u64>().ok())
+                .ok_or_else(|| anyhow::anyhow!("cache value for `{key}` is not a counter"))?
+                + 1;
+            entry.value = count.to_string().into_bytes();
+            return Ok(count);
+        }
+
+        inner.rate_limits.insert(
+            key.to_owned(),
+            RateLimitEntry {
+                value: b"1".to_vec(),
+                expires_at: now + Duration::from_secs(window_seconds),
+            },
+        );
+
+        Ok(1)
+    }
+
+    pub async fn sliding_window_hit(
+        &self,
+        key: &str,
+        now_ms: u64,
+        window_ms: u64,
+        max_hits: u64,
+    ) -> anyhow::Result<RateLimitDecision> {
+        self.update(key, Duration::from_millis(window_ms), |current| {
+            let mut log: Vec<u64> = current
+                .unwrap_or_default()
+                .chunks_exact(8)
+                .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap_or_default()))
+                .collect();
+            let decision = sliding_window_step(&mut log, now_ms, window_ms, max_hits);
+            let value = log.iter().flat_map(|hit| hit.to_le_bytes()).collect();
+            (value, decision)
+        })
+    }
+
+    pub async fn token_bucket_take(
+        &self,
+        key: &str,
+        now_ms: u64,
+        capacity: u64,
+        refill_every_ms: u64,
+    ) -> anyhow::Result<RateLimitDecision> {
+        let ttl = Duration::from_millis(capacity.max(1).saturating_mul(refill_every_ms.max(1)));
+        self.update(key, ttl, |current| {
+            let state = current.and_then(|bytes| {
+                let tokens = u64::from_le_bytes(bytes.get(..8)?.try_into().ok()?);
+                let refilled_at_ms = u64::from_le_bytes(bytes.get(8..16)?.try_into().ok()?);
+                Some(TokenBucketState {
+                    tokens,
+                    refilled_at_ms,
+                })
+            });
+            let (state, decision) = token_bucket_step(state, now_ms, capacity, refill_every_ms);
+            let mut value = state.tokens.to_le_bytes().to_vec();
+            value.extend_from_slice(&state.refilled_at_ms.to_le_bytes());
+            (value, decision)
+        })
+    }
+
+    pub async fn ping(&self) -> anyhow::Result<()> {
+        self.lock().map(|_| ())
+    }
+
+    /// Replace the rate limit state at `key` with one computed from the
+    /// current state, atomically, resetting its TTL to `ttl`.
+    fn update<R>(
+        &self,
+        key: &str,
+        ttl: Duration,
+        apply: impl FnOnce(Option<&[u8]>) -> (Vec<u8>, R),
+    ) -> anyhow::Result<R> {
+        let now = Instant::now();
+        let mut inner = self.lock()?;
+
+        let current = inner
+            .live_rate_limit(key, now)
+            .map(|entry| entry.value.as_slice());
+        let (value, result) = apply(current);
+        inner.rate_limits.insert(
+            key.to_owned(),
+            RateLimitEntry {
+                value,
+                expires_at: now + ttl,
+            },
+        );
+
+        Ok(result)
+    }
+
+    fn lock(&self) -> anyhow::Result<std::sync::MutexGuard<'_, MemoryCacheInner>> {
+        self.inner
+            .lock()
+            .map_err(|_| anyhow::anyhow!("memory cache lock poisoned"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn evicts_least_recently_used_entry() {
+        let store = MemoryCacheStore::new(2);
+        store.set("a", b"1".to_vec(), 60).await.unwrap();
+        store.set("b", b"2".to_vec(), 60).await.unwrap();
+
+        // Reading `a` makes `b` the least recently used.
+        assert!(store.get("a").await.unwrap().is_some());
+        store.set("c", b"3".to_vec(), 60).await.unwrap();
+
+        assert!(store.get("a").await.unwrap().is_some());
+        assert!(store.get("b").await.unwrap().is_none());
+        assert!(store.get("c").await.unwrap().is_some());
+        assert_eq!(store.evictions(), 1);
+    }
+
+    #[tokio::test]
+    async fn expired_entries_are_not_returned() {
+        let store = MemoryCacheStore::new(8);
+        store.set("gone", b"1".to_vec(), 0).await.unwrap();
+
+        assert!(store.get("gone").await.unwrap().is_none());
+        assert_eq!(store.evictions(), 0);
+    }
+
+    #[tokio::test]
+    async fn increment_counts_within_window() {
+        let store = MemoryCacheStore::new(8);
+
+        assert_eq!(store.increment_with_window("hits", 60).await.unwrap(), 1);
+        assert_eq!(store.increment_with_window("hits", 60).await.unwrap(), 2);
+
+        assert_eq!(store.increment_with_window("fresh", 0).await.unwrap(), 1);
+        assert_eq!(store.increment_with_window("fresh", 0).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn rate_limit_state_is_not_evicted() {
+        let store = MemoryCacheStore::new(1);
+        assert_eq!(store.increment_with_window("hits", 60).await.unwrap(), 1);
+
+        store.set("a", b"1".to_vec(), 60).await.unwrap();
+        store.set("b", b"2".to_vec(), 60).await.unwrap();
+
+        assert_eq!(store.increment_with_window("hits", 60).await.unwrap(), 2);
+        assert_eq!(store.evictions(), 1);
+    }
+}
@@ -1,28 +1,48 @@
+mod memory_store;
 mod noop_store;
+mod rate_limit;
 mod redis_store;
 
+use std::collections::hash_map::RandomState;
 use std::future::Future;
-use std::sync::Arc;
+use std::hash::BuildHasher;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::Duration;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use serde::Serialize;
 use serde::de::DeserializeOwned;
-use tracing::warn;
+use tokio::task::JoinHandle;
+use tokio_stream::StreamExt;
+use tracing::{info, warn};
 
+pub use memory_store::DEFAULT_MEMORY_CACHE_MAX_ENTRIES;
+pub use rate_limit::{RateLimitDecision, RateLimiter};
+
+use memory_store::MemoryCacheStore;
 use noop_store::NoopCacheStore;
 use redis_store::RedisCacheStore;
 
 #[derive(Clone, Debug)]
 enum CacheBackend {
     Disabled(NoopCacheStore),
+    Memory(MemoryCacheStore),
     Redis(RedisCacheStore),
 }
 
+/// Short-lived copies of Redis values kept in process, so hot config reads
+/// skip the network. Invalidations are broadcast over Redis pub/sub.
+#[derive(Clone, Debug)]
+struct LocalTier {
+    store: MemoryCacheStore,
+    ttl: Duration,
+}
+
 #[derive(Clone, Debug)]
 pub struct CacheService {
     key_prefix: String,
     backend: CacheBackend,
+    local: Option<LocalTier>,
     stats: Arc<CacheStatsInner>,
     llm_rate_limit_window: Duration,
     llm_rate_limit_max_hits: u64,
@@ -31,6 +51,7 @@ pub struct CacheService {
 #[derive(Debug, Default)]
 struct CacheStatsInner {
     hit: AtomicU64,
+    local_hit: AtomicU64,
     miss: AtomicU64,
     set: AtomicU64,
     del: AtomicU64,
@@ -43,6 +64,9 @@ struct CacheStatsInner {
 #[derive(Clone, Debug, Default)]
 pub struct CacheStatsSnapshot {
     pub hit: u64,
+    /// Hits served by the local tier without a Redis round trip; also
+    /// counted in `hit`.
+    pub local_hit: u64,
     pub miss: u64,
     pub set: u64,
     pub del: u64,
@@ -50,12 +74,14 @@ pub struct CacheStatsSnapshot {
     pub fallback_load: u64,
     pub ratelimit_checks: u64,
     pub ratelimit_blocks: u64,
+    pub evictions: u64,
 }
 
 impl CacheStatsInner {
     fn snapshot(&self) -> CacheStatsSnapshot {
         CacheStatsSnapshot {
             hit: self.hit.load(Ordering::Relaxed),
+            local_hit: self.local_hit.load(Ordering::Relaxed),
             miss: self.miss.load(Ordering::Relaxed),
             set: self.set.load(Ordering::Relaxed),
             del: self.del.load(Ordering::Relaxed),
@@ -63,34 +89,71 @@ impl CacheStatsInner {
             fallback_load: self.fallback_load.load(Ordering::Relaxed),
             ratelimit_checks: self.ratelimit_checks.load(Ordering::Relaxed),
             ratelimit_blocks: self.ratelimit_blocks.load(Ordering::Relaxed),
+            evictions: 0,
         }
     }
 }
 
 pub const CONFIG_CACHE_TTL: Duration = Duration::from_secs(15 * 60);
 pub const WORD_LIST_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+/// Discord role and overwrite data fetched over HTTP when the gateway cache
+/// doesn't have it. Kept short so role changes apply quickly.
+pub const PERMISSION_CACHE_TTL: Duration = Duration::from_secs(60);
 pub const DEFAULT_LLM_MENTION_RATE_LIMIT_WINDOW: Duration = Duration::from_secs(10);
 pub const DEFAULT_LLM_MENTION_RATE_LIMIT_MAX_HITS: u64 = 2;
+/// Upper bound on how stale a local copy can get if an invalidation message
+/// is lost.
+pub const DEFAULT_LOCAL_CACHE_TTL: Duration = Duration::from_secs(30);
+const INVALIDATION_RETRY_DELAY: Duration = Duration::from_secs(5);
 
 impl CacheService {
-    pub fn disabled(prefix: impl Into<String>) -> Self {
+    fn with_backend(prefix: impl Into<String>, backend: CacheBackend) -> Self {
         Self {
             key_prefix: prefix.into(),
-            backend: CacheBackend::Disabled(NoopCacheStore),
+            backend,
+            local: None,
             stats: Arc::new(CacheStatsInner::default()),
             llm_rate_limit_window: DEFAULT_LLM_MENTION_RATE_LIMIT_WINDOW,
             llm_rate_limit_max_hits: DEFAULT_LLM_MENTION_RATE_LIMIT_MAX_HITS,
         }
     }
 
+    /// No caching. Rate limits are still tracked in process, as with
+    /// [`CacheService::memory`].
+    pub fn disabled(prefix: impl Into<String>) -> Self {
+        Self::with_backend(prefix, CacheBackend::Disabled(NoopCacheStore::new()))
+    }
+
+    /// In-process cache holding at most `max_entries` keys. Only suitable for
+    /// a single bot instance, since nothing is shared between processes.
+    pub fn memory(prefix: impl Into<String>, max_entries: usize) -> Self {
+        Self::with_backend(
+            prefix,
+            CacheBackend::Memory(MemoryCacheStore::new(max_entries)),
+        )
+    }
+
+    /// Redis-backed cache with a local tier of [`DEFAULT_LOCAL_CACHE_TTL`] in
+    /// front of it.
     pub fn redis(redis_url: &str, prefix: impl Into<String>) -> anyhow::Result<Self> {
-        Ok(Self {
-            key_prefix: prefix.into(),
-            backend: CacheBackend::Redis(RedisCacheStore::from_url(redis_url)?),
-            stats: Arc::new(CacheStatsInner::default()),
-            llm_rate_limit_window: DEFAULT_LLM_MENTION_RATE_LIMIT_WINDOW,
-            llm_rate_limit_max_hits: DEFAULT_LLM_MENTION_RATE_LIMIT_MAX_HITS,
-        })
+        let mut cache = Self::with_backend(
+            prefix,
+            CacheBackend::Redis(RedisCacheStore::from_url(redis_url)?),
+        );
+        cache.configure_local_tier(DEFAULT_LOCAL_CACHE_TTL, DEFAULT_MEMORY_CACHE_MAX_ENTRIES);
+        Ok(cache)
+    }
+
+    /// Size the local tier in front of Redis. A zero `ttl` turns it off. Has
+    /// no effect on other backends.
+    pub fn configure_local_tier(&mut self, ttl: Duration, max_entries: usize) {
+        self.local = match self.backend {
+            CacheBackend::Redis(_) if !ttl.is_zero() => Some(LocalTier {
+                store: MemoryCacheStore::new(max_entries),
+                ttl,
+            }),
+            _ => None,
+        };
     }
 
     pub fn configure_llm_rate_limit(&mut self, window: Duration, max_hits: u64) {
@@ -111,12 +174,24 @@ impl CacheService {
         format!("{}:{}", self.key_prefix, suffix.as_ref())
     }
 
+    fn invalidation_channel(&self) -> String {
+        self.key("cache:invalidate")
+    }
+
     pub async fn get_json<T>(&self, key: &str) -> anyhow::Result<Option<T>>
     where
         T: DeserializeOwned,
     {
+        if let Some(local) = &self.local
+            && let Ok(Some(bytes)) = local.store.get(key).await
+        {
+            self.stats.local_hit.fetch_add(1, Ordering::Relaxed);
+            return self.parse_hit(key, &bytes).map(Some);
+        }
+
         let value = match &self.backend {
             CacheBackend::Disabled(store) => store.get(key).await,
+            CacheBackend::Memory(store) => store.get(key).await,
             CacheBackend::Redis(store) => store.get(key).await,
         }
         .inspect_err(|_| {
@@ -125,14 +200,10 @@ impl CacheService {
 
         match value {
             Some(bytes) => {
-                let parsed = serde_json::from_slice(&bytes)
-                    .map_err(|e| {
-                        anyhow::anyhow!("failed to deserialize cache value for `{key}`: {e}")
-                    })
-                    .inspect_err(|_| {
-                        self.stats.error.fetch_add(1, Ordering::Relaxed);
-                    })?;
-                self.stats.hit.fetch_add(1, Ordering::Relaxed);
+                let parsed = self.parse_hit(key, &bytes)?;
+                if let Some(local) = &self.local {
+                    let _ = local.store.set(key, bytes, local.ttl.as_secs()).await;
+                }
                 Ok(Some(parsed))
             }
             None => {
@@ -142,6 +213,19 @@ impl CacheService {
         }
     }
 
+    fn parse_hit<T>(&self, key: &str, bytes: &[u8]) -> anyhow::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let parsed = serde_json::from_slice(bytes)
+            .map_err(|e| anyhow::anyhow!("failed to deserialize cache value for `{key}`: {e}"))
+            .inspect_err(|_| {
+                self.stats.error.fetch_add(1, Ordering::Relaxed);
+            })?;
+        self.stats.hit.fetch_add(1, Ordering::Relaxed);
+        Ok(parsed)
+    }
+
     pub async fn set_json<T>(&self, key: &str, value: &T, ttl: Duration) -> anyhow::Result<()>
     where
         T: Serialize,
@@ -150,8 +234,17 @@ impl CacheService {
         let payload = serde_json::to_vec(value)
             .map_err(|e| anyhow::anyhow!("failed to serialize cache value for `{key}`: {e}"))?;
 
+        if let Some(local) = &self.local {
+            let local_ttl_seconds = ttl_seconds.min(local.ttl.as_secs());
+            let _ = local
+                .store
+                .set(key, payload.clone(), local_ttl_seconds)
+                .await;
+        }
+
         let result = match &self.backend {
             CacheBackend::Disabled(store) => store.set(key, payload, ttl_seconds).await,
+            CacheBackend::Memory(store) => store.set(key, payload, ttl_seconds).await,
             CacheBackend::Redis(store) => store.set(key, payload, ttl_seconds).await,
         };
 
@@ -167,9 +260,20 @@ impl CacheService {
         }
     }
 
+    /// Delete `key`. With a local tier, the deletion is also published so
+    /// every other instance drops its local copy.
     pub async fn del(&self, key: &str) -> anyhow::Result<()> {
+        if let Some(local) = &self.local {
+            let _ = local.store.del(key).await;
+        }
+
         let result = match &self.backend {
             CacheBackend::Disabled(store) => store.del(key).await,
+            CacheBackend::Memory(store) => store.del(key).await,
+            CacheBackend::Redis(store) if self.local.is_some() => match store.del(key).await {
+                Ok(()) => store.publish(&self.invalidation_channel(), key).await,
+                Err(e) => Err(e),
+            },
             CacheBackend::Redis(store) => store.del(key).await,
         };
 
@@ -226,6 +330,7 @@ impl CacheService {
         let window_seconds = window.as_secs().max(1);
         let result = match &self.backend {
             CacheBackend::Disabled(store) => store.increment_with_window(key, window_seconds).await,
+            CacheBackend::Memory(store) => store.increment_with_window(key, window_seconds).await,
             CacheBackend::Redis(store) => store.increment_with_window(key, window_seconds).await,
         };
 
@@ -238,17 +343,149 @@ impl CacheService {
         }
     }
 
+    /// Record a hit in the sliding-window log at `key` if fewer than
+    /// `max_hits` landed in the last `window`. Prefer [`RateLimiter`].
+    pub async fn sliding_window_hit(
+        &self,
+        key: &str,
+        window: Duration,
+        max_hits: u64,
+    ) -> anyhow::Result<RateLimitDecision> {
+        self.stats.ratelimit_checks.fetch_add(1, Ordering::Relaxed);
+        let now_ms = unix_millis();
+        let window_ms = u64::try_from(window.as_millis()).unwrap_or(u64::MAX).max(1);
+        let result = match &self.backend {
+            CacheBackend::Disabled(store) => {
+                store
+                    .sliding_window_hit(key, now_ms, window_ms, max_hits)
+                    .await
+            }
+            CacheBackend::Memory(store) => {
+                store
+                    .sliding_window_hit(key, now_ms, window_ms, max_hits)
+                    .await
+            }
+            CacheBackend::Redis(store) => {
+                let member = format!("{now_ms}:{}", hit_nonce());
+                store
+                    .sliding_window_hit(key, now_ms, window_ms, max_hits, &member)
+                    .await
+            }
+        };
+
+        self.finish_rate_limit_check(result)
+    }
+
+    /// Take a token from the bucket at `key`, which holds up to `capacity`
+    /// and regains one every `refill_every`. Prefer [`RateLimiter`].
+    pub async fn token_bucket_take(
+        &self,
+        key: &str,
+        capacity: u64,
+        refill_every: Duration,
+    ) -> anyhow::Result<RateLimitDecision> {
+        self.stats.ratelimit_checks.fetch_add(1, Ordering::Relaxed);
+        let now_ms = unix_millis();
+        let refill_every_ms = u64::try_from(refill_every.as_millis())
+            .unwrap_or(u64::MAX)
+            .max(1);
+        let result = match &self.backend {
+            CacheBackend::Disabled(store) => {
+                store
+                    .token_bucket_take(key, now_ms, capacity, refill_every_ms)
+                    .await
+            }
+            CacheBackend::Memory(store) => {
+                store
+                    .token_bucket_take(key, now_ms, capacity, refill_every_ms)
+                    .await
+            }
+            CacheBackend::Redis(store) => {
+                store
+                    .token_bucket_take(key, now_ms, capacity, refill_every_ms)
+                    .await
+            }
+        };
+
+        self.finish_rate_limit_check(result)
+    }
+
+    fn finish_rate_limit_check(
+        &self,
+        result: anyhow::Result<RateLimitDecision>,
+    ) -> anyhow::Result<RateLimitDecision> {
+        match result {
+            Ok(decision) => {
+                if !decision.allowed {
+                    self.record_rate_limit_block();
+                }
+                Ok(decision)
+            }
+            Err(e) => {
+                self.stats.error.fetch_add(1, Ordering::Relaxed);
+                Err(e)
+            }
+        }
+    }
+
     pub fn record_rate_limit_block(&self) {
         self.stats.ratelimit_blocks.fetch_add(1, Ordering::Relaxed);
     }
 
     pub fn stats_snapshot(&self) -> CacheStatsSnapshot {
-        self.stats.snapshot()
+        let mut snapshot = self.stats.snapshot();
+        if let CacheBackend::Memory(store) = &self.backend {
+            snapshot.evictions = store.evictions();
+        }
+        if let Some(local) = &self.local {
+            snapshot.evictions += local.store.evictions();
+        }
+        snapshot
+    }
+
+    /// Subscribe to invalidations published by other instances and drop the
+    /// matching local entries. Returns `None` when there is no local tier.
+    /// The task reconnects on its own and clears the local tier after each
+    /// (re)subscribe, since messages sent while disconnected are lost.
+    pub fn spawn_invalidation_listener(&self) -> Option<JoinHandle<()>> {
+        let (CacheBackend::Redis(store), Some(local)) = (&self.backend, &self.local) else {
+            return None;
+        };
+
+        let store = store.clone();
+        let local = local.store.clone();
+        let channel = self.invalidation_channel();
+
+        Some(tokio::spawn(async move {
+            loop {
+                match store.subscribe(&channel).await {
+                    Ok(mut messages) => {
+                        let _ = local.clear().await;
+                        info!(channel = %channel, "Listening for cache invalidations.");
+
+                        while let Some(message) = messages.next().await {
+                            match message.get_payload::<String>() {
+                                Ok(key) => {
+                                    let _ = local.del(&key).await;
+                                }
+                                Err(e) => warn!(?e, "malformed cache invalidation message"),
+                            }
+                        }
+
+                        warn!("cache invalidation subscription closed; reconnecting");
+                    }
+                    Err(e) => warn!(?e, "failed to subscribe to cache invalidations; retrying"),
+                }
+
+                tokio::time::sleep(INVALIDATION_RETRY_DELAY).await;
+            }
+        }))
     }
 
     pub async fn ping(&self) -> anyhow::Result<()> {
         match &self.backend {
             CacheBackend::Disabled(store) => store.ping().await,
+            CacheBackend::Memory(store) => store.ping().await,
             CacheBackend::Redis(store) => store.ping().await,
         }
     }
@@ -258,6 +495,24 @@ impl CacheService {
     }
 }
 
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX))
+        .unwrap_or(0)
+}
+
+/// Unique suffix for sliding-window log entries, so hits in the same
+/// millisecond from any instance are all counted.
+fn hit_nonce() -> String {
+    static INSTANCE: OnceLock<u64> = OnceLock::new();
+    static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+    let instance = INSTANCE.get_or_init(|| RandomState::new().hash_one(std::process::id()));
+    let sequence = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    format!("{instance:x}:{sequence}")
+}
+
 fn normalize_llm_rate_limit(window: Duration, max_hits: u64) -> (Duration, u64) {
     let window_seconds = window.as_secs().clamp(1, 3600);
     let normalized_hits = max_hits.max(1);
@@ -268,10 +523,68 @@ pub fn ai_config_key(cache: &CacheService, guild_id: u64) -> String {
     cache.key(format!("guild:{guild_id}:config:ai"))
 }
 
+pub fn ai_long_reply_mode_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config:ai_long_reply_mode"))
+}
+
+pub fn ai_thread_mode_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config:ai_thread_mode"))
+}
+
+pub fn ai_quota_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config:ai_quota"))
+}
+
+pub fn ai_moderation_config_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config:ai_moderation"))
+}
+
+pub fn ai_moderation_rate_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:ratelimit:ai_moderation"))
+}
+
+pub fn anti_nuke_config_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config:anti_nuke"))
+}
+
+pub fn anti_nuke_actions_key(cache: &CacheService, guild_id: u64, actor_id: u64) -> String {
+    cache.key(format!(
+        "guild:{guild_id}:user:{actor_id}:ratelimit:anti_nuke"
+    ))
+}
+
+/// Marks that a burst by the actor was already responded to.
+pub fn anti_nuke_response_key(cache: &CacheService, guild_id: u64, actor_id: u64) -> String {
+    cache.key(format!(
+        "guild:{guild_id}:user:{actor_id}:ratelimit:anti_nuke_response"
+    ))
+}
+
 pub fn modlog_config_key(cache: &CacheService, guild_id: u64) -> String {
     cache.key(format!("guild:{guild_id}:config:modlog"))
 }
 
+pub fn moderator_safety_config_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config:moderator_safety"))
+}
+
+pub fn command_acl_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config:command_acl"))
+}
+
+pub fn disabled_commands_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config:disabled_commands"))
+}
+
+pub fn guild_prefix_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config:prefix"))
+}
+
+/// `bucket` scopes the cooldown, e.g. `guild:1:user:2` or `channel:3`.
+pub fn command_cooldown_key(cache: &CacheService, bucket: &str, command: &str) -> String {
+    cache.key(format!("{bucket}:cooldown:{command}"))
+}
+
 pub fn escalation_config_key(cache: &CacheService, guild_id: u64) -> String {
     cache.key(format!("guild:{guild_id}:config:escalation"))
 }
@@ -284,6 +597,67 @@ pub fn word_filter_words_key(cache: &CacheService, guild_id: u64) -> String {
     cache.key(format!("guild:{guild_id}:config:word_filter_words"))
 }
 
+pub fn word_filter_automod_rules_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config:word_filter_automod_rules"))
+}
+
+pub fn attachment_filter_config_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:config:attachment_filter"))
+}
+
+pub fn attachment_filter_extensions_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!(
+        "guild:{guild_id}:config:attachment_filter_extensions"
+    ))
+}
+
+pub fn attachment_filter_channel_key(
+    cache: &CacheService,
+    guild_id: u64,
+    channel_id: u64,
+) -> String {
+    cache.key(format!(
+        "guild:{guild_id}:channel:{channel_id}:config:attachment_filter"
+    ))
+}
+
+pub fn llm_prompt_key(cache: &CacheService, guild_id: u64, channel_id: Option<u64>) -> String {
+    match channel_id {
+        Some(channel_id) => cache.key(format!(
+            "guild:{guild_id}:channel:{channel_id}:config:llm_prompt"
+        )),
+        None => cache.key(format!("guild:{guild_id}:config:llm_prompt")),
+    }
+}
+
+pub fn llm_thread_key(cache: &CacheService, guild_id: u64, thread_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:channel:{thread_id}:llm_thread"))
+}
+
+pub fn discord_guild_roles_key(cache: &CacheService, guild_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:discord:roles"))
+}
+
+pub fn discord_member_roles_key(cache: &CacheService, guild_id: u64, user_id: u64) -> String {
+    cache.key(format!("guild:{guild_id}:user:{user_id}:discord:roles"))
+}
+
+pub fn discord_channel_parent_key(cache: &CacheService, guild_id: u64, channel_id: u64) -> String {
+    cache.key(format!(
+        "guild:{guild_id}:channel:{channel_id}:discord:parent"
+    ))
+}
+
+pub fn discord_channel_overwrites_key(
+    cache: &CacheService,
+    guild_id: u64,
+    channel_id: u64,
+) -> String {
+    cache.key(format!(
+        "guild:{guild_id}:channel:{channel_id}:discord:overwrites"
+    ))
+}
+
 pub fn llm_mention_rate_limit_key(
     cache: &CacheService,
     guild_id: u64,
@@ -296,13 +670,54 @@ pub fn llm_mention_rate_limit_key(
 }
 
 pub async fn invalidate_ai_config(cache: &CacheService, guild_id: u64) -> anyhow::Result<()> {
-    cache.del(&ai_config_key(cache, guild_id)).await
+    cache.del(&ai_config_key(cache, guild_id)).await?;
+    cache.del(&ai_long_reply_mode_key(cache, guild_id)).await?;
+    cache.del(&ai_thread_mode_key(cache, guild_id)).await?;
+    cache.del(&ai_quota_key(cache, guild_id)).await
+}
+
+pub async fn invalidate_ai_moderation_config(
+    cache: &CacheService,
+    guild_id: u64,
+) -> anyhow::Result<()> {
+    cache.del(&ai_moderation_config_key(cache, guild_id)).await
+}
+
+pub async fn invalidate_anti_nuke_config(
+    cache: &CacheService,
+    guild_id: u64,
+) -> anyhow::Result<()> {
+    cache.del(&anti_nuke_config_key(cache, guild_id)).await
 }
 
 pub async fn invalidate_modlog_config(cache: &CacheService, guild_id: u64) -> anyhow::Result<()> {
     cache.del(&modlog_config_key(cache, guild_id)).await
 }
 
+pub async fn invalidate_moderator_safety_config(
+    cache: &CacheService,
+    guild_id: u64,
+) -> anyhow::Result<()> {
+    cache
+        .del(&moderator_safety_config_key(cache, guild_id))
+        .await
+}
+
+pub async fn invalidate_command_acl(cache: &CacheService, guild_id: u64) -> anyhow::Result<()> {
+    cache.del(&command_acl_key(cache, guild_id)).await
+}
+
+pub async fn invalidate_disabled_commands(
+    cache: &CacheService,
+    guild_id: u64,
+) -> anyhow::Result<()> {
+    cache.del(&disabled_commands_key(cache, guild_id)).await
+}
+
+pub async fn invalidate_guild_prefix(cache: &CacheService, guild_id: u64) -> anyhow::Result<()> {
+    cache.del(&guild_prefix_key(cache, guild_id)).await
+}
+
 pub async fn invalidate_escalation_config(
     cache: &CacheService,
     guild_id: u64,
@@ -315,6 +730,55 @@ pub async fn invalidate_word_filter(cache: &CacheService, guild_id: u64) -> anyh
     cache.del(&word_filter_words_key(cache, guild_id)).await
 }
 
+pub async fn invalidate_word_filter_automod_rules(
+    cache: &CacheService,
+    guild_id: u64,
+) -> anyhow::Result<()> {
+    cache
+        .del(&word_filter_automod_rules_key(cache, guild_id))
+        .await
+}
+
+pub async fn invalidate_llm_prompt(
+    cache: &CacheService,
+    guild_id: u64,
+    channel_id: Option<u64>,
+) -> anyhow::Result<()> {
+    cache
+        .del(&llm_prompt_key(cache, guild_id, channel_id))
+        .await
+}
+
+pub async fn invalidate_llm_thread(
+    cache: &CacheService,
+    guild_id: u64,
+    thread_id: u64,
+) -> anyhow::Result<()> {
+    cache.del(&llm_thread_key(cache, guild_id, thread_id)).await
+}
+
+pub async fn invalidate_attachment_filter(
+    cache: &CacheService,
+    guild_id: u64,
+) -> anyhow::Result<()> {
+    cache
+        .del(&attachment_filter_config_key(cache, guild_id))
+        .await?;
+    cache
+        .del(&attachment_filter_extensions_key(cache, guild_id))
+        .await
+}
+
+pub async fn invalidate_attachment_filter_channel(
+    cache: &CacheService,
+    guild_id: u64,
+    channel_id: u64,
+) -> anyhow::Result<()> {
+    cache
+        .del(&attachment_filter_channel_key(cache, guild_id, channel_id))
+        .await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -339,4 +803,16 @@ mod tests {
         assert_eq!(window, Duration::from_secs(3600));
         assert_eq!(max_hits, 5);
     }
+
+    #[tokio::test]
+    async fn disabled_cache_still_enforces_rate_limits() {
+        let cache = CacheService::disabled("autumn:test");
+        let limiter = RateLimiter::SlidingWindow {
+            window: Duration::from_secs(60),
+            max_hits: 1,
+        };
+
+        assert!(limiter.check(&cache, "key").await.unwrap().allowed);
+        assert!(!limiter.check(&cache, "key").await.unwrap().allowed);
+    }
 }
@@ -1,7 +1,22 @@
-#[derive(Clone, Debug, Default)]
-pub struct NoopCacheStore;
+use super::memory_store::MemoryCacheStore;
+use super::rate_limit::RateLimitDecision;
+
+/// Caches nothing, but still keeps rate limit state in process: with nowhere
+/// to count hits, anti-nuke thresholds and cooldowns would never trip.
+#[derive(Clone, Debug)]
+pub struct NoopCacheStore {
+    rate_limits: MemoryCacheStore,
+}
 
 impl NoopCacheStore {
+    pub fn new() -> Self {
+        Self {
+            // Rate limit state is kept apart from cached values and never
+            // counts against the entry limit, so the limit is moot here.
+            rate_limits: MemoryCacheStore::new(1),
+        }
+    }
+
     pub async fn get(&self, _key: &str) -> anyhow::Result<Option<Vec<u8>>> {
         Ok(None)
     }
@@ -16,10 +31,36 @@ impl NoopCacheStore {
 
     pub async fn increment_with_window(
         &self,
-        _key: &str,
-        _window_seconds: u64,
+        key: &str,
+        window_seconds: u64,
     ) -> anyhow::Result<u64> {
-        Ok(1)
+        self.rate_limits
+            .increment_with_window(key, window_seconds)
+            .await
+    }
+
+    pub async fn sliding_window_hit(
+        &self,
+        key: &str,
+        now_ms: u64,
+        window_ms: u64,
+        max_hits: u64,
+    ) -> anyhow::Result<RateLimitDecision> {
+        self.rate_limits
+            .sliding_window_hit(key, now_ms, window_ms, max_hits)
+            .await
+    }
+
+    pub async fn token_bucket_take(
+        &self,
+        key: &str,
+        now_ms: u64,
+        capacity: u64,
+        refill_every_ms: u64,
+    ) -> anyhow::Result<RateLimitDecision> {
+        self.rate_limits
+            .token_bucket_take(key, now_ms, capacity, refill_every_ms)
+            .await
     }
 
     pub async fn ping(&self) -> anyhow::Result<()> {
@@ -0,0 +1,164 @@
+use std::time::Duration;
+
+use super::CacheService;
+
+/// Outcome of a single rate limit check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    /// Hits still available right after this one.
+    pub remaining: u64,
+    /// How long until the next hit would be allowed; zero when allowed.
+    pub retry_after: Duration,
+}
+
+/// A reusable rate limit policy. Each check counts as a hit when allowed;
+/// blocked hits are not recorded, so a caller hammering a limit doesn't
+/// extend it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RateLimiter {
+    /// At most `max_hits` within any `window`-long span. Unlike a fixed
+    /// window, bursts straddling a window edge don't get double the allowance.
+    SlidingWindow { window: Duration, max_hits: u64 },
+    /// Bursts of up to `capacity`, refilled at one hit per `refill_every`.
+    TokenBucket {
+        capacity: u64,
+        refill_every: Duration,
+    },
+}
+
+impl RateLimiter {
+    pub async fn check(
+        &self,
+        cache: &CacheService,
+        key: &str,
+    ) -> anyhow::Result<RateLimitDecision> {
+        match *self {
+            Self::SlidingWindow { window, max_hits } => {
+                cache.sliding_window_hit(key, window, max_hits).await
+            }
+            Self::TokenBucket {
+                capacity,
+                refill_every,
+            } => cache.token_bucket_take(key, capacity, refill_every).await,
+        }
+    }
+}
+
+/// Sliding-window log step: drop hits older than the window, then record
+/// `now_ms` if there is room. `log` holds hit timestamps in ascending order.
+pub(crate) fn sliding_window_step(
+    log: &mut Vec<u64>,
+    now_ms: u64,
+    window_ms: u64,
+    max_hits: u64,
+) -> RateLimitDecision {
+    let cutoff = now_ms.saturating_sub(window_ms);
+    log.retain(|hit| *hit > cutoff);
+
+    let count = log.len() as u64;
+    if count < max_hits {
+        log.push(now_ms);
+        return RateLimitDecision {
+            allowed: true,
+            remaining: max_hits - count - 1,
+            retry_after: Duration::ZERO,
+        };
+    }
+
+    let oldest = log.first().copied().unwrap_or(now_ms);
+    RateLimitDecision {
+        allowed: false,
+        remaining: 0,
+        retry_after: Duration::from_millis((oldest + window_ms).saturating_sub(now_ms)),
+    }
+}
+
+/// Token bucket state: tokens left and when the last refill was credited.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct TokenBucketState {
+    pub tokens: u64,
+    pub refilled_at_ms: u64,
+}
+
+/// Token bucket step: credit whole tokens earned since the last refill, then
+/// take one if available. A missing state starts as a full bucket.
+pub(crate) fn token_bucket_step(
+    state: Option<TokenBucketState>,
+    now_ms: u64,
+    capacity: u64,
+    refill_every_ms: u64,
+) -> (TokenBucketState, RateLimitDecision) {
+    let refill_every_ms = refill_every_ms.max(1);
+    let mut state = state.unwrap_or(TokenBucketState {
+        tokens: capacity,
+        refilled_at_ms: now_ms,
+    });
+
+    let earned = now_ms.saturating_sub(state.refilled_at_ms) / refill_every_ms;
+    if earned > 0 {
+        state.tokens = state.tokens.saturating_add(earned).min(capacity);
+        state.refilled_at_ms += earned * refill_every_ms;
+    }
+    // A full bucket doesn't bank time towards the next token.
+    if state.tokens >= capacity {
+        state.refilled_at_ms = now_ms;
+    }
+
+    if state.tokens > 0 {
+        state.tokens -= 1;
+        let decision = RateLimitDecision {
+            allowed: true,
+            remaining: state.tokens,
+            retry_after: Duration::ZERO,
+        };
+        return (state, decision);
+    }
+
+    let retry_after_ms = (state.refilled_at_ms + refill_every_ms).saturating_sub(now_ms);
+    let decision = RateLimitDecision {
+        allowed: false,
+        remaining: 0,
+        retry_after: Duration::from_millis(retry_after_ms),
+    };
+    (state, decision)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sliding_window_blocks_bursts_across_window_edges() {
+        let mut log = Vec::new();
+
+        // Two hits at the end of one window...
+        assert!(sliding_window_step(&mut log, 9_000, 10_000, 2).allowed);
+        assert!(sliding_window_step(&mut log, 9_500, 10_000, 2).allowed);
+
+        // ...leave no room at the start of the next, unlike a fixed window.
+        let blocked = sliding_window_step(&mut log, 10_500, 10_000, 2);
+        assert!(!blocked.allowed);
+        assert_eq!(blocked.retry_after, Duration::from_millis(8_500));
+
+        assert!(sliding_window_step(&mut log, 19_001, 10_000, 2).allowed);
+    }
+
+    #[test]
+    fn token_bucket_refills_one_token_per_interval() {
+        let (state, first) = token_bucket_step(None, 0, 2, 1_000);
+        assert!(first.allowed);
+        assert_eq!(first.remaining, 1);
+
+        let (state, second) = token_bucket_step(Some(state), 100, 2, 1_000);
+        assert!(second.allowed);
+
+        let (state, blocked) = token_bucket_step(Some(state), 200, 2, 1_000);
+        assert!(!blocked.allowed);
+        assert_eq!(blocked.retry_after, Duration::from_millis(800));
+
+        let (_, refilled) = token_bucket_step(Some(state), 1_000, 2, 1_000);
+        assert!(refilled.allowed);
+        assert_eq!(refilled.remaining, 0);
+    }
+}
@@ -1,9 +1,65 @@
 use deadpool_redis::{Config, Pool, Runtime};
 use redis::AsyncCommands;
+use redis::aio::PubSubStream;
+
+use super::rate_limit::RateLimitDecision;
+
+/// Sliding-window log over a sorted set scored by hit time in milliseconds.
+/// Returns `{allowed, remaining, retry_after_ms}`.
+const SLIDING_WINDOW_SCRIPT: &str = r"
+local now = tonumber(ARGV[1])
+local window = tonumber(ARGV[2])
+local limit = tonumber(ARGV[3])
+redis.call('ZREMRANGEBYSCORE', KEYS[1], '-inf', now - window)
+local count = redis.call('ZCARD', KEYS[1])
+if count < limit then
+  redis.call('ZADD', KEYS[1], now, ARGV[4])
+  redis.call('PEXPIRE', KEYS[1], window)
+  return {1, limit - count - 1, 0}
+end
+local oldest = redis.call('ZRANGE', KEYS[1], 0, 0, 'WITHSCORES')
+return {0, 0, tonumber(oldest[2]) + window - now}
+";
+
+/// Token bucket stored as a hash of `tokens` and `ts` (last refill, ms).
+/// Mirrors `token_bucket_step`. Returns `{allowed, remaining, retry_after_ms}`.
+const TOKEN_BUCKET_SCRIPT: &str = r"
+local now = tonumber(ARGV[1])
+local capacity = tonumber(ARGV[2])
+local interval = tonumber(ARGV[3])
+local state = redis.call('HMGET', KEYS[1], 'tokens', 'ts')
+local tokens = tonumber(state[1])
+local ts = tonumber(state[2])
+if tokens == nil or ts == nil then
+  tokens = capacity
+  ts = now
+end
+local earned = math.floor((now - ts) / interval)
+if earned > 0 then
+  tokens = math.min(capacity, tokens + earned)
+  ts = ts + earned * interval
+end
+if tokens >= capacity then
+  ts = now
+end
+local allowed = 0
+local retry = 0
+if tokens > 0 then
+  tokens = tokens - 1
+  allowed = 1
+else
+  retry = ts + interval - now
+end
+redis.call('HSET', KEYS[1], 'tokens', tokens, 'ts', ts)
+redis.call('PEXPIRE', KEYS[1], capacity * interval)
+return {allowed, tokens, retry}
+";
 
 #[derive(Clone, Debug)]
 pub struct RedisCacheStore {
     pool: Pool,
+    /// Pub/sub needs a dedicated connection, which the pool can't lend out.
+    client: redis::Client,
 }
 
 impl RedisCacheStore {
@@ -12,8 +68,10 @@ impl RedisCacheStore {
         let pool = config
             .create_pool(Some(Runtime::Tokio1))
             .map_err(|e| anyhow::anyhow!("failed to create redis pool: {e}"))?;
+        let client = redis::Client::open(redis_url)
+            .map_err(|e| anyhow::anyhow!("failed to create redis client: {e}"))?;
 
-        Ok(Self { pool })
+        Ok(Self { pool, client })
     }
 
     pub async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
@@ -86,6 +144,88 @@ impl RedisCacheStore {
         Ok(count)
     }
 
+    pub async fn sliding_window_hit(
+        &self,
+        key: &str,
+        now_ms: u64,
+        window_ms: u64,
+        max_hits: u64,
+        member: &str,
+    ) -> anyhow::Result<RateLimitDecision> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| anyhow::anyhow!("failed to get redis connection: {e}"))?;
+
+        let (allowed, remaining, retry_after_ms) = redis::Script::new(SLIDING_WINDOW_SCRIPT)
+            .key(key)
+            .arg(now_ms)
+            .arg(window_ms)
+            .arg(max_hits)
+            .arg(member)
+            .invoke_async::<(u8, u64, i64)>(&mut *conn)
+            .await
+            .map_err(|e| anyhow::anyhow!("redis sliding window failed for key `{key}`: {e}"))?;
+
+        Ok(script_decision(allowed, remaining, retry_after_ms))
+    }
+
+    pub async fn token_bucket_take(
+        &self,
+        key: &str,
+        now_ms: u64,
+        capacity: u64,
+        refill_every_ms: u64,
+    ) -> anyhow::Result<RateLimitDecision> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| anyhow::anyhow!("failed to get redis connection: {e}"))?;
+
+        let (allowed, remaining, retry_after_ms) = redis::Script::new(TOKEN_BUCKET_SCRIPT)
+            .key(key)
+            .arg(now_ms)
+            .arg(capacity)
+            .arg(refill_every_ms.max(1))
+            .invoke_async::<(u8, u64, i64)>(&mut *conn)
+            .await
+            .map_err(|e| anyhow::anyhow!("redis token bucket failed for key `{key}`: {e}"))?;
+
+        Ok(script_decision(allowed, remaining, retry_after_ms))
+    }
+
+    pub async fn publish(&self, channel: &str, message: &str) -> anyhow::Result<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| anyhow::anyhow!("failed to get redis connection: {e}"))?;
+
+        let _ = conn
+            .publish::<_, _, u64>(channel, message)
+            .await
+            .map_err(|e| anyhow::anyhow!("redis PUBLISH failed for channel `{channel}`: {e}"))?;
+
+        Ok(())
+    }
+
+    pub async fn subscribe(&self, channel: &str) -> anyhow::Result<PubSubStream> {
+        let mut pubsub = self
+            .client
+            .get_async_pubsub()
+            .await
+            .map_err(|e| anyhow::anyhow!("failed to open redis pub/sub connection: {e}"))?;
+
+        pubsub
+            .subscribe(channel)
+            .await
+            .map_err(|e| anyhow::anyhow!("redis SUBSCRIBE failed for channel `{channel}`: {e}"))?;
+
+        Ok(pubsub.into_on_message())
+    }
+
     pub async fn ping(&self) -> anyhow::Result<()> {
         let mut conn = self
             .pool
@@ -107,3 +247,11 @@ impl RedisCacheStore {
         Ok(())
     }
 }
+
+fn script_decision(allowed: u8, remaining: u64, retry_after_ms: i64) -> RateLimitDecision {
+    RateLimitDecision {
+        allowed: allowed == 1,
+        remaining,
+        retry_after: std::time::Duration::from_millis(u64::try_from(retry_after_ms).unwrap_or(0)),
+    }
+}
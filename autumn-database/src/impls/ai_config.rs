@@ -1,6 +1,9 @@
 use anyhow::Context as _;
 
-use crate::cache::{CONFIG_CACHE_TTL, ai_config_key, invalidate_ai_config};
+use crate::cache::{
+    CONFIG_CACHE_TTL, ai_config_key, ai_long_reply_mode_key, ai_thread_mode_key,
+    invalidate_ai_config,
+};
 use crate::database::Database;
 
 pub async fn get_llm_enabled(db: &Database, guild_id: u64) -> anyhow::Result<bool> {
@@ -38,3 +41,86 @@ pub async fn set_llm_enabled(db: &Database, guild_id: u64, enabled: bool) -> any
 
     Ok(())
 }
+
+/// How replies over Discord's message length limit are delivered: `split`
+/// (reply chain, the default) or `attachment` (a `.md` file).
+pub async fn get_llm_long_reply_mode(db: &Database, guild_id: u64) -> anyhow::Result<String> {
+    let cache_key = ai_long_reply_mode_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(&cache_key, CONFIG_CACHE_TTL, || async {
+            let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+            let mode: Option<String> = sqlx::query_scalar(
+                "SELECT long_reply_mode FROM guild_ai_config WHERE guild_id = $1",
+            )
+            .bind(guild_id_i64)
+            .fetch_optional(db.pool())
+            .await?;
+
+            Ok(mode.unwrap_or_else(|| "split".to_owned()))
+        })
+        .await
+}
+
+pub async fn set_llm_long_reply_mode(
+    db: &Database,
+    guild_id: u64,
+    mode: &str,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query(
+        "INSERT INTO guild_ai_config (guild_id, long_reply_mode)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET long_reply_mode = EXCLUDED.long_reply_mode",
+    )
+    .bind(guild_id_i64)
+    .bind(mode)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_ai_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Whether a mention outside a thread starts a new thread for the
+/// conversation. Off by default.
+pub async fn get_llm_thread_mode(db: &Database, guild_id: u64) -> anyhow::Result<bool> {
+    let cache_key = ai_thread_mode_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(&cache_key, CONFIG_CACHE_TTL, || async {
+            let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+            let enabled: Option<bool> =
+                sqlx::query_scalar("SELECT thread_mode FROM guild_ai_config WHERE guild_id = $1")
+                    .bind(guild_id_i64)
+                    .fetch_optional(db.pool())
+                    .await?;
+
+            Ok(enabled.unwrap_or(false))
+        })
+        .await
+}
+
+pub async fn set_llm_thread_mode(
+    db: &Database,
+    guild_id: u64,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query(
+        "INSERT INTO guild_ai_config (guild_id, thread_mode)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET thread_mode = EXCLUDED.thread_mode",
+    )
+    .bind(guild_id_i64)
+    .bind(enabled)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_ai_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use std::time::Duration;
+
+use anyhow::Context as _;
+
+use crate::cache::{
+    CONFIG_CACHE_TTL, ai_moderation_config_key, ai_moderation_rate_key,
+    invalidate_ai_moderation_config,
+};
+use crate::database::Database;
+use crate::model::ai_moderation::AiModerationConfig;
+
+const AI_MODERATION_RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// The guild's AI moderation settings, or the defaults (disabled) if the
+/// guild never configured it.
+pub async fn get_ai_moderation_config(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<AiModerationConfig> {
+    let cache_key = ai_moderation_config_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(&cache_key, CONFIG_CACHE_TTL, || async {
+            let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+            let row = sqlx::query_as::<_, (bool, f32, i16, i32)>(
+                "SELECT enabled, threshold, sample_percent, max_per_minute
+                 FROM ai_moderation_config WHERE guild_id = $1",
+            )
+            .bind(guild_id_i64)
+            .fetch_optional(db.pool())
+            .await?;
+
+            let Some((enabled, threshold, sample_percent, max_per_minute)) = row else {
+                return Ok(AiModerationConfig::default_for(guild_id));
+            };
+
+            Ok(AiModerationConfig {
+                guild_id,
+                enabled,
+                threshold,
+                sample_percent: u8::try_from(sample_percent)
+                    .context("sample_percent out of u8 range")?,
+                max_per_minute: u32::try_from(max_per_minute)
+                    .context("max_per_minute out of u32 range")?,
+            })
+        })
+        .await
+}
+
+pub async fn set_ai_moderation_enabled(
+    db: &Database,
+    guild_id: u64,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query(
+        "INSERT INTO ai_moderation_config (guild_id, enabled)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET enabled = EXCLUDED.enabled",
+    )
+    .bind(guild_id_i64)
+    .bind(enabled)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_ai_moderation_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// `threshold` is clamped to 0.0-1.0.
+pub async fn set_ai_moderation_threshold(
+    db: &Database,
+    guild_id: u64,
+    threshold: f32,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query(
+        "INSERT INTO ai_moderation_config (guild_id, threshold)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET threshold = EXCLUDED.threshold",
+    )
+    .bind(guild_id_i64)
+    .bind(threshold.clamp(0.0, 1.0))
+    .execute(db.pool())
+    .await?;
+
+    invalidate_ai_moderation_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// `sample_percent` is clamped to 1-100.
+pub async fn set_ai_moderation_sample_percent(
+    db: &Database,
+    guild_id: u64,
+    sample_percent: u8,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query(
+        "INSERT INTO ai_moderation_config (guild_id, sample_percent)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET sample_percent = EXCLUDED.sample_percent",
+    )
+    .bind(guild_id_i64)
+    .bind(i16::from(sample_percent.clamp(1, 100)))
+    .execute(db.pool())
+    .await?;
+
+    invalidate_ai_moderation_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+pub async fn set_ai_moderation_max_per_minute(
+    db: &Database,
+    guild_id: u64,
+    max_per_minute: u32,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let max_per_minute_i32 =
+        i32::try_from(max_per_minute.max(1)).context("max_per_minute out of i32 range")?;
+
+    sqlx::query(
+        "INSERT INTO ai_moderation_config (guild_id, max_per_minute)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET max_per_minute = EXCLUDED.max_per_minute",
+    )
+    .bind(guild_id_i64)
+    .bind(max_per_minute_i32)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_ai_moderation_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Count one classification against the guild's per-minute cap and report
+/// whether it is still within the cap.
+pub async fn ai_moderation_within_rate(
+    db: &Database,
+    guild_id: u64,
+    max_per_minute: u32,
+) -> anyhow::Result<bool> {
+    let cache = db.cache();
+    let key = ai_moderation_rate_key(cache, guild_id);
+    let count = cache
+        .increment_with_window(&key, AI_MODERATION_RATE_WINDOW)
+        .await?;
+
+    if count > u64::from(max_per_minute) {
+        cache.record_rate_limit_block();
+        return Ok(false);
+    }
+
+    Ok(true)
+}
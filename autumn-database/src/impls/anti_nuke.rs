@@ -0,0 +1,193 @@
+use std::time::Duration;
+
+use anyhow::Context as _;
+
+use crate::cache::{
+    CONFIG_CACHE_TTL, RateLimiter, anti_nuke_actions_key, anti_nuke_config_key,
+    anti_nuke_response_key, invalidate_anti_nuke_config,
+};
+use crate::database::Database;
+use crate::model::anti_nuke::AntiNukeConfig;
+
+/// The guild's anti-nuke settings, or the defaults (disabled) if the guild
+/// never configured it.
+pub async fn get_anti_nuke_config(db: &Database, guild_id: u64) -> anyhow::Result<AntiNukeConfig> {
+    let cache_key = anti_nuke_config_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(&cache_key, CONFIG_CACHE_TTL, || async {
+            let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+            let row = sqlx::query_as::<_, (bool, i32, i32)>(
+                "SELECT enabled, max_actions, window_seconds
+                 FROM anti_nuke_config WHERE guild_id = $1",
+            )
+            .bind(guild_id_i64)
+            .fetch_optional(db.pool())
+            .await?;
+
+            let trusted_rows = sqlx::query_scalar::<_, i64>(
+                "SELECT user_id FROM anti_nuke_trusted_users
+                 WHERE guild_id = $1
+                 ORDER BY user_id",
+            )
+            .bind(guild_id_i64)
+            .fetch_all(db.pool())
+            .await?;
+
+            let trusted_user_ids = trusted_rows
+                .into_iter()
+                .map(|user_id| u64::try_from(user_id).context("user_id row out of u64 range"))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            let mut config = AntiNukeConfig::default_for(guild_id);
+            config.trusted_user_ids = trusted_user_ids;
+
+            if let Some((enabled, max_actions, window_seconds)) = row {
+                config.enabled = enabled;
+                config.max_actions =
+                    u32::try_from(max_actions).context("max_actions out of u32 range")?;
+                config.window_seconds =
+                    u32::try_from(window_seconds).context("window_seconds out of u32 range")?;
+            }
+
+            Ok(config)
+        })
+        .await
+}
+
+pub async fn set_anti_nuke_enabled(
+    db: &Database,
+    guild_id: u64,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query(
+        "INSERT INTO anti_nuke_config (guild_id, enabled)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET enabled = EXCLUDED.enabled",
+    )
+    .bind(guild_id_i64)
+    .bind(enabled)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_anti_nuke_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Both values are raised to at least 1.
+pub async fn set_anti_nuke_threshold(
+    db: &Database,
+    guild_id: u64,
+    max_actions: u32,
+    window_seconds: u32,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let max_actions_i32 =
+        i32::try_from(max_actions.max(1)).context("max_actions out of i32 range")?;
+    let window_seconds_i32 =
+        i32::try_from(window_seconds.max(1)).context("window_seconds out of i32 range")?;
+
+    sqlx::query(
+        "INSERT INTO anti_nuke_config (guild_id, max_actions, window_seconds)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (guild_id)
+         DO UPDATE SET max_actions = EXCLUDED.max_actions,
+                       window_seconds = EXCLUDED.window_seconds",
+    )
+    .bind(guild_id_i64)
+    .bind(max_actions_i32)
+    .bind(window_seconds_i32)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_anti_nuke_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Returns `false` if the user was already trusted.
+pub async fn add_anti_nuke_trusted_user(
+    db: &Database,
+    guild_id: u64,
+    user_id: u64,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let user_id_i64 = i64::try_from(user_id).context("user_id out of i64 range")?;
+
+    let result = sqlx::query(
+        "INSERT INTO anti_nuke_trusted_users (guild_id, user_id)
+         VALUES ($1, $2)
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(guild_id_i64)
+    .bind(user_id_i64)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_anti_nuke_config(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Returns `false` if the user wasn't trusted.
+pub async fn remove_anti_nuke_trusted_user(
+    db: &Database,
+    guild_id: u64,
+    user_id: u64,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let user_id_i64 = i64::try_from(user_id).context("user_id out of i64 range")?;
+
+    let result = sqlx::query(
+        "DELETE FROM anti_nuke_trusted_users
+         WHERE guild_id = $1 AND user_id = $2",
+    )
+    .bind(guild_id_i64)
+    .bind(user_id_i64)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_anti_nuke_config(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Count one destructive action by `actor_id` and report whether it pushed
+/// them over the guild's threshold. Only the first crossing within a window
+/// reports `true`, so a burst is responded to once rather than per action.
+pub async fn record_anti_nuke_action(
+    db: &Database,
+    config: &AntiNukeConfig,
+    actor_id: u64,
+) -> anyhow::Result<bool> {
+    let cache = db.cache();
+    let window = Duration::from_secs(u64::from(config.window_seconds.max(1)));
+
+    let actions = RateLimiter::SlidingWindow {
+        window,
+        max_hits: u64::from(config.max_actions.max(1)),
+    }
+    .check(
+        cache,
+        &anti_nuke_actions_key(cache, config.guild_id, actor_id),
+    )
+    .await?;
+    if actions.allowed {
+        return Ok(false);
+    }
+
+    let response = RateLimiter::SlidingWindow {
+        window,
+        max_hits: 1,
+    }
+    .check(
+        cache,
+        &anti_nuke_response_key(cache, config.guild_id, actor_id),
+    )
+    .await?;
+
+    Ok(response.allowed)
+}
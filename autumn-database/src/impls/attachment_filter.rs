@@ -0,0 +1,364 @@
+use anyhow::Context as _;
+
+use crate::cache::{
+    CONFIG_CACHE_TTL, WORD_LIST_CACHE_TTL, attachment_filter_channel_key,
+    attachment_filter_config_key, attachment_filter_extensions_key, invalidate_attachment_filter,
+    invalidate_attachment_filter_channel,
+};
+use crate::database::Database;
+use crate::model::attachment_filter::{AttachmentChannelRule, AttachmentFilterConfig};
+
+// ---------------------------------------------------------------------------
+// Config CRUD
+// ---------------------------------------------------------------------------
+
+pub async fn get_attachment_filter_config(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<AttachmentFilterConfig>> {
+    let cache_key = attachment_filter_config_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(&cache_key, CONFIG_CACHE_TTL, || async {
+            let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+            let row = sqlx::query_as::<_, (bool, String, String, Option<i64>)>(
+                "SELECT enabled, action, extension_mode, max_size_bytes
+                 FROM attachment_filter_config WHERE guild_id = $1",
+            )
+            .bind(guild_id_i64)
+            .fetch_optional(db.pool())
+            .await?;
+
+            row.map(|(enabled, action, extension_mode, max_size_bytes)| {
+                Ok(AttachmentFilterConfig {
+                    guild_id,
+                    enabled,
+                    action,
+                    extension_mode,
+                    max_size_bytes: max_size_bytes
+                        .map(u64::try_from)
+                        .transpose()
+                        .context("max_size_bytes out of u64 range")?,
+                })
+            })
+            .transpose()
+        })
+        .await
+}
+
+/// Check whether the attachment filter is enabled for a guild and return the config.
+pub async fn get_attachment_filter_if_enabled(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Option<AttachmentFilterConfig>> {
+    let config = get_attachment_filter_config(db, guild_id).await?;
+    Ok(config.filter(|cfg| cfg.enabled))
+}
+
+pub async fn set_attachment_filter_enabled(
+    db: &Database,
+    guild_id: u64,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query(
+        "INSERT INTO attachment_filter_config (guild_id, enabled)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET enabled = EXCLUDED.enabled",
+    )
+    .bind(guild_id_i64)
+    .bind(enabled)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_attachment_filter(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+pub async fn set_attachment_filter_action(
+    db: &Database,
+    guild_id: u64,
+    action: &str,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query(
+        "INSERT INTO attachment_filter_config (guild_id, action)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET action = EXCLUDED.action",
+    )
+    .bind(guild_id_i64)
+    .bind(action)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_attachment_filter(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Set whether the extension list is a deny list (`deny`) or an allow list (`allow`).
+pub async fn set_attachment_filter_extension_mode(
+    db: &Database,
+    guild_id: u64,
+    mode: &str,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query(
+        "INSERT INTO attachment_filter_config (guild_id, extension_mode)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET extension_mode = EXCLUDED.extension_mode",
+    )
+    .bind(guild_id_i64)
+    .bind(mode)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_attachment_filter(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Set the maximum attachment size in bytes. `None` removes the limit.
+pub async fn set_attachment_filter_max_size(
+    db: &Database,
+    guild_id: u64,
+    max_size_bytes: Option<u64>,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let max_size_i64 = max_size_bytes
+        .map(i64::try_from)
+        .transpose()
+        .context("max_size_bytes out of i64 range")?;
+
+    sqlx::query(
+        "INSERT INTO attachment_filter_config (guild_id, max_size_bytes)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET max_size_bytes = EXCLUDED.max_size_bytes",
+    )
+    .bind(guild_id_i64)
+    .bind(max_size_i64)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_attachment_filter(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Extension CRUD
+// ---------------------------------------------------------------------------
+
+/// Normalize user input like `.EXE` or ` exe ` into the stored form (`exe`).
+pub fn normalize_extension(raw: &str) -> String {
+    raw.trim().trim_start_matches('.').to_ascii_lowercase()
+}
+
+/// Add an extension to the guild's list. Returns `true` if inserted, `false`
+/// if the extension already existed.
+pub async fn add_filter_extension(
+    db: &Database,
+    guild_id: u64,
+    extension: &str,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let normalized = normalize_extension(extension);
+
+    let result = sqlx::query(
+        "INSERT INTO attachment_filter_extensions (guild_id, extension)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id, extension) DO NOTHING",
+    )
+    .bind(guild_id_i64)
+    .bind(&normalized)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_attachment_filter(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Remove an extension from the guild's list. Returns `true` if removed.
+pub async fn remove_filter_extension(
+    db: &Database,
+    guild_id: u64,
+    extension: &str,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let normalized = normalize_extension(extension);
+
+    let result = sqlx::query(
+        "DELETE FROM attachment_filter_extensions WHERE guild_id = $1 AND extension = $2",
+    )
+    .bind(guild_id_i64)
+    .bind(&normalized)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_attachment_filter(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// List the guild's extensions, sorted alphabetically.
+pub async fn list_filter_extensions(db: &Database, guild_id: u64) -> anyhow::Result<Vec<String>> {
+    let cache_key = attachment_filter_extensions_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(&cache_key, WORD_LIST_CACHE_TTL, || async {
+            let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+            let extensions: Vec<String> = sqlx::query_scalar(
+                "SELECT extension FROM attachment_filter_extensions
+                 WHERE guild_id = $1
+                 ORDER BY extension ASC",
+            )
+            .bind(guild_id_i64)
+            .fetch_all(db.pool())
+            .await?;
+
+            Ok(extensions)
+        })
+        .await
+}
+
+// ---------------------------------------------------------------------------
+// Per-channel media rules
+// ---------------------------------------------------------------------------
+
+pub async fn get_attachment_channel_rule(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+) -> anyhow::Result<Option<AttachmentChannelRule>> {
+    let cache_key = attachment_filter_channel_key(db.cache(), guild_id, channel_id);
+    db.cache()
+        .get_or_load_json(&cache_key, CONFIG_CACHE_TTL, || async {
+            let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+            let channel_id_i64 =
+                i64::try_from(channel_id).context("channel_id out of i64 range")?;
+
+            let row = sqlx::query_as::<_, (bool, bool)>(
+                "SELECT allow_images, allow_videos
+                 FROM attachment_filter_channel_rules
+                 WHERE guild_id = $1 AND channel_id = $2",
+            )
+            .bind(guild_id_i64)
+            .bind(channel_id_i64)
+            .fetch_optional(db.pool())
+            .await?;
+
+            Ok(
+                row.map(|(allow_images, allow_videos)| AttachmentChannelRule {
+                    channel_id,
+                    allow_images,
+                    allow_videos,
+                }),
+            )
+        })
+        .await
+}
+
+pub async fn list_attachment_channel_rules(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Vec<AttachmentChannelRule>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let rows = sqlx::query_as::<_, (i64, bool, bool)>(
+        "SELECT channel_id, allow_images, allow_videos
+         FROM attachment_filter_channel_rules
+         WHERE guild_id = $1
+         ORDER BY channel_id ASC",
+    )
+    .bind(guild_id_i64)
+    .fetch_all(db.pool())
+    .await?;
+
+    rows.into_iter()
+        .map(|(channel_id, allow_images, allow_videos)| {
+            Ok(AttachmentChannelRule {
+                channel_id: u64::try_from(channel_id).context("channel_id row out of u64 range")?,
+                allow_images,
+                allow_videos,
+            })
+        })
+        .collect()
+}
+
+pub async fn set_channel_allow_images(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+    allowed: bool,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
+
+    sqlx::query(
+        "INSERT INTO attachment_filter_channel_rules (guild_id, channel_id, allow_images)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (guild_id, channel_id) DO UPDATE SET allow_images = EXCLUDED.allow_images",
+    )
+    .bind(guild_id_i64)
+    .bind(channel_id_i64)
+    .bind(allowed)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_attachment_filter_channel(db.cache(), guild_id, channel_id).await?;
+
+    Ok(())
+}
+
+pub async fn set_channel_allow_videos(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+    allowed: bool,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
+
+    sqlx::query(
+        "INSERT INTO attachment_filter_channel_rules (guild_id, channel_id, allow_videos)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (guild_id, channel_id) DO UPDATE SET allow_videos = EXCLUDED.allow_videos",
+    )
+    .bind(guild_id_i64)
+    .bind(channel_id_i64)
+    .bind(allowed)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_attachment_filter_channel(db.cache(), guild_id, channel_id).await?;
+
+    Ok(())
+}
+
+/// Remove a channel's media rule so it falls back to allowing images and videos.
+/// Returns `true` if a rule was removed.
+pub async fn clear_attachment_channel_rule(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
+
+    let result = sqlx::query(
+        "DELETE FROM attachment_filter_channel_rules WHERE guild_id = $1 AND channel_id = $2",
+    )
+    .bind(guild_id_i64)
+    .bind(channel_id_i64)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_attachment_filter_channel(db.cache(), guild_id, channel_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
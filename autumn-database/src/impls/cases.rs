@@ -433,6 +433,69 @@ pub async fn add_case_note(
     Ok(true)
 }
 
+/// Move a case out of `from_status` into `to_status`, recording who did it.
+/// Returns `None` if the case does not exist or is no longer in `from_status`.
+pub async fn transition_case_status(
+    db: &Database,
+    guild_id: u64,
+    case_code: &str,
+    action_case_number: u64,
+    actor_user_id: u64,
+    from_status: &str,
+    to_status: &str,
+) -> anyhow::Result<Option<ModerationCase>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let action_case_number_i64 =
+        i64::try_from(action_case_number).context("action_case_number out of i64 range")?;
+    let actor_user_id_i64 =
+        i64::try_from(actor_user_id).context("actor_user_id out of i64 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    let mut tx = db.pool().begin().await?;
+
+    let updated: Option<ModerationCaseRow> = sqlx::query_as(
+        "UPDATE mod_cases
+         SET status = $1, updated_at = $2
+         WHERE guild_id = $3 AND case_code = $4 AND action_case_number = $5 AND status = $6
+         RETURNING id, case_number, case_code, action_case_number, guild_id, target_user_id, moderator_user_id, action, reason, status, duration_seconds, created_at, updated_at",
+    )
+    .bind(to_status)
+    .bind(now)
+    .bind(guild_id_i64)
+    .bind(case_code)
+    .bind(action_case_number_i64)
+    .bind(from_status)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(updated) = updated else {
+        tx.rollback().await?;
+        return Ok(None);
+    };
+
+    sqlx::query(
+        "INSERT INTO mod_case_events (
+            case_id,
+            guild_id,
+            event_type,
+            actor_user_id,
+            note,
+            created_at
+         ) VALUES ($1, $2, 'status_updated', $3, $4, $5)",
+    )
+    .bind(updated.id)
+    .bind(guild_id_i64)
+    .bind(actor_user_id_i64)
+    .bind(Some(format!("{from_status} -> {to_status}")))
+    .bind(now)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Some(to_moderation_case(updated)?))
+}
+
 fn to_case_summary(row: ModerationCaseRow) -> anyhow::Result<CaseSummary> {
     Ok(CaseSummary {
         case_number: u64::try_from(row.case_number).context("case_number out of u64 range")?,
@@ -497,10 +560,18 @@ fn action_code(action: &str) -> &'static str {
         "unwarn_all" => "UWA",
         "purge" => "P",
         "terminate" => "TR",
-        "word_filter_timeout" | "word_filter_delete" | "word_filter_log" | "word_filter_warn" => {
-            "WF"
-        }
+        "word_filter_timeout"
+        | "word_filter_delete"
+        | "word_filter_log"
+        | "word_filter_warn"
+        | "word_filter_automod" => "WF",
+        "attachment_filter_timeout"
+        | "attachment_filter_delete"
+        | "attachment_filter_log"
+        | "attachment_filter_warn" => "AF",
         "auto_timeout" => "AT",
+        "review" => "RV",
+        "anti_nuke" => "AN",
         _ => "M",
     }
 }
@@ -0,0 +1,98 @@
+use anyhow::Context as _;
+
+use crate::cache::{CONFIG_CACHE_TTL, command_acl_key, invalidate_command_acl};
+use crate::database::Database;
+use crate::model::command_acl::{CommandAclRule, CommandAclTarget};
+
+/// Every command rule configured in the guild.
+pub async fn get_command_acl_rules(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Vec<CommandAclRule>> {
+    let cache_key = command_acl_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(&cache_key, CONFIG_CACHE_TTL, || async {
+            let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+            let rows = sqlx::query_as::<_, (String, String, i64, bool)>(
+                "SELECT command_name, target_kind, target_id, allow
+                 FROM command_acl
+                 WHERE guild_id = $1
+                 ORDER BY command_name, target_kind, target_id",
+            )
+            .bind(guild_id_i64)
+            .fetch_all(db.pool())
+            .await?;
+
+            rows.into_iter()
+                .map(|(command_name, target_kind, target_id, allow)| {
+                    let target_id =
+                        u64::try_from(target_id).context("target_id row out of u64 range")?;
+                    let target = CommandAclTarget::from_parts(&target_kind, target_id)
+                        .with_context(|| format!("unknown command acl target `{target_kind}`"))?;
+                    Ok(CommandAclRule {
+                        command_name,
+                        target,
+                        allow,
+                    })
+                })
+                .collect()
+        })
+        .await
+}
+
+/// Insert or replace the rule for `command_name` and `target`.
+pub async fn set_command_acl_rule(
+    db: &Database,
+    guild_id: u64,
+    command_name: &str,
+    target: CommandAclTarget,
+    allow: bool,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let target_id_i64 = i64::try_from(target.id()).context("target_id out of i64 range")?;
+
+    sqlx::query(
+        "INSERT INTO command_acl (guild_id, command_name, target_kind, target_id, allow)
+         VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (guild_id, command_name, target_kind, target_id)
+         DO UPDATE SET allow = EXCLUDED.allow",
+    )
+    .bind(guild_id_i64)
+    .bind(command_name)
+    .bind(target.kind())
+    .bind(target_id_i64)
+    .bind(allow)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_command_acl(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Returns `false` if there was no such rule.
+pub async fn remove_command_acl_rule(
+    db: &Database,
+    guild_id: u64,
+    command_name: &str,
+    target: CommandAclTarget,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let target_id_i64 = i64::try_from(target.id()).context("target_id out of i64 range")?;
+
+    let result = sqlx::query(
+        "DELETE FROM command_acl
+         WHERE guild_id = $1 AND command_name = $2 AND target_kind = $3 AND target_id = $4",
+    )
+    .bind(guild_id_i64)
+    .bind(command_name)
+    .bind(target.kind())
+    .bind(target_id_i64)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_command_acl(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,100 @@
+use anyhow::Context as _;
+
+use crate::cache::{CONFIG_CACHE_TTL, disabled_commands_key, invalidate_disabled_commands};
+use crate::database::Database;
+use crate::model::disabled_commands::DisabledCommand;
+
+/// Every command disabled in the guild, guild-wide entries first.
+pub async fn get_disabled_commands(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Vec<DisabledCommand>> {
+    let cache_key = disabled_commands_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(&cache_key, CONFIG_CACHE_TTL, || async {
+            let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+            let rows = sqlx::query_as::<_, (String, Option<i64>)>(
+                "SELECT command_name, channel_id
+                 FROM disabled_commands
+                 WHERE guild_id = $1
+                 ORDER BY channel_id NULLS FIRST, command_name",
+            )
+            .bind(guild_id_i64)
+            .fetch_all(db.pool())
+            .await?;
+
+            rows.into_iter()
+                .map(|(command_name, channel_id)| {
+                    let channel_id = channel_id
+                        .map(u64::try_from)
+                        .transpose()
+                        .context("channel_id row out of u64 range")?;
+                    Ok(DisabledCommand {
+                        command_name,
+                        channel_id,
+                    })
+                })
+                .collect()
+        })
+        .await
+}
+
+/// Disable `command_name` guild-wide, or only in `channel_id`. Returns
+/// `false` if it was already disabled there.
+pub async fn disable_command(
+    db: &Database,
+    guild_id: u64,
+    command_name: &str,
+    channel_id: Option<u64>,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = channel_id
+        .map(i64::try_from)
+        .transpose()
+        .context("channel_id out of i64 range")?;
+
+    let result = sqlx::query(
+        "INSERT INTO disabled_commands (guild_id, command_name, channel_id)
+         VALUES ($1, $2, $3)
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(guild_id_i64)
+    .bind(command_name)
+    .bind(channel_id_i64)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_disabled_commands(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Re-enable `command_name` guild-wide, or only in `channel_id`. Returns
+/// `false` if it wasn't disabled there.
+pub async fn enable_command(
+    db: &Database,
+    guild_id: u64,
+    command_name: &str,
+    channel_id: Option<u64>,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = channel_id
+        .map(i64::try_from)
+        .transpose()
+        .context("channel_id out of i64 range")?;
+
+    let result = sqlx::query(
+        "DELETE FROM disabled_commands
+         WHERE guild_id = $1 AND command_name = $2 AND channel_id IS NOT DISTINCT FROM $3",
+    )
+    .bind(guild_id_i64)
+    .bind(command_name)
+    .bind(channel_id_i64)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_disabled_commands(db.cache(), guild_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
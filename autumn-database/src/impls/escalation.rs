@@ -182,7 +182,7 @@ pub async fn count_warnings_in_window(
 }
 
 /// Count timeout cases for a user within a guild in the given time window.
-/// Includes both manual timeouts, auto-timeouts, and word-filter timeouts.
+/// Includes manual timeouts, auto-timeouts, and word/attachment filter timeouts.
 pub async fn count_timeouts_in_window(
     db: &Database,
     guild_id: u64,
@@ -196,7 +196,7 @@ pub async fn count_timeouts_in_window(
     let count: i64 = sqlx::query_scalar(
         "SELECT COUNT(*) FROM mod_cases \
          WHERE guild_id = $1 AND target_user_id = $2 AND created_at >= $3 \
-         AND action IN ('timeout', 'auto_timeout', 'word_filter_timeout')",
+         AND action IN ('timeout', 'auto_timeout', 'word_filter_timeout', 'attachment_filter_timeout')",
     )
     .bind(guild_id_i64)
     .bind(user_id_i64)
@@ -0,0 +1,54 @@
+use anyhow::Context as _;
+
+use crate::cache::{CONFIG_CACHE_TTL, guild_prefix_key, invalidate_guild_prefix};
+use crate::database::Database;
+
+/// The guild's own command prefix, if it set one.
+pub async fn get_guild_prefix(db: &Database, guild_id: u64) -> anyhow::Result<Option<String>> {
+    let cache_key = guild_prefix_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(&cache_key, CONFIG_CACHE_TTL, || async {
+            let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+            let prefix: Option<String> =
+                sqlx::query_scalar("SELECT prefix FROM guild_prefixes WHERE guild_id = $1")
+                    .bind(guild_id_i64)
+                    .fetch_optional(db.pool())
+                    .await?;
+
+            Ok(prefix)
+        })
+        .await
+}
+
+pub async fn set_guild_prefix(db: &Database, guild_id: u64, prefix: &str) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query(
+        "INSERT INTO guild_prefixes (guild_id, prefix)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id) DO UPDATE SET prefix = EXCLUDED.prefix",
+    )
+    .bind(guild_id_i64)
+    .bind(prefix)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_guild_prefix(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Go back to the default prefix.
+pub async fn clear_guild_prefix(db: &Database, guild_id: u64) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    sqlx::query("DELETE FROM guild_prefixes WHERE guild_id = $1")
+        .bind(guild_id_i64)
+        .execute(db.pool())
+        .await?;
+
+    invalidate_guild_prefix(db.cache(), guild_id).await?;
+
+    Ok(())
+}
@@ -2,10 +2,15 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::Context as _;
 
-use crate::{database::Database, model::llm_chat::LlmChatEntry};
+use crate::{
+    cache::{CONFIG_CACHE_TTL, invalidate_llm_thread, llm_thread_key},
+    database::Database,
+    model::llm_chat::{LlmChannelMemory, LlmChatEntry},
+};
 
 #[derive(sqlx::FromRow)]
 struct LlmChatRow {
+    id: i64,
     user_id: i64,
     display_name: Option<String>,
     role: String,
@@ -13,6 +18,9 @@ struct LlmChatRow {
     created_at: i64,
 }
 
+/// `reply_to_user_id` is, for an assistant reply, the user whose prompt it
+/// answers.
+#[allow(clippy::too_many_arguments)]
 pub async fn insert_llm_chat_message(
     db: &Database,
     guild_id: u64,
@@ -21,15 +29,20 @@ pub async fn insert_llm_chat_message(
     display_name: Option<&str>,
     role: &str,
     content: &str,
+    reply_to_user_id: Option<u64>,
 ) -> anyhow::Result<()> {
     let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
     let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
     let user_id_i64 = i64::try_from(user_id).context("user_id out of i64 range")?;
+    let reply_to_user_id_i64 = reply_to_user_id
+        .map(i64::try_from)
+        .transpose()
+        .context("reply_to_user_id out of i64 range")?;
     let created_at_i64 = i64::try_from(now_unix_secs()).context("created_at out of i64 range")?;
 
     sqlx::query(
-           "INSERT INTO llm_chat_history (guild_id, channel_id, user_id, display_name, role, content, created_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)",
+           "INSERT INTO llm_chat_history (guild_id, channel_id, user_id, display_name, role, content, created_at, reply_to_user_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
     )
     .bind(guild_id_i64)
     .bind(channel_id_i64)
@@ -38,6 +51,7 @@ pub async fn insert_llm_chat_message(
     .bind(role)
     .bind(content)
     .bind(created_at_i64)
+    .bind(reply_to_user_id_i64)
     .execute(db.pool())
     .await?;
 
@@ -49,27 +63,81 @@ pub async fn list_recent_llm_chat_messages(
     guild_id: u64,
     channel_id: u64,
     limit: u32,
+) -> anyhow::Result<Vec<LlmChatEntry>> {
+    list_llm_chat_messages_after(db, guild_id, channel_id, None, limit).await
+}
+
+/// List the newest messages in a channel, newest first, optionally only those
+/// with an id greater than `after_id`.
+pub async fn list_llm_chat_messages_after(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+    after_id: Option<u64>,
+    limit: u32,
 ) -> anyhow::Result<Vec<LlmChatEntry>> {
     let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
     let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
+    let after_id_i64 = i64::try_from(after_id.unwrap_or(0)).context("after_id out of i64 range")?;
     let limit_i64 = i64::from(limit.clamp(1, 200));
 
     let rows: Vec<LlmChatRow> = sqlx::query_as(
-        "SELECT user_id, display_name, role, content, created_at
+        "SELECT id, user_id, display_name, role, content, created_at
          FROM llm_chat_history
-         WHERE guild_id = $1 AND channel_id = $2
+         WHERE guild_id = $1 AND channel_id = $2 AND id > $3
          ORDER BY created_at DESC, id DESC
-         LIMIT $3",
+         LIMIT $4",
+    )
+    .bind(guild_id_i64)
+    .bind(channel_id_i64)
+    .bind(after_id_i64)
+    .bind(limit_i64)
+    .fetch_all(db.pool())
+    .await?;
+
+    entries_from_rows(rows)
+}
+
+/// Up to `limit` entries with ids between `after_id` and `before_id`
+/// (both exclusive, either open), oldest first.
+pub async fn list_llm_chat_messages_oldest_first(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+    after_id: Option<u64>,
+    before_id: Option<u64>,
+    limit: u32,
+) -> anyhow::Result<Vec<LlmChatEntry>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
+    let after_id_i64 = i64::try_from(after_id.unwrap_or(0)).context("after_id out of i64 range")?;
+    let before_id_i64 = i64::try_from(before_id.unwrap_or(i64::MAX as u64))
+        .context("before_id out of i64 range")?;
+    let limit_i64 = i64::from(limit.clamp(1, 200));
+
+    let rows: Vec<LlmChatRow> = sqlx::query_as(
+        "SELECT id, user_id, display_name, role, content, created_at
+         FROM llm_chat_history
+         WHERE guild_id = $1 AND channel_id = $2 AND id > $3 AND id < $4
+         ORDER BY id ASC
+         LIMIT $5",
     )
     .bind(guild_id_i64)
     .bind(channel_id_i64)
+    .bind(after_id_i64)
+    .bind(before_id_i64)
     .bind(limit_i64)
     .fetch_all(db.pool())
     .await?;
 
+    entries_from_rows(rows)
+}
+
+fn entries_from_rows(rows: Vec<LlmChatRow>) -> anyhow::Result<Vec<LlmChatEntry>> {
     let mut out = Vec::with_capacity(rows.len());
     for row in rows {
         out.push(LlmChatEntry {
+            id: u64::try_from(row.id).context("id row out of u64 range")?,
             user_id: u64::try_from(row.user_id).context("user_id row out of u64 range")?,
             display_name: row.display_name,
             role: row.role,
@@ -81,6 +149,261 @@ pub async fn list_recent_llm_chat_messages(
     Ok(out)
 }
 
+pub async fn get_llm_channel_memory(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+) -> anyhow::Result<Option<LlmChannelMemory>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
+
+    let row = sqlx::query_as::<_, (String, i64, i64)>(
+        "SELECT summary, summarized_through_id, updated_at
+         FROM llm_channel_memory
+         WHERE guild_id = $1 AND channel_id = $2",
+    )
+    .bind(guild_id_i64)
+    .bind(channel_id_i64)
+    .fetch_optional(db.pool())
+    .await?;
+
+    row.map(|(summary, summarized_through_id, updated_at)| {
+        Ok(LlmChannelMemory {
+            guild_id,
+            channel_id,
+            summary,
+            summarized_through_id: u64::try_from(summarized_through_id)
+                .context("summarized_through_id out of u64 range")?,
+            updated_at: u64::try_from(updated_at).context("updated_at out of u64 range")?,
+        })
+    })
+    .transpose()
+}
+
+/// Save a channel's memory, built from history read at `read_at`. Nothing is
+/// saved if the channel's history was cleared or forgotten since then, so a
+/// summary of deleted messages can't outlive them.
+pub async fn upsert_llm_channel_memory(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+    summary: &str,
+    summarized_through_id: u64,
+    read_at: u64,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
+    let through_id_i64 =
+        i64::try_from(summarized_through_id).context("summarized_through_id out of i64 range")?;
+    let updated_at_i64 = i64::try_from(now_unix_secs()).context("updated_at out of i64 range")?;
+    let read_at_i64 = i64::try_from(read_at).context("read_at out of i64 range")?;
+
+    sqlx::query(
+        "INSERT INTO llm_channel_memory (guild_id, channel_id, summary, summarized_through_id, updated_at)
+         SELECT $1, $2, $3, $4, $5
+         WHERE NOT EXISTS (
+             SELECT 1 FROM llm_channel_memory_resets
+             WHERE guild_id = $1 AND channel_id = $2 AND reset_at >= $6
+         )
+         ON CONFLICT (guild_id, channel_id) DO UPDATE
+         SET summary = EXCLUDED.summary,
+             summarized_through_id = EXCLUDED.summarized_through_id,
+             updated_at = EXCLUDED.updated_at
+         WHERE llm_channel_memory.summarized_through_id < EXCLUDED.summarized_through_id",
+    )
+    .bind(guild_id_i64)
+    .bind(channel_id_i64)
+    .bind(summary)
+    .bind(through_id_i64)
+    .bind(updated_at_i64)
+    .bind(read_at_i64)
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+/// Delete a channel's chat history and memory. Returns the number of
+/// history rows removed.
+pub async fn clear_llm_channel_history(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+) -> anyhow::Result<u64> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = i64::try_from(channel_id).context("channel_id out of i64 range")?;
+
+    let reset_at_i64 = i64::try_from(now_unix_secs()).context("reset_at out of i64 range")?;
+
+    let mut tx = db.pool().begin().await?;
+
+    sqlx::query(
+        "INSERT INTO llm_channel_memory_resets (guild_id, channel_id, reset_at)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (guild_id, channel_id) DO UPDATE SET reset_at = EXCLUDED.reset_at",
+    )
+    .bind(guild_id_i64)
+    .bind(channel_id_i64)
+    .bind(reset_at_i64)
+    .execute(&mut *tx)
+    .await?;
+
+    let removed =
+        sqlx::query("DELETE FROM llm_chat_history WHERE guild_id = $1 AND channel_id = $2")
+            .bind(guild_id_i64)
+            .bind(channel_id_i64)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+    sqlx::query("DELETE FROM llm_channel_memory WHERE guild_id = $1 AND channel_id = $2")
+        .bind(guild_id_i64)
+        .bind(channel_id_i64)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(removed)
+}
+
+/// Delete everything a user said to Autumn across the guild, along with
+/// Autumn's replies to them, which often quote them. Memory of the channels
+/// they talked in is dropped too, since it may summarize their messages.
+/// Returns the number of history rows removed.
+pub async fn delete_llm_user_history(
+    db: &Database,
+    guild_id: u64,
+    user_id: u64,
+) -> anyhow::Result<u64> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let user_id_i64 = i64::try_from(user_id).context("user_id out of i64 range")?;
+    let reset_at_i64 = i64::try_from(now_unix_secs()).context("reset_at out of i64 range")?;
+
+    let mut tx = db.pool().begin().await?;
+
+    // Refreshes already under way must not save memory of these channels.
+    sqlx::query(
+        "INSERT INTO llm_channel_memory_resets (guild_id, channel_id, reset_at)
+         SELECT DISTINCT guild_id, channel_id, $3 FROM llm_chat_history
+         WHERE guild_id = $1 AND user_id = $2
+         ON CONFLICT (guild_id, channel_id) DO UPDATE SET reset_at = EXCLUDED.reset_at",
+    )
+    .bind(guild_id_i64)
+    .bind(user_id_i64)
+    .bind(reset_at_i64)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        "DELETE FROM llm_channel_memory
+         WHERE guild_id = $1
+           AND channel_id IN (
+               SELECT DISTINCT channel_id FROM llm_chat_history
+               WHERE guild_id = $1 AND user_id = $2
+           )",
+    )
+    .bind(guild_id_i64)
+    .bind(user_id_i64)
+    .execute(&mut *tx)
+    .await?;
+
+    // Replies stored before they recorded who they answer are paired with
+    // the first reply after each of the user's prompts.
+    let removed = sqlx::query(
+        "DELETE FROM llm_chat_history
+         WHERE guild_id = $1
+           AND (
+               user_id = $2
+               OR reply_to_user_id = $2
+               OR id IN (
+                   SELECT (
+                       SELECT MIN(reply.id) FROM llm_chat_history reply
+                       WHERE reply.guild_id = prompt.guild_id
+                         AND reply.channel_id = prompt.channel_id
+                         AND reply.role = 'assistant'
+                         AND reply.reply_to_user_id IS NULL
+                         AND reply.id > prompt.id
+                   )
+                   FROM llm_chat_history prompt
+                   WHERE prompt.guild_id = $1 AND prompt.user_id = $2 AND prompt.role = 'user'
+               )
+           )",
+    )
+    .bind(guild_id_i64)
+    .bind(user_id_i64)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    tx.commit().await?;
+
+    Ok(removed)
+}
+
+/// Record a thread Autumn started for an AI conversation.
+pub async fn insert_llm_thread(
+    db: &Database,
+    guild_id: u64,
+    thread_id: u64,
+    parent_channel_id: u64,
+    created_by: u64,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let thread_id_i64 = i64::try_from(thread_id).context("thread_id out of i64 range")?;
+    let parent_channel_id_i64 =
+        i64::try_from(parent_channel_id).context("parent_channel_id out of i64 range")?;
+    let created_by_i64 = i64::try_from(created_by).context("created_by out of i64 range")?;
+    let created_at_i64 = i64::try_from(now_unix_secs()).context("created_at out of i64 range")?;
+
+    sqlx::query(
+        "INSERT INTO llm_threads (thread_id, guild_id, parent_channel_id, created_by, created_at)
+         VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (thread_id) DO NOTHING",
+    )
+    .bind(thread_id_i64)
+    .bind(guild_id_i64)
+    .bind(parent_channel_id_i64)
+    .bind(created_by_i64)
+    .bind(created_at_i64)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_llm_thread(db.cache(), guild_id, thread_id).await?;
+
+    Ok(())
+}
+
+/// Parent channel of `channel_id` if it is a thread Autumn started for an AI
+/// conversation, `None` for any other channel.
+pub async fn get_llm_thread_parent(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+) -> anyhow::Result<Option<u64>> {
+    let cache_key = llm_thread_key(db.cache(), guild_id, channel_id);
+    db.cache()
+        .get_or_load_json(&cache_key, CONFIG_CACHE_TTL, || async {
+            let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+            let channel_id_i64 =
+                i64::try_from(channel_id).context("channel_id out of i64 range")?;
+
+            let parent: Option<i64> = sqlx::query_scalar(
+                "SELECT parent_channel_id FROM llm_threads
+                 WHERE guild_id = $1 AND thread_id = $2",
+            )
+            .bind(guild_id_i64)
+            .bind(channel_id_i64)
+            .fetch_optional(db.pool())
+            .await?;
+
+            parent
+                .map(|parent| u64::try_from(parent).context("parent_channel_id out of u64 range"))
+                .transpose()
+        })
+        .await
+}
+
 fn now_unix_secs() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -0,0 +1,147 @@
+use anyhow::Context as _;
+
+use crate::cache::{CONFIG_CACHE_TTL, invalidate_llm_prompt, llm_prompt_key};
+use crate::database::Database;
+use crate::model::llm_prompt::LlmSystemPrompt;
+
+/// Longest system prompt accepted by `set_llm_system_prompt`, in characters.
+pub const LLM_PROMPT_MAX_CHARS: usize = 2000;
+
+/// Get the stored prompt for exactly this scope: the guild-wide prompt when
+/// `channel_id` is `None`, otherwise that channel's override.
+pub async fn get_llm_system_prompt(
+    db: &Database,
+    guild_id: u64,
+    channel_id: Option<u64>,
+) -> anyhow::Result<Option<LlmSystemPrompt>> {
+    let cache_key = llm_prompt_key(db.cache(), guild_id, channel_id);
+    db.cache()
+        .get_or_load_json(&cache_key, CONFIG_CACHE_TTL, || async {
+            let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+            let channel_id_i64 = channel_id
+                .map(i64::try_from)
+                .transpose()
+                .context("channel_id out of i64 range")?;
+
+            let row = sqlx::query_as::<_, (String, i64, i64)>(
+                "SELECT prompt, updated_by, updated_at
+                 FROM llm_system_prompts
+                 WHERE guild_id = $1 AND channel_id IS NOT DISTINCT FROM $2",
+            )
+            .bind(guild_id_i64)
+            .bind(channel_id_i64)
+            .fetch_optional(db.pool())
+            .await?;
+
+            row.map(|(prompt, updated_by, updated_at)| {
+                Ok(LlmSystemPrompt {
+                    guild_id,
+                    channel_id,
+                    prompt,
+                    updated_by: u64::try_from(updated_by).context("updated_by out of u64 range")?,
+                    updated_at: u64::try_from(updated_at).context("updated_at out of u64 range")?,
+                })
+            })
+            .transpose()
+        })
+        .await
+}
+
+/// Resolve the prompt that applies in a channel: the channel override if one
+/// exists, otherwise the guild-wide prompt.
+pub async fn resolve_llm_system_prompt(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+) -> anyhow::Result<Option<LlmSystemPrompt>> {
+    if let Some(prompt) = get_llm_system_prompt(db, guild_id, Some(channel_id)).await? {
+        return Ok(Some(prompt));
+    }
+
+    get_llm_system_prompt(db, guild_id, None).await
+}
+
+pub async fn set_llm_system_prompt(
+    db: &Database,
+    guild_id: u64,
+    channel_id: Option<u64>,
+    prompt: &str,
+    updated_by: u64,
+) -> anyhow::Result<()> {
+    let prompt_chars = prompt.chars().count();
+    if prompt_chars > LLM_PROMPT_MAX_CHARS {
+        anyhow::bail!(
+            "system prompt is {prompt_chars} characters; the limit is {LLM_PROMPT_MAX_CHARS}"
+        );
+    }
+
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let updated_by_i64 = i64::try_from(updated_by).context("updated_by out of i64 range")?;
+
+    match channel_id {
+        Some(channel_id) => {
+            let channel_id_i64 =
+                i64::try_from(channel_id).context("channel_id out of i64 range")?;
+
+            sqlx::query(
+                "INSERT INTO llm_system_prompts (guild_id, channel_id, prompt, updated_by)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (guild_id, channel_id) WHERE channel_id IS NOT NULL
+                 DO UPDATE SET prompt = EXCLUDED.prompt,
+                               updated_by = EXCLUDED.updated_by,
+                               updated_at = EXTRACT(EPOCH FROM NOW())::BIGINT",
+            )
+            .bind(guild_id_i64)
+            .bind(channel_id_i64)
+            .bind(prompt)
+            .bind(updated_by_i64)
+            .execute(db.pool())
+            .await?;
+        }
+        None => {
+            sqlx::query(
+                "INSERT INTO llm_system_prompts (guild_id, channel_id, prompt, updated_by)
+                 VALUES ($1, NULL, $2, $3)
+                 ON CONFLICT (guild_id) WHERE channel_id IS NULL
+                 DO UPDATE SET prompt = EXCLUDED.prompt,
+                               updated_by = EXCLUDED.updated_by,
+                               updated_at = EXTRACT(EPOCH FROM NOW())::BIGINT",
+            )
+            .bind(guild_id_i64)
+            .bind(prompt)
+            .bind(updated_by_i64)
+            .execute(db.pool())
+            .await?;
+        }
+    }
+
+    invalidate_llm_prompt(db.cache(), guild_id, channel_id).await?;
+
+    Ok(())
+}
+
+/// Delete the prompt for this scope. Returns `true` if one existed.
+pub async fn reset_llm_system_prompt(
+    db: &Database,
+    guild_id: u64,
+    channel_id: Option<u64>,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = channel_id
+        .map(i64::try_from)
+        .transpose()
+        .context("channel_id out of i64 range")?;
+
+    let result = sqlx::query(
+        "DELETE FROM llm_system_prompts
+         WHERE guild_id = $1 AND channel_id IS NOT DISTINCT FROM $2",
+    )
+    .bind(guild_id_i64)
+    .bind(channel_id_i64)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_llm_prompt(db.cache(), guild_id, channel_id).await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,248 @@
+use anyhow::Context as _;
+
+use crate::cache::{CONFIG_CACHE_TTL, ai_quota_key, invalidate_ai_config};
+use crate::database::Database;
+use crate::impls::warnings::now_unix_secs;
+use crate::model::llm_usage::{LlmQuota, LlmQuotaScope, LlmUsageTotals, LlmUserUsage};
+
+pub struct NewLlmUsage<'a> {
+    pub guild_id: u64,
+    pub channel_id: u64,
+    pub user_id: u64,
+    /// What the request was for, e.g. `reply` or `summarize`.
+    pub kind: &'a str,
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+    pub latency_ms: u32,
+    pub succeeded: bool,
+}
+
+pub async fn record_llm_usage(db: &Database, usage: NewLlmUsage<'_>) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(usage.guild_id).context("guild_id out of i64 range")?;
+    let channel_id_i64 = i64::try_from(usage.channel_id).context("channel_id out of i64 range")?;
+    let user_id_i64 = i64::try_from(usage.user_id).context("user_id out of i64 range")?;
+    let prompt_tokens_i32 =
+        i32::try_from(usage.prompt_tokens).context("prompt_tokens out of i32 range")?;
+    let completion_tokens_i32 =
+        i32::try_from(usage.completion_tokens).context("completion_tokens out of i32 range")?;
+    let latency_ms_i32 = i32::try_from(usage.latency_ms).context("latency_ms out of i32 range")?;
+    let now = i64::try_from(now_unix_secs()).context("now out of i64 range")?;
+
+    sqlx::query(
+        "INSERT INTO llm_usage
+            (guild_id, channel_id, user_id, kind, prompt_tokens, completion_tokens,
+             latency_ms, succeeded, created_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+    )
+    .bind(guild_id_i64)
+    .bind(channel_id_i64)
+    .bind(user_id_i64)
+    .bind(usage.kind)
+    .bind(prompt_tokens_i32)
+    .bind(completion_tokens_i32)
+    .bind(latency_ms_i32)
+    .bind(usage.succeeded)
+    .bind(now)
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_llm_quota(db: &Database, guild_id: u64) -> anyhow::Result<LlmQuota> {
+    let cache_key = ai_quota_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(&cache_key, CONFIG_CACHE_TTL, || async {
+            let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+            let row = sqlx::query_as::<_, (Option<i64>, Option<i64>)>(
+                "SELECT daily_user_token_quota, daily_guild_token_quota
+                 FROM guild_ai_config WHERE guild_id = $1",
+            )
+            .bind(guild_id_i64)
+            .fetch_optional(db.pool())
+            .await?;
+
+            let Some((user_quota, guild_quota)) = row else {
+                return Ok(LlmQuota::default());
+            };
+
+            Ok(LlmQuota {
+                daily_user_tokens: user_quota
+                    .map(u64::try_from)
+                    .transpose()
+                    .context("daily_user_token_quota out of u64 range")?,
+                daily_guild_tokens: guild_quota
+                    .map(u64::try_from)
+                    .transpose()
+                    .context("daily_guild_token_quota out of u64 range")?,
+            })
+        })
+        .await
+}
+
+/// Set or clear (`None`) one of the guild's daily token quotas.
+pub async fn set_llm_quota(
+    db: &Database,
+    guild_id: u64,
+    scope: LlmQuotaScope,
+    daily_tokens: Option<u64>,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let daily_tokens_i64 = daily_tokens
+        .map(i64::try_from)
+        .transpose()
+        .context("daily_tokens out of i64 range")?;
+
+    let query = match scope {
+        LlmQuotaScope::User => {
+            "INSERT INTO guild_ai_config (guild_id, daily_user_token_quota)
+             VALUES ($1, $2)
+             ON CONFLICT (guild_id) DO UPDATE
+             SET daily_user_token_quota = EXCLUDED.daily_user_token_quota"
+        }
+        LlmQuotaScope::Guild => {
+            "INSERT INTO guild_ai_config (guild_id, daily_guild_token_quota)
+             VALUES ($1, $2)
+             ON CONFLICT (guild_id) DO UPDATE
+             SET daily_guild_token_quota = EXCLUDED.daily_guild_token_quota"
+        }
+    };
+
+    sqlx::query(query)
+        .bind(guild_id_i64)
+        .bind(daily_tokens_i64)
+        .execute(db.pool())
+        .await?;
+
+    invalidate_ai_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Tokens used in the guild since `since`, by one user if `user_id` is set.
+pub async fn llm_tokens_used_since(
+    db: &Database,
+    guild_id: u64,
+    user_id: Option<u64>,
+    since: u64,
+) -> anyhow::Result<u64> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let user_id_i64 = user_id
+        .map(i64::try_from)
+        .transpose()
+        .context("user_id out of i64 range")?;
+    let since_i64 = i64::try_from(since).context("since out of i64 range")?;
+
+    let used: i64 = sqlx::query_scalar(
+        "SELECT COALESCE(SUM(prompt_tokens + completion_tokens), 0)::BIGINT
+         FROM llm_usage
+         WHERE guild_id = $1
+           AND ($2::BIGINT IS NULL OR user_id = $2)
+           AND created_at >= $3",
+    )
+    .bind(guild_id_i64)
+    .bind(user_id_i64)
+    .bind(since_i64)
+    .fetch_one(db.pool())
+    .await?;
+
+    u64::try_from(used).context("used tokens out of u64 range")
+}
+
+/// The first quota the user or guild has used up since `since`, checking the
+/// user's quota first.
+pub async fn exceeded_llm_quota(
+    db: &Database,
+    guild_id: u64,
+    user_id: u64,
+    since: u64,
+) -> anyhow::Result<Option<LlmQuotaScope>> {
+    let quota = get_llm_quota(db, guild_id).await?;
+
+    if let Some(limit) = quota.daily_user_tokens
+        && llm_tokens_used_since(db, guild_id, Some(user_id), since).await? >= limit
+    {
+        return Ok(Some(LlmQuotaScope::User));
+    }
+
+    if let Some(limit) = quota.daily_guild_tokens
+        && llm_tokens_used_since(db, guild_id, None, since).await? >= limit
+    {
+        return Ok(Some(LlmQuotaScope::Guild));
+    }
+
+    Ok(None)
+}
+
+pub async fn llm_usage_totals(
+    db: &Database,
+    guild_id: u64,
+    since: u64,
+) -> anyhow::Result<LlmUsageTotals> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let since_i64 = i64::try_from(since).context("since out of i64 range")?;
+
+    let (requests, failed, prompt_tokens, completion_tokens, average_latency_ms) =
+        sqlx::query_as::<_, (i64, i64, i64, i64, i64)>(
+            "SELECT COUNT(*),
+                    COUNT(*) FILTER (WHERE NOT succeeded),
+                    COALESCE(SUM(prompt_tokens), 0)::BIGINT,
+                    COALESCE(SUM(completion_tokens), 0)::BIGINT,
+                    COALESCE(AVG(latency_ms), 0)::BIGINT
+             FROM llm_usage
+             WHERE guild_id = $1 AND created_at >= $2",
+        )
+        .bind(guild_id_i64)
+        .bind(since_i64)
+        .fetch_one(db.pool())
+        .await?;
+
+    Ok(LlmUsageTotals {
+        requests: u64::try_from(requests).context("requests out of u64 range")?,
+        failed_requests: u64::try_from(failed).context("failed_requests out of u64 range")?,
+        prompt_tokens: u64::try_from(prompt_tokens).context("prompt_tokens out of u64 range")?,
+        completion_tokens: u64::try_from(completion_tokens)
+            .context("completion_tokens out of u64 range")?,
+        average_latency_ms: u64::try_from(average_latency_ms)
+            .context("average_latency_ms out of u64 range")?,
+    })
+}
+
+/// Members with the most tokens used since `since`, highest first.
+pub async fn list_top_llm_users(
+    db: &Database,
+    guild_id: u64,
+    since: u64,
+    limit: u32,
+) -> anyhow::Result<Vec<LlmUserUsage>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let since_i64 = i64::try_from(since).context("since out of i64 range")?;
+
+    let rows = sqlx::query_as::<_, (i64, i64, i64)>(
+        "SELECT user_id,
+                COUNT(*),
+                COALESCE(SUM(prompt_tokens + completion_tokens), 0)::BIGINT AS total_tokens
+         FROM llm_usage
+         WHERE guild_id = $1 AND created_at >= $2
+         GROUP BY user_id
+         ORDER BY total_tokens DESC, user_id
+         LIMIT $3",
+    )
+    .bind(guild_id_i64)
+    .bind(since_i64)
+    .bind(i64::from(limit))
+    .fetch_all(db.pool())
+    .await?;
+
+    rows.into_iter()
+        .map(|(user_id, requests, total_tokens)| {
+            Ok(LlmUserUsage {
+                user_id: u64::try_from(user_id).context("user_id row out of u64 range")?,
+                requests: u64::try_from(requests).context("requests row out of u64 range")?,
+                total_tokens: u64::try_from(total_tokens)
+                    .context("total_tokens row out of u64 range")?,
+            })
+        })
+        .collect()
+}
@@ -1,8 +1,17 @@
 pub mod ai_config;
+pub mod ai_moderation;
+pub mod anti_nuke;
+pub mod attachment_filter;
 pub mod cases;
+pub mod command_acl;
+pub mod disabled_commands;
 pub mod escalation;
+pub mod guild_prefix;
 pub mod leveling;
 pub mod llm_chat;
+pub mod llm_prompt;
+pub mod llm_usage;
+pub mod moderator_safety;
 pub mod modlog_config;
 pub mod notes;
 pub mod rate_limit;
@@ -0,0 +1,156 @@
+use anyhow::Context as _;
+
+use crate::cache::{
+    CONFIG_CACHE_TTL, invalidate_moderator_safety_config, moderator_safety_config_key,
+};
+use crate::database::Database;
+use crate::model::moderator_safety::{DESTRUCTIVE_ACTIONS, ModeratorPause, ModeratorSafetyConfig};
+
+/// The guild's moderator safety settings, or the defaults if the guild never
+/// configured them.
+pub async fn get_moderator_safety_config(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<ModeratorSafetyConfig> {
+    let cache_key = moderator_safety_config_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(&cache_key, CONFIG_CACHE_TTL, || async {
+            let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+            let row = sqlx::query_scalar::<_, i32>(
+                "SELECT destructive_limit_per_hour
+                 FROM moderator_safety_config WHERE guild_id = $1",
+            )
+            .bind(guild_id_i64)
+            .fetch_optional(db.pool())
+            .await?;
+
+            let Some(limit) = row else {
+                return Ok(ModeratorSafetyConfig::default_for(guild_id));
+            };
+
+            Ok(ModeratorSafetyConfig {
+                guild_id,
+                destructive_limit_per_hour: u32::try_from(limit)
+                    .context("destructive_limit_per_hour out of u32 range")?,
+            })
+        })
+        .await
+}
+
+/// `0` disables the limit.
+pub async fn set_destructive_limit(db: &Database, guild_id: u64, limit: u32) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let limit_i32 = i32::try_from(limit).context("limit out of i32 range")?;
+
+    sqlx::query(
+        "INSERT INTO moderator_safety_config (guild_id, destructive_limit_per_hour)
+         VALUES ($1, $2)
+         ON CONFLICT (guild_id)
+         DO UPDATE SET destructive_limit_per_hour = EXCLUDED.destructive_limit_per_hour",
+    )
+    .bind(guild_id_i64)
+    .bind(limit_i32)
+    .execute(db.pool())
+    .await?;
+
+    invalidate_moderator_safety_config(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Destructive cases a moderator opened at or after `since`.
+pub async fn count_destructive_actions_since(
+    db: &Database,
+    guild_id: u64,
+    moderator_user_id: u64,
+    since: i64,
+) -> anyhow::Result<i64> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let moderator_i64 =
+        i64::try_from(moderator_user_id).context("moderator_user_id out of i64 range")?;
+
+    let count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM mod_cases
+         WHERE guild_id = $1 AND moderator_user_id = $2
+           AND created_at >= $3 AND action = ANY($4)",
+    )
+    .bind(guild_id_i64)
+    .bind(moderator_i64)
+    .bind(since)
+    .bind(DESTRUCTIVE_ACTIONS)
+    .fetch_one(db.pool())
+    .await?;
+
+    Ok(count)
+}
+
+/// The moderator's most recent pause, whether or not it is still running.
+pub async fn get_moderator_pause(
+    db: &Database,
+    guild_id: u64,
+    user_id: u64,
+) -> anyhow::Result<Option<ModeratorPause>> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let user_id_i64 = i64::try_from(user_id).context("user_id out of i64 range")?;
+
+    let pause = sqlx::query_as::<_, ModeratorPause>(
+        "SELECT paused_at, paused_until FROM moderator_pauses
+         WHERE guild_id = $1 AND user_id = $2",
+    )
+    .bind(guild_id_i64)
+    .bind(user_id_i64)
+    .fetch_optional(db.pool())
+    .await?;
+
+    Ok(pause)
+}
+
+pub async fn pause_moderator(
+    db: &Database,
+    guild_id: u64,
+    user_id: u64,
+    now: i64,
+    paused_until: i64,
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let user_id_i64 = i64::try_from(user_id).context("user_id out of i64 range")?;
+
+    sqlx::query(
+        "INSERT INTO moderator_pauses (guild_id, user_id, paused_at, paused_until)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (guild_id, user_id)
+         DO UPDATE SET paused_at = EXCLUDED.paused_at, paused_until = EXCLUDED.paused_until",
+    )
+    .bind(guild_id_i64)
+    .bind(user_id_i64)
+    .bind(now)
+    .bind(paused_until)
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+/// End a running pause at `now`. Returns whether the moderator was paused.
+pub async fn clear_moderator_pause(
+    db: &Database,
+    guild_id: u64,
+    user_id: u64,
+    now: i64,
+) -> anyhow::Result<bool> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+    let user_id_i64 = i64::try_from(user_id).context("user_id out of i64 range")?;
+
+    let result = sqlx::query(
+        "UPDATE moderator_pauses SET paused_until = $3
+         WHERE guild_id = $1 AND user_id = $2 AND paused_until > $3",
+    )
+    .bind(guild_id_i64)
+    .bind(user_id_i64)
+    .bind(now)
+    .execute(db.pool())
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -1,4 +1,4 @@
-use crate::cache::llm_mention_rate_limit_key;
+use crate::cache::{RateLimiter, llm_mention_rate_limit_key};
 use crate::database::Database;
 
 pub async fn llm_mention_within_limit(
@@ -9,14 +9,10 @@ pub async fn llm_mention_within_limit(
 ) -> anyhow::Result<bool> {
     let cache = db.cache();
     let key = llm_mention_rate_limit_key(cache, guild_id, channel_id, user_id);
-    let count = cache
-        .increment_with_window(&key, cache.llm_rate_limit_window())
-        .await?;
-    let max_hits = cache.llm_rate_limit_max_hits();
+    let limiter = RateLimiter::SlidingWindow {
+        window: cache.llm_rate_limit_window(),
+        max_hits: cache.llm_rate_limit_max_hits(),
+    };
 
-    if count > max_hits {
-        cache.record_rate_limit_block();
-    }
-
-    Ok(count <= max_hits)
+    Ok(limiter.check(cache, &key).await?.allowed)
 }
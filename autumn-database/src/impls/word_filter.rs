@@ -1,7 +1,8 @@
 use anyhow::Context as _;
 
 use crate::cache::{
-    CONFIG_CACHE_TTL, WORD_LIST_CACHE_TTL, invalidate_word_filter, word_filter_config_key,
+    CONFIG_CACHE_TTL, WORD_LIST_CACHE_TTL, invalidate_word_filter,
+    invalidate_word_filter_automod_rules, word_filter_automod_rules_key, word_filter_config_key,
     word_filter_words_key,
 };
 use crate::database::Database;
@@ -268,6 +269,85 @@ pub async fn clear_preset_words(db: &Database, guild_id: u64) -> anyhow::Result<
     Ok(result.rows_affected())
 }
 
+// ---------------------------------------------------------------------------
+// Discord AutoMod rule tracking
+// ---------------------------------------------------------------------------
+
+/// List the AutoMod rule IDs created for a guild's word list, ordered by chunk.
+pub async fn list_word_filter_automod_rules(
+    db: &Database,
+    guild_id: u64,
+) -> anyhow::Result<Vec<u64>> {
+    let cache_key = word_filter_automod_rules_key(db.cache(), guild_id);
+    db.cache()
+        .get_or_load_json(&cache_key, CONFIG_CACHE_TTL, || async {
+            let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+            let rule_ids: Vec<i64> = sqlx::query_scalar(
+                "SELECT rule_id FROM word_filter_automod_rules
+                 WHERE guild_id = $1
+                 ORDER BY chunk_index ASC",
+            )
+            .bind(guild_id_i64)
+            .fetch_all(db.pool())
+            .await?;
+
+            rule_ids
+                .into_iter()
+                .map(|id| u64::try_from(id).context("rule_id out of u64 range"))
+                .collect()
+        })
+        .await
+}
+
+/// Replace the tracked AutoMod rule IDs for a guild. `rule_ids[i]` holds the
+/// rule that carries chunk `i` of the word list.
+pub async fn set_word_filter_automod_rules(
+    db: &Database,
+    guild_id: u64,
+    rule_ids: &[u64],
+) -> anyhow::Result<()> {
+    let guild_id_i64 = i64::try_from(guild_id).context("guild_id out of i64 range")?;
+
+    let mut tx = db.pool().begin().await?;
+
+    sqlx::query("DELETE FROM word_filter_automod_rules WHERE guild_id = $1")
+        .bind(guild_id_i64)
+        .execute(&mut *tx)
+        .await?;
+
+    for (index, rule_id) in rule_ids.iter().enumerate() {
+        let rule_id_i64 = i64::try_from(*rule_id).context("rule_id out of i64 range")?;
+        let index_i32 = i32::try_from(index).context("chunk_index out of i32 range")?;
+
+        sqlx::query(
+            "INSERT INTO word_filter_automod_rules (rule_id, guild_id, chunk_index)
+             VALUES ($1, $2, $3)",
+        )
+        .bind(rule_id_i64)
+        .bind(guild_id_i64)
+        .bind(index_i32)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    invalidate_word_filter_automod_rules(db.cache(), guild_id).await?;
+
+    Ok(())
+}
+
+/// Whether the given AutoMod rule was created by `!wordfilter sync-automod`.
+pub async fn is_word_filter_automod_rule(
+    db: &Database,
+    guild_id: u64,
+    rule_id: u64,
+) -> anyhow::Result<bool> {
+    let rule_ids = list_word_filter_automod_rules(db, guild_id).await?;
+    Ok(rule_ids.contains(&rule_id))
+}
+
 /// Check whether the word filter is enabled for a guild and return the config.
 pub async fn get_word_filter_if_enabled(
     db: &Database,
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_AI_MODERATION_THRESHOLD: f32 = 0.85;
+pub const DEFAULT_AI_MODERATION_SAMPLE_PERCENT: u8 = 100;
+pub const DEFAULT_AI_MODERATION_MAX_PER_MINUTE: u32 = 30;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AiModerationConfig {
+    pub guild_id: u64,
+    pub enabled: bool,
+    /// Minimum confidence (0.0-1.0) for a message to be flagged.
+    pub threshold: f32,
+    /// Percentage of messages that are classified.
+    pub sample_percent: u8,
+    /// Most classifications per minute for the guild.
+    pub max_per_minute: u32,
+}
+
+impl AiModerationConfig {
+    pub fn default_for(guild_id: u64) -> Self {
+        Self {
+            guild_id,
+            enabled: false,
+            threshold: DEFAULT_AI_MODERATION_THRESHOLD,
+            sample_percent: DEFAULT_AI_MODERATION_SAMPLE_PERCENT,
+            max_per_minute: DEFAULT_AI_MODERATION_MAX_PER_MINUTE,
+        }
+    }
+}
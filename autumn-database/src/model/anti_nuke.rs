@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_ANTI_NUKE_MAX_ACTIONS: u32 = 5;
+pub const DEFAULT_ANTI_NUKE_WINDOW_SECONDS: u32 = 60;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AntiNukeConfig {
+    pub guild_id: u64,
+    pub enabled: bool,
+    /// Most destructive audit-log actions one actor may make per window.
+    pub max_actions: u32,
+    pub window_seconds: u32,
+    /// Actors that are never acted against.
+    pub trusted_user_ids: Vec<u64>,
+}
+
+impl AntiNukeConfig {
+    pub fn default_for(guild_id: u64) -> Self {
+        Self {
+            guild_id,
+            enabled: false,
+            max_actions: DEFAULT_ANTI_NUKE_MAX_ACTIONS,
+            window_seconds: DEFAULT_ANTI_NUKE_WINDOW_SECONDS,
+            trusted_user_ids: Vec::new(),
+        }
+    }
+
+    pub fn is_trusted(&self, user_id: u64) -> bool {
+        self.trusted_user_ids.contains(&user_id)
+    }
+}
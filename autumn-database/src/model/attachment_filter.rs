@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AttachmentFilterConfig {
+    pub guild_id: u64,
+    pub enabled: bool,
+    pub action: String,
+    pub extension_mode: String,
+    pub max_size_bytes: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct AttachmentChannelRule {
+    pub channel_id: u64,
+    pub allow_images: bool,
+    pub allow_videos: bool,
+}
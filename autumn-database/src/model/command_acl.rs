@@ -0,0 +1,154 @@
+use serde::{Deserialize, Serialize};
+
+/// Who or where a command rule applies to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommandAclTarget {
+    Role(u64),
+    User(u64),
+    Channel(u64),
+}
+
+impl CommandAclTarget {
+    pub fn from_parts(kind: &str, id: u64) -> Option<Self> {
+        match kind {
+            "role" => Some(Self::Role(id)),
+            "user" => Some(Self::User(id)),
+            "channel" => Some(Self::Channel(id)),
+            _ => None,
+        }
+    }
+
+    pub fn kind(self) -> &'static str {
+        match self {
+            Self::Role(_) => "role",
+            Self::User(_) => "user",
+            Self::Channel(_) => "channel",
+        }
+    }
+
+    pub fn id(self) -> u64 {
+        match self {
+            Self::Role(id) | Self::User(id) | Self::Channel(id) => id,
+        }
+    }
+
+    pub fn mention(self) -> String {
+        match self {
+            Self::Role(id) => format!("<@&{id}>"),
+            Self::User(id) => format!("<@{id}>"),
+            Self::Channel(id) => format!("<#{id}>"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CommandAclRule {
+    pub command_name: String,
+    pub target: CommandAclTarget,
+    pub allow: bool,
+}
+
+/// Resolve a guild's rules for one invocation of `command_name`.
+///
+/// `Some(false)` when a rule blocks it: a channel deny, a channel allowlist
+/// that doesn't include this channel, a user deny, or any role deny. Otherwise
+/// `Some(true)` when a user or role rule allows it, and `None` when no rule
+/// applies and the command's own permission requirement decides. A user rule
+/// beats role rules.
+pub fn evaluate_command_acl(
+    rules: &[CommandAclRule],
+    command_name: &str,
+    channel_id: u64,
+    user_id: u64,
+    role_ids: &[u64],
+) -> Option<bool> {
+    let rules = rules
+        .iter()
+        .filter(|rule| rule.command_name == command_name);
+
+    let mut channel_allowlist = false;
+    let mut allowed_here = false;
+    let mut user_rule = None;
+    let mut role_allow = false;
+    let mut role_deny = false;
+    for rule in rules {
+        match rule.target {
+            CommandAclTarget::Channel(id) if rule.allow => {
+                channel_allowlist = true;
+                allowed_here |= id == channel_id;
+            }
+            CommandAclTarget::Channel(id) if id == channel_id => return Some(false),
+            CommandAclTarget::User(id) if id == user_id => user_rule = Some(rule.allow),
+            CommandAclTarget::Role(id) if role_ids.contains(&id) => {
+                role_allow |= rule.allow;
+                role_deny |= !rule.allow;
+            }
+            _ => {}
+        }
+    }
+
+    if channel_allowlist && !allowed_here {
+        return Some(false);
+    }
+
+    if user_rule.is_some() {
+        return user_rule;
+    }
+
+    if role_deny {
+        Some(false)
+    } else if role_allow {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(target: CommandAclTarget, allow: bool) -> CommandAclRule {
+        CommandAclRule {
+            command_name: "warn".to_owned(),
+            target,
+            allow,
+        }
+    }
+
+    #[test]
+    fn user_rules_beat_role_rules_and_channels_restrict() {
+        let rules = vec![
+            rule(CommandAclTarget::Role(10), true),
+            rule(CommandAclTarget::Role(11), false),
+            rule(CommandAclTarget::User(2), true),
+            rule(CommandAclTarget::Channel(100), false),
+        ];
+
+        assert_eq!(
+            evaluate_command_acl(&rules, "warn", 1, 1, &[10]),
+            Some(true)
+        );
+        assert_eq!(
+            evaluate_command_acl(&rules, "warn", 1, 1, &[10, 11]),
+            Some(false)
+        );
+        assert_eq!(
+            evaluate_command_acl(&rules, "warn", 1, 2, &[11]),
+            Some(true)
+        );
+        assert_eq!(
+            evaluate_command_acl(&rules, "warn", 100, 2, &[]),
+            Some(false)
+        );
+        assert_eq!(evaluate_command_acl(&rules, "warn", 1, 1, &[]), None);
+        assert_eq!(evaluate_command_acl(&rules, "kick", 1, 1, &[10]), None);
+
+        let allowlist = vec![rule(CommandAclTarget::Channel(100), true)];
+        assert_eq!(evaluate_command_acl(&allowlist, "warn", 100, 1, &[]), None);
+        assert_eq!(
+            evaluate_command_acl(&allowlist, "warn", 1, 1, &[]),
+            Some(false)
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// Name under which replies to bot mentions (LLM chat) can be disabled like a
+/// command.
+pub const LLM_CHAT_COMMAND: &str = "chat";
+
+/// A command turned off in a guild, everywhere when `channel_id` is `None`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisabledCommand {
+    pub command_name: String,
+    pub channel_id: Option<u64>,
+}
+
+/// Whether `command_name` is disabled guild-wide or in `channel_id`.
+pub fn is_command_disabled(
+    disabled: &[DisabledCommand],
+    command_name: &str,
+    channel_id: u64,
+) -> bool {
+    disabled.iter().any(|entry| {
+        entry.command_name == command_name && entry.channel_id.is_none_or(|id| id == channel_id)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_guild_wide_or_in_one_channel() {
+        let disabled = vec![
+            DisabledCommand {
+                command_name: "universe".to_owned(),
+                channel_id: None,
+            },
+            DisabledCommand {
+                command_name: "chat".to_owned(),
+                channel_id: Some(100),
+            },
+        ];
+
+        assert!(is_command_disabled(&disabled, "universe", 1));
+        assert!(is_command_disabled(&disabled, "chat", 100));
+        assert!(!is_command_disabled(&disabled, "chat", 1));
+        assert!(!is_command_disabled(&disabled, "ping", 100));
+    }
+}
@@ -1,8 +1,21 @@
+use serde::{Deserialize, Serialize};
+
 #[derive(Clone, Debug)]
 pub struct LlmChatEntry {
+    pub id: u64,
     pub user_id: u64,
     pub display_name: Option<String>,
     pub role: String,
     pub content: String,
     pub created_at: u64,
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LlmChannelMemory {
+    pub guild_id: u64,
+    pub channel_id: u64,
+    pub summary: String,
+    /// Highest `llm_chat_history.id` folded into `summary`.
+    pub summarized_through_id: u64,
+    pub updated_at: u64,
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LlmSystemPrompt {
+    pub guild_id: u64,
+    /// `None` for the guild-wide prompt.
+    pub channel_id: Option<u64>,
+    pub prompt: String,
+    pub updated_by: u64,
+    pub updated_at: u64,
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// Daily token quotas for a guild. `None` means unlimited.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct LlmQuota {
+    /// Tokens each member may use per day.
+    pub daily_user_tokens: Option<u64>,
+    /// Tokens the whole guild may use per day.
+    pub daily_guild_tokens: Option<u64>,
+}
+
+/// Which quota a request ran into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LlmQuotaScope {
+    User,
+    Guild,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LlmUsageTotals {
+    pub requests: u64,
+    pub failed_requests: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub average_latency_ms: u64,
+}
+
+impl LlmUsageTotals {
+    pub fn total_tokens(&self) -> u64 {
+        self.prompt_tokens + self.completion_tokens
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LlmUserUsage {
+    pub user_id: u64,
+    pub requests: u64,
+    pub total_tokens: u64,
+}
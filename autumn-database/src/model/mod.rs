@@ -1,7 +1,15 @@
+pub mod ai_moderation;
+pub mod anti_nuke;
+pub mod attachment_filter;
 pub mod cases;
+pub mod command_acl;
+pub mod disabled_commands;
 pub mod escalation;
 pub mod leveling;
 pub mod llm_chat;
+pub mod llm_prompt;
+pub mod llm_usage;
+pub mod moderator_safety;
 pub mod notes;
 pub mod warnings;
 pub mod word_filter;
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+pub const DEFAULT_DESTRUCTIVE_LIMIT_PER_HOUR: u32 = 10;
+
+/// Case actions that count towards the destructive-action limit.
+pub const DESTRUCTIVE_ACTIONS: &[&str] = &["ban", "kick", "terminate"];
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ModeratorSafetyConfig {
+    pub guild_id: u64,
+    /// Most destructive actions per moderator per hour; 0 disables the limit.
+    pub destructive_limit_per_hour: u32,
+}
+
+impl ModeratorSafetyConfig {
+    pub fn default_for(guild_id: u64) -> Self {
+        Self {
+            guild_id,
+            destructive_limit_per_hour: DEFAULT_DESTRUCTIVE_LIMIT_PER_HOUR,
+        }
+    }
+}
+
+/// A moderator's most recent pause. Lifting a pause early ends it at that
+/// moment rather than deleting it, so actions taken before the pause stop
+/// counting towards the limit.
+#[derive(Clone, Copy, Debug, FromRow)]
+pub struct ModeratorPause {
+    pub paused_at: i64,
+    pub paused_until: i64,
+}
+
+impl ModeratorPause {
+    pub fn is_active(&self, now: i64) -> bool {
+        self.paused_until > now
+    }
+}
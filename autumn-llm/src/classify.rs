@@ -0,0 +1,129 @@
+use serde::Deserialize;
+
+use crate::prompt::CLASSIFICATION_PROMPT;
+use crate::provider::ChatMessage;
+
+/// Longest message excerpt sent for classification.
+const CLASSIFY_MAX_CHARS: usize = 1500;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToxicityCategory {
+    Harassment,
+    Hate,
+    SelfHarm,
+    Spam,
+}
+
+impl ToxicityCategory {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw
+            .trim()
+            .to_ascii_lowercase()
+            .replace(['-', ' '], "_")
+            .as_str()
+        {
+            "harassment" => Some(Self::Harassment),
+            "hate" => Some(Self::Hate),
+            "self_harm" => Some(Self::SelfHarm),
+            "spam" => Some(Self::Spam),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Harassment => "harassment",
+            Self::Hate => "hate",
+            Self::SelfHarm => "self_harm",
+            Self::Spam => "spam",
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Harassment => "Harassment",
+            Self::Hate => "Hate",
+            Self::SelfHarm => "Self-harm",
+            Self::Spam => "Spam",
+        }
+    }
+}
+
+/// The model's verdict on one message. `category` is `None` for messages
+/// that fit no category.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Classification {
+    pub category: Option<ToxicityCategory>,
+    /// 0.0-1.0.
+    pub confidence: f32,
+    pub reason: String,
+}
+
+impl Classification {
+    /// Whether the message should go to moderators for review.
+    pub fn is_flagged(&self, threshold: f32) -> bool {
+        self.category.is_some() && self.confidence >= threshold
+    }
+}
+
+#[derive(Deserialize)]
+struct RawClassification {
+    #[serde(default)]
+    category: String,
+    #[serde(default)]
+    confidence: f32,
+    #[serde(default)]
+    reason: String,
+}
+
+pub(crate) fn build_classification_messages(content: &str) -> Vec<ChatMessage> {
+    let excerpt: String = content.chars().take(CLASSIFY_MAX_CHARS).collect();
+    vec![
+        ChatMessage::system(CLASSIFICATION_PROMPT),
+        ChatMessage::user(format!("Message:\n\"\"\"\n{excerpt}\n\"\"\"")),
+    ]
+}
+
+/// Parse the model's JSON verdict, tolerating prose or code fences around it.
+pub(crate) fn parse_classification(response: &str) -> anyhow::Result<Classification> {
+    let start = response.find('{');
+    let end = response.rfind('}');
+    let json = match (start, end) {
+        (Some(start), Some(end)) if start < end => &response[start..=end],
+        _ => anyhow::bail!("classification response contained no JSON object"),
+    };
+
+    let raw: RawClassification = serde_json::from_str(json)?;
+    Ok(Classification {
+        category: ToxicityCategory::parse(&raw.category),
+        confidence: raw.confidence.clamp(0.0, 1.0),
+        reason: raw.reason.trim().to_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_verdicts_wrapped_in_prose() {
+        let parsed = parse_classification(
+            "Sure:\n```json\n{\"category\": \"Self-harm\", \"confidence\": 0.93, \"reason\": \"mentions hurting themselves\"}\n```",
+        )
+        .expect("valid verdict");
+
+        assert_eq!(parsed.category, Some(ToxicityCategory::SelfHarm));
+        assert!(parsed.is_flagged(0.9));
+        assert!(!parsed.is_flagged(0.95));
+    }
+
+    #[test]
+    fn none_category_is_never_flagged() {
+        let parsed = parse_classification(r#"{"category":"none","confidence":1.0,"reason":""}"#)
+            .expect("valid verdict");
+
+        assert_eq!(parsed.category, None);
+        assert!(!parsed.is_flagged(0.0));
+        assert!(parse_classification("no idea").is_err());
+    }
+}
@@ -1,68 +1,189 @@
 use std::env;
+use std::sync::Arc;
 
 use anyhow::Context as _;
-use autumn_database::{Database, impls::llm_chat::list_recent_llm_chat_messages};
-use ollama_rs::{
-    Ollama,
-    generation::chat::{ChatMessage, request::ChatMessageRequest},
-    models::ModelOptions,
+use autumn_database::{
+    Database,
+    impls::{llm_chat::upsert_llm_channel_memory, warnings::now_unix_secs},
 };
+use tracing::{debug, warn};
+
+use crate::classify::{Classification, build_classification_messages, parse_classification};
+use crate::context::{
+    ChannelContext, DEFAULT_CONTEXT_TOKEN_BUDGET, build_channel_messages, estimate_request_tokens,
+    estimate_tokens, format_history_content, load_channel_context, load_overflow,
+};
+use crate::prompt::{MEMORY_SUMMARY_PROMPT, TOOL_USE_PROMPT};
+use crate::provider::{
+    ChatDeltaSender, ChatImage, ChatMessage, ChatOptions, ChatProvider, ChatResponse, ChatTurn,
+    MockProvider, OllamaProvider, OpenAiCompatibleProvider, TokenUsage,
+};
+use crate::summary::{ModerationRecord, build_summary_messages};
+use crate::tools::ToolRegistry;
 
 #[derive(Clone, Debug)]
 pub struct LlmService {
-    client: Ollama,
-    model: String,
+    provider: Arc<dyn ChatProvider>,
+    context_token_budget: usize,
 }
 
-impl LlmService {
-    pub fn from_env_optional() -> anyhow::Result<Option<Self>> {
-        let enabled = env::var("OLLAMA_ENABLED")
-            .ok()
-            .map(|value| {
-                matches!(
-                    value.trim().to_ascii_lowercase().as_str(),
-                    "1" | "true" | "yes" | "on"
-                )
-            })
-            .unwrap_or(true);
+impl TokenUsage {
+    /// Estimated with the same heuristic as context budgeting, for backends
+    /// that don't report usage.
+    fn estimate(messages: &[ChatMessage], completion: &str) -> Self {
+        Self {
+            prompt_tokens: saturating_u32(estimate_request_tokens(messages)),
+            completion_tokens: saturating_u32(estimate_tokens(completion)),
+        }
+    }
 
-        if !enabled {
-            return Ok(None);
+    /// What the backend reported, or an estimate when it reported nothing.
+    fn reported_or_estimate(
+        reported: Option<Self>,
+        messages: &[ChatMessage],
+        completion: &str,
+    ) -> Self {
+        reported.unwrap_or_else(|| Self::estimate(messages, completion))
+    }
+}
+
+/// A generated reply and what it cost.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LlmReply {
+    pub content: String,
+    pub usage: TokenUsage,
+    /// The model called tools first, so the reply may carry data only the
+    /// invoker was allowed to see.
+    pub used_tools: bool,
+}
+
+impl LlmReply {
+    fn new(messages: &[ChatMessage], response: ChatResponse) -> Self {
+        Self {
+            usage: TokenUsage::reported_or_estimate(response.usage, messages, &response.content),
+            content: response.content.trim().to_owned(),
+            used_tools: false,
         }
+    }
+}
 
-        let host_raw = env::var("OLLAMA_HOST").ok();
-        let port_raw = env::var("OLLAMA_PORT").ok();
-        let model_raw = env::var("OLLAMA_MODEL").ok();
+/// Longest stored channel memory, in characters.
+const MEMORY_MAX_CHARS: usize = 2000;
+/// Rounds of tool calls allowed before the model must answer.
+const MAX_TOOL_ROUNDS: usize = 4;
 
-        let host = host_raw.as_deref().map(str::trim).unwrap_or_default();
-        let port = port_raw.as_deref().map(str::trim).unwrap_or_default();
-        let model = model_raw.as_deref().map(str::trim).unwrap_or_default();
+impl LlmService {
+    pub fn new(provider: impl ChatProvider + 'static) -> Self {
+        Self {
+            provider: Arc::new(provider),
+            context_token_budget: DEFAULT_CONTEXT_TOKEN_BUDGET,
+        }
+    }
 
-        if host.is_empty() && port.is_empty() && model.is_empty() {
-            return Ok(None);
+    pub fn with_context_token_budget(mut self, budget: usize) -> Self {
+        self.context_token_budget = budget;
+        self
+    }
+
+    pub fn context_token_budget(&self) -> usize {
+        self.context_token_budget
+    }
+
+    /// Whether the configured model accepts image attachments.
+    pub async fn supports_images(&self) -> bool {
+        self.provider.supports_images().await
+    }
+
+    /// Name of the configured backend (`ollama`, `openai`, or `mock`).
+    pub fn provider_name(&self) -> &'static str {
+        self.provider.name()
+    }
+
+    /// Build the service from env, or `None` when the selected provider has
+    /// no configuration. `LLM_PROVIDER` picks the backend and defaults to
+    /// `ollama`.
+    pub fn from_env_optional() -> anyhow::Result<Option<Self>> {
+        match provider_kind()?.as_str() {
+            "openai" => {
+                let base_url = env_trimmed("OPENAI_BASE_URL");
+                let model = env_trimmed("OPENAI_MODEL");
+                if base_url.is_none() && model.is_none() {
+                    return Ok(None);
+                }
+            }
+            "mock" => {}
+            _ => {
+                let enabled = env::var("OLLAMA_ENABLED")
+                    .ok()
+                    .map(|value| {
+                        matches!(
+                            value.trim().to_ascii_lowercase().as_str(),
+                            "1" | "true" | "yes" | "on"
+                        )
+                    })
+                    .unwrap_or(true);
+
+                if !enabled {
+                    return Ok(None);
+                }
+
+                let host = env_trimmed("OLLAMA_HOST");
+                let port = env_trimmed("OLLAMA_PORT");
+                let model = env_trimmed("OLLAMA_MODEL");
+
+                if host.is_none() && port.is_none() && model.is_none() {
+                    return Ok(None);
+                }
+            }
         }
 
         Ok(Some(Self::from_env()?))
     }
 
     pub fn from_env() -> anyhow::Result<Self> {
-        let host = env::var("OLLAMA_HOST")
-            .ok()
-            .map(|value| value.trim().to_owned())
-            .filter(|value| !value.is_empty())
-            .unwrap_or_else(|| "http://127.0.0.1".to_owned());
-        let port = env::var("OLLAMA_PORT")
-            .ok()
-            .and_then(|value| value.parse::<u16>().ok())
-            .unwrap_or(11434);
-        let model = env::var("OLLAMA_MODEL")
-            .ok()
-            .map(|value| value.trim().to_owned())
-            .filter(|value| !value.is_empty())
-            .unwrap_or_else(|| "gpt-oss:20b-cloud".to_owned());
-
-        let client = Ollama::new(host, port);
-        Ok(Self { client, model })
+        let service = Self::provider_from_env()?;
+
+        let budget = env_trimmed("LLM_CONTEXT_TOKEN_BUDGET")
+            .and_then(|value| value.parse::<usize>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(DEFAULT_CONTEXT_TOKEN_BUDGET);
+
+        Ok(service.with_context_token_budget(budget))
+    }
+
+    fn provider_from_env() -> anyhow::Result<Self> {
+        match provider_kind()?.as_str() {
+            "openai" => {
+                let base_url = env_trimmed("OPENAI_BASE_URL")
+                    .context("OPENAI_BASE_URL is required when LLM_PROVIDER=openai")?;
+                let model = env_trimmed("OPENAI_MODEL")
+                    .context("OPENAI_MODEL is required when LLM_PROVIDER=openai")?;
+                let api_key = env_trimmed("OPENAI_API_KEY");
+                let vision = env_trimmed("OPENAI_VISION").is_some_and(|value| {
+                    matches!(
+                        value.to_ascii_lowercase().as_str(),
+                        "1" | "true" | "yes" | "on"
+                    )
+                });
+
+                Ok(Self::new(
+                    OpenAiCompatibleProvider::new(base_url, api_key, model)?.with_vision(vision),
+                ))
+            }
+            "mock" => Ok(Self::new(MockProvider::echo())),
+            _ => {
+                let host =
+                    env_trimmed("OLLAMA_HOST").unwrap_or_else(|| "http://127.0.0.1".to_owned());
+                let port = env::var("OLLAMA_PORT")
+                    .ok()
+                    .and_then(|value| value.parse::<u16>().ok())
+                    .unwrap_or(11434);
+                let model =
+                    env_trimmed("OLLAMA_MODEL").unwrap_or_else(|| "gpt-oss:20b-cloud".to_owned());
+
+                Ok(Self::new(OllamaProvider::new(host, port, model)))
+            }
+        }
     }
 
     pub async fn generate_channel_reply(
@@ -73,55 +194,457 @@ impl LlmService {
         user_prompt: &str,
         author_display_name: &str,
     ) -> anyhow::Result<String> {
-        let history = list_recent_llm_chat_messages(db, guild_id, channel_id, 20).await?;
-
-        let mut messages = Vec::with_capacity(history.len() + 2);
-        messages.push(ChatMessage::system(crate::prompt::system_prompt()));
-
-        for item in history.into_iter().rev() {
-            let mapped = match item.role.as_str() {
-                "user" => ChatMessage::user(format_history_content(
-                    "user",
-                    item.display_name.as_deref(),
-                    &item.content,
-                )),
-                "assistant" => ChatMessage::assistant(item.content.clone()),
-                _ => continue,
+        let context = load_channel_context(
+            db,
+            guild_id,
+            channel_id,
+            user_prompt,
+            self.context_token_budget,
+        )
+        .await?;
+
+        self.generate_reply_with_context(&context, user_prompt, author_display_name, &[])
+            .await
+            .map(|reply| reply.content)
+    }
+
+    /// Generate a reply from an already-loaded channel context.
+    pub async fn generate_reply_with_context(
+        &self,
+        context: &ChannelContext,
+        user_prompt: &str,
+        author_display_name: &str,
+        images: &[ChatImage],
+    ) -> anyhow::Result<LlmReply> {
+        let messages = build_channel_messages(context, user_prompt, author_display_name, images);
+
+        let response = self
+            .provider
+            .chat(&messages, ChatOptions::default())
+            .await
+            .with_context(|| format!("{} chat request failed", self.provider.name()))?;
+
+        Ok(LlmReply::new(&messages, response))
+    }
+
+    /// Like [`LlmService::generate_channel_reply`], but send pieces of the
+    /// reply to `deltas` as they are generated. Returns the final, trimmed
+    /// reply once the stream ends.
+    pub async fn stream_channel_reply(
+        &self,
+        db: &Database,
+        guild_id: u64,
+        channel_id: u64,
+        user_prompt: &str,
+        author_display_name: &str,
+        deltas: ChatDeltaSender,
+    ) -> anyhow::Result<String> {
+        let context = load_channel_context(
+            db,
+            guild_id,
+            channel_id,
+            user_prompt,
+            self.context_token_budget,
+        )
+        .await?;
+
+        self.stream_reply_with_context(&context, user_prompt, author_display_name, &[], deltas)
+            .await
+            .map(|reply| reply.content)
+    }
+
+    pub async fn stream_reply_with_context(
+        &self,
+        context: &ChannelContext,
+        user_prompt: &str,
+        author_display_name: &str,
+        images: &[ChatImage],
+        deltas: ChatDeltaSender,
+    ) -> anyhow::Result<LlmReply> {
+        let messages = build_channel_messages(context, user_prompt, author_display_name, images);
+
+        let response = self
+            .provider
+            .chat_stream(&messages, ChatOptions::default(), deltas)
+            .await
+            .with_context(|| format!("{} chat stream failed", self.provider.name()))?;
+
+        Ok(LlmReply::new(&messages, response))
+    }
+
+    /// Generate a reply, letting the model call `tools` first. Tool results
+    /// are fed back until the model answers or [`MAX_TOOL_ROUNDS`] is used
+    /// up, after which it has to answer without tools.
+    pub async fn generate_reply_with_tools(
+        &self,
+        context: &ChannelContext,
+        user_prompt: &str,
+        author_display_name: &str,
+        images: &[ChatImage],
+        tools: &dyn ToolRegistry,
+    ) -> anyhow::Result<LlmReply> {
+        let mut messages =
+            build_channel_messages(context, user_prompt, author_display_name, images);
+        messages.insert(1, ChatMessage::system(TOOL_USE_PROMPT));
+
+        let specs = tools.specs();
+        // Every round resends the whole conversation, so usage adds up.
+        let mut usage = TokenUsage::default();
+        let mut used_tools = false;
+        for _ in 0..MAX_TOOL_ROUNDS {
+            let response = self
+                .provider
+                .chat_with_tools(&messages, &specs, ChatOptions::default())
+                .await
+                .with_context(|| format!("{} tool chat request failed", self.provider.name()))?;
+
+            let calls = match response.turn {
+                ChatTurn::Reply(content) => {
+                    let mut reply = LlmReply::new(
+                        &messages,
+                        ChatResponse {
+                            content,
+                            usage: response.usage,
+                        },
+                    );
+                    reply.usage += usage;
+                    reply.used_tools = used_tools;
+                    return Ok(reply);
+                }
+                ChatTurn::ToolCalls(calls) => calls,
             };
-            messages.push(mapped);
+            used_tools = true;
+
+            let call_message = ChatMessage::assistant_tool_calls(calls.clone());
+            usage += response.usage.unwrap_or_else(|| TokenUsage {
+                prompt_tokens: saturating_u32(estimate_request_tokens(&messages)),
+                completion_tokens: saturating_u32(estimate_request_tokens(std::slice::from_ref(
+                    &call_message,
+                ))),
+            });
+            messages.push(call_message);
+            for call in &calls {
+                let result = match tools.call(call).await {
+                    Ok(result) => result,
+                    Err(source) => {
+                        warn!(?source, tool = %call.name, "llm tool call failed");
+                        serde_json::json!({ "error": source.to_string() }).to_string()
+                    }
+                };
+                messages.push(ChatMessage::tool(call.id.clone(), result));
+            }
         }
 
-        let priority_prompt = format!(
-            "--- LATEST MESSAGE TO REPLY TO ---\n{}: {}",
-            author_display_name, user_prompt
-        );
-        messages.push(ChatMessage::user(priority_prompt));
+        let response = self
+            .provider
+            .chat(&messages, ChatOptions::default())
+            .await
+            .with_context(|| format!("{} chat request failed", self.provider.name()))?;
+
+        let mut reply = LlmReply::new(&messages, response);
+        reply.usage += usage;
+        reply.used_tools = used_tools;
+        Ok(reply)
+    }
+
+    /// Summarize a user's moderation record for moderators, citing case
+    /// labels. `user_label` names the user in the prompt.
+    pub async fn summarize_moderation_record(
+        &self,
+        record: &ModerationRecord,
+        user_label: &str,
+    ) -> anyhow::Result<LlmReply> {
+        let messages = build_summary_messages(record, user_label);
+        // Keep the summary close to the records.
+        let options = ChatOptions {
+            temperature: 0.2,
+            ..ChatOptions::default()
+        };
+
+        let response = self
+            .provider
+            .chat(&messages, options)
+            .await
+            .with_context(|| format!("{} summary request failed", self.provider.name()))?;
 
-        let request = ChatMessageRequest::new(self.model.clone(), messages).options(
-            ModelOptions::default()
-                .temperature(0.75)
-                .repeat_penalty(1.2),
+        Ok(LlmReply::new(&messages, response))
+    }
+
+    /// Classify one message for AI moderation. Returns what the request cost
+    /// alongside the classification.
+    pub async fn classify_message(
+        &self,
+        content: &str,
+    ) -> anyhow::Result<(Classification, TokenUsage)> {
+        let messages = build_classification_messages(content);
+        let options = ChatOptions {
+            temperature: 0.0,
+            ..ChatOptions::default()
+        };
+
+        let response = self
+            .provider
+            .chat(&messages, options)
+            .await
+            .with_context(|| format!("{} classification request failed", self.provider.name()))?;
+
+        let usage = TokenUsage::reported_or_estimate(response.usage, &messages, &response.content);
+        let classification = parse_classification(&response.content)
+            .context("failed to parse classification response")?;
+        Ok((classification, usage))
+    }
+
+    /// Fold history that no longer fits in the token budget into the
+    /// channel's stored memory. `prompt_channel_id` is the channel whose
+    /// system prompt replies use: the parent channel for a thread. Returns
+    /// what the summary request cost, or `None` when nothing needed folding.
+    pub async fn refresh_channel_memory(
+        &self,
+        db: &Database,
+        guild_id: u64,
+        channel_id: u64,
+        prompt_channel_id: u64,
+    ) -> anyhow::Result<Option<TokenUsage>> {
+        let read_at = now_unix_secs();
+        let (memory, overflow) = load_overflow(
+            db,
+            guild_id,
+            channel_id,
+            prompt_channel_id,
+            self.context_token_budget,
+        )
+        .await?;
+
+        let Some(through_id) = overflow.iter().map(|entry| entry.id).max() else {
+            return Ok(None);
+        };
+
+        let transcript = overflow
+            .iter()
+            .map(|entry| match entry.role.as_str() {
+                "assistant" => format!("Autumn: {}", entry.content),
+                role => format_history_content(role, entry.display_name.as_deref(), &entry.content),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let request = format!(
+            "Existing memory:\n{}\n\nNew messages:\n{}",
+            memory
+                .as_ref()
+                .map_or("(none)", |memory| memory.summary.as_str()),
+            transcript
         );
+        let messages = [
+            ChatMessage::system(MEMORY_SUMMARY_PROMPT),
+            ChatMessage::user(request),
+        ];
+
         let response = self
-            .client
-            .send_chat_messages(request)
+            .provider
+            .chat(&messages, ChatOptions::default())
             .await
-            .context("failed to get ollama chat response")?;
+            .with_context(|| format!("{} memory summary request failed", self.provider.name()))?;
+        let usage = TokenUsage::reported_or_estimate(response.usage, &messages, &response.content);
+        let summary: String = response
+            .content
+            .trim()
+            .chars()
+            .take(MEMORY_MAX_CHARS)
+            .collect();
 
-        Ok(response.message.content.trim().to_owned())
+        if summary.is_empty() {
+            return Ok(Some(usage));
+        }
+
+        upsert_llm_channel_memory(db, guild_id, channel_id, &summary, through_id, read_at).await?;
+        debug!(
+            guild_id,
+            channel_id,
+            folded = overflow.len(),
+            "updated llm channel memory"
+        );
+
+        Ok(Some(usage))
     }
 }
 
-fn format_history_content(role: &str, display_name: Option<&str>, content: &str) -> String {
-    let normalized_name = display_name
-        .map(str::trim)
-        .filter(|name| !name.is_empty())
-        .map(|name| name.replace('\n', " "))
-        .unwrap_or_else(|| "unknown".to_owned());
-
-    if role == "user" {
-        format!("{}: {}", normalized_name, content)
-    } else {
-        content.to_owned()
+fn provider_kind() -> anyhow::Result<String> {
+    let kind = env_trimmed("LLM_PROVIDER")
+        .map(|value| value.to_ascii_lowercase())
+        .unwrap_or_else(|| "ollama".to_owned());
+
+    match kind.as_str() {
+        "ollama" | "openai" | "mock" => Ok(kind),
+        other => anyhow::bail!("unknown LLM_PROVIDER `{other}` (expected ollama, openai, or mock)"),
+    }
+}
+
+fn saturating_u32(value: usize) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
+}
+
+fn env_trimmed(key: &str) -> Option<String> {
+    env::var(key)
+        .ok()
+        .map(|value| value.trim().to_owned())
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::{ChatRole, ToolCall, ToolSpec};
+    use crate::tools::ToolFuture;
+    use autumn_database::model::llm_chat::LlmChatEntry;
+
+    struct EchoTools;
+
+    impl ToolRegistry for EchoTools {
+        fn specs(&self) -> Vec<ToolSpec> {
+            vec![ToolSpec {
+                name: "echo".to_owned(),
+                description: "Echo the arguments.".to_owned(),
+                parameters: serde_json::json!({ "type": "object" }),
+            }]
+        }
+
+        fn call<'a>(&'a self, call: &'a ToolCall) -> ToolFuture<'a> {
+            Box::pin(async move { Ok(call.arguments.to_string()) })
+        }
+    }
+
+    fn entry(role: &str, name: &str, content: &str) -> LlmChatEntry {
+        LlmChatEntry {
+            id: 1,
+            user_id: 1,
+            display_name: Some(name.to_owned()),
+            role: role.to_owned(),
+            content: content.to_owned(),
+            created_at: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn reply_uses_provider_and_orders_history_oldest_first() {
+        let provider = MockProvider::with_reply("  hello there  ");
+        let service = LlmService::new(provider.clone());
+
+        // Newest first, as the database returns it.
+        let history = vec![
+            entry("assistant", "Autumn", "second"),
+            entry("user", "Mika", "first"),
+        ];
+
+        let context = ChannelContext {
+            system_prompt: "Be brief.".to_owned(),
+            memory: Some("Mika likes tea.".to_owned()),
+            history,
+        };
+
+        let reply = service
+            .generate_reply_with_context(&context, "what's up?", "Mika", &[])
+            .await
+            .expect("mock provider never fails");
+        assert_eq!(reply.content, "hello there");
+        assert_eq!(reply.usage.completion_tokens, 4);
+        assert!(reply.usage.prompt_tokens > 0);
+
+        let requests = provider.requests();
+        assert_eq!(requests.len(), 1);
+
+        let roles: Vec<ChatRole> = requests[0].iter().map(|message| message.role).collect();
+        assert_eq!(
+            roles,
+            [
+                ChatRole::System,
+                ChatRole::System,
+                ChatRole::User,
+                ChatRole::Assistant,
+                ChatRole::User
+            ]
+        );
+        assert_eq!(requests[0][0].content, "Be brief.");
+        assert!(requests[0][1].content.ends_with("Mika likes tea."));
+        assert_eq!(requests[0][2].content, "Mika: first");
+        assert!(requests[0][4].content.ends_with("Mika: what's up?"));
+    }
+
+    #[tokio::test]
+    async fn images_are_attached_to_the_latest_message() {
+        let provider = MockProvider::with_reply("a cat").with_vision();
+        let service = LlmService::new(provider.clone());
+        assert!(service.supports_images().await);
+        assert!(
+            !LlmService::new(MockProvider::echo())
+                .supports_images()
+                .await
+        );
+
+        let image = ChatImage::from_bytes("image/png", b"png");
+        service
+            .generate_reply_with_context(
+                &ChannelContext::default(),
+                "what is this?",
+                "Mika",
+                std::slice::from_ref(&image),
+            )
+            .await
+            .expect("mock provider never fails");
+
+        let request = &provider.requests()[0];
+        let (latest, earlier) = request.split_last().expect("non-empty request");
+        assert_eq!(latest.images, [image]);
+        assert!(earlier.iter().all(|message| message.images.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn streamed_reply_matches_concatenated_deltas() {
+        let service = LlmService::new(MockProvider::with_reply("one two three"));
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+        let reply = service
+            .stream_reply_with_context(&ChannelContext::default(), "count", "Mika", &[], tx)
+            .await
+            .expect("mock provider never fails");
+
+        let mut streamed = String::new();
+        while let Some(delta) = rx.recv().await {
+            streamed.push_str(&delta);
+        }
+
+        assert_eq!(reply.content, "one two three");
+        assert_eq!(streamed, reply.content);
+    }
+
+    #[tokio::test]
+    async fn tool_results_are_fed_back_before_the_reply() {
+        let call = ToolCall {
+            id: "call_0".to_owned(),
+            name: "echo".to_owned(),
+            arguments: serde_json::json!({ "label": "B3" }),
+        };
+        let provider = MockProvider::with_reply("B3 was a ban.")
+            .with_turns([ChatTurn::ToolCalls(vec![call.clone()])]);
+        let service = LlmService::new(provider.clone());
+
+        let reply = service
+            .generate_reply_with_tools(&ChannelContext::default(), "why?", "Mika", &[], &EchoTools)
+            .await
+            .expect("mock provider never fails");
+        assert_eq!(reply.content, "B3 was a ban.");
+        assert!(reply.used_tools);
+
+        let requests = provider.requests();
+        assert_eq!(requests.len(), 2);
+
+        let followup = &requests[1];
+        let assistant = &followup[followup.len() - 2];
+        assert_eq!(assistant.tool_calls, vec![call]);
+
+        let result = &followup[followup.len() - 1];
+        assert_eq!(result.role, ChatRole::Tool);
+        assert_eq!(result.tool_call_id.as_deref(), Some("call_0"));
+        assert_eq!(result.content, r#"{"label":"B3"}"#);
     }
 }
@@ -0,0 +1,331 @@
+use autumn_database::{
+    Database,
+    impls::llm_chat::{
+        get_llm_channel_memory, list_llm_chat_messages_after, list_llm_chat_messages_oldest_first,
+    },
+    model::llm_chat::{LlmChannelMemory, LlmChatEntry},
+};
+
+use crate::prompt::system_prompt_for_channel;
+use crate::provider::{ChatImage, ChatMessage};
+
+/// Token budget for the whole request (system prompt, memory, history, and
+/// the latest message) when `LLM_CONTEXT_TOKEN_BUDGET` is not set.
+pub const DEFAULT_CONTEXT_TOKEN_BUDGET: usize = 3000;
+
+/// Most history rows considered per request; older rows are only reachable
+/// through the channel memory.
+const HISTORY_FETCH_LIMIT: u32 = 200;
+
+/// Approximate per-message cost of role markers and separators.
+const MESSAGE_OVERHEAD_TOKENS: usize = 4;
+
+/// Everything the model sees about a channel besides the latest message.
+#[derive(Clone, Debug, Default)]
+pub struct ChannelContext {
+    pub system_prompt: String,
+    /// Rolling summary of history older than `history`.
+    pub memory: Option<String>,
+    /// Newest first, already trimmed to the token budget.
+    pub history: Vec<LlmChatEntry>,
+}
+
+/// Rough token count for budgeting: about four characters per token, which
+/// holds up well enough for English chat across common tokenizers.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Rough token count of a whole request, including per-message overhead.
+pub(crate) fn estimate_request_tokens(messages: &[ChatMessage]) -> usize {
+    messages
+        .iter()
+        .map(|message| {
+            let call_tokens: usize = message
+                .tool_calls
+                .iter()
+                .map(|call| {
+                    estimate_tokens(&call.name) + estimate_tokens(&call.arguments.to_string())
+                })
+                .sum();
+            estimate_tokens(&message.content) + call_tokens + MESSAGE_OVERHEAD_TOKENS
+        })
+        .sum()
+}
+
+/// How many of the newest entries fit within `budget` tokens.
+pub fn select_history_window(history_newest_first: &[LlmChatEntry], budget: usize) -> usize {
+    let mut used = 0;
+    for (index, entry) in history_newest_first.iter().enumerate() {
+        used += entry_tokens(entry);
+        if used > budget {
+            return index;
+        }
+    }
+
+    history_newest_first.len()
+}
+
+/// Load the system prompt, memory, and as much recent history as fits in
+/// `token_budget` after reserving room for `latest_message`.
+pub async fn load_channel_context(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+    latest_message: &str,
+    token_budget: usize,
+) -> anyhow::Result<ChannelContext> {
+    load_history_context(
+        db,
+        guild_id,
+        channel_id,
+        channel_id,
+        latest_message,
+        token_budget,
+    )
+    .await
+}
+
+/// Like [`load_channel_context`] for a conversation thread: history and
+/// memory come from the thread, the system prompt from its parent channel.
+pub async fn load_thread_context(
+    db: &Database,
+    guild_id: u64,
+    thread_id: u64,
+    parent_channel_id: u64,
+    latest_message: &str,
+    token_budget: usize,
+) -> anyhow::Result<ChannelContext> {
+    load_history_context(
+        db,
+        guild_id,
+        thread_id,
+        parent_channel_id,
+        latest_message,
+        token_budget,
+    )
+    .await
+}
+
+/// Context for a reply chain: the channel's system prompt plus as much of
+/// `chain` (newest first) as fits in `token_budget`. Channel history and
+/// memory are left out so each conversation stays separate.
+pub async fn load_reply_chain_context(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+    mut chain: Vec<LlmChatEntry>,
+    latest_message: &str,
+    token_budget: usize,
+) -> anyhow::Result<ChannelContext> {
+    let system_prompt = system_prompt_for_channel(db, guild_id, channel_id).await?;
+
+    let history_budget = history_budget(
+        token_budget,
+        &system_prompt,
+        None,
+        estimate_tokens(latest_message) + MESSAGE_OVERHEAD_TOKENS,
+    );
+    chain.truncate(select_history_window(&chain, history_budget));
+
+    Ok(ChannelContext {
+        system_prompt,
+        memory: None,
+        history: chain,
+    })
+}
+
+async fn load_history_context(
+    db: &Database,
+    guild_id: u64,
+    history_channel_id: u64,
+    prompt_channel_id: u64,
+    latest_message: &str,
+    token_budget: usize,
+) -> anyhow::Result<ChannelContext> {
+    let (system_prompt, memory, mut history) =
+        load_unbounded_context(db, guild_id, history_channel_id, prompt_channel_id).await?;
+
+    let history_budget = history_budget(
+        token_budget,
+        &system_prompt,
+        memory.as_ref(),
+        estimate_tokens(latest_message) + MESSAGE_OVERHEAD_TOKENS,
+    );
+    history.truncate(select_history_window(&history, history_budget));
+
+    Ok(ChannelContext {
+        system_prompt,
+        memory: memory.map(|memory| memory.summary),
+        history,
+    })
+}
+
+/// The oldest entries that no longer fit in the window and are not yet in
+/// the memory, oldest first, along with the current memory. Budgeted against
+/// `prompt_channel_id`'s system prompt, as the reply path is.
+pub(crate) async fn load_overflow(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+    prompt_channel_id: u64,
+    token_budget: usize,
+) -> anyhow::Result<(Option<LlmChannelMemory>, Vec<LlmChatEntry>)> {
+    let (system_prompt, memory, history) =
+        load_unbounded_context(db, guild_id, channel_id, prompt_channel_id).await?;
+
+    // Leave headroom for the next message so the reply path (which reserves
+    // room for it) does not immediately drop rows the memory skipped.
+    let history_budget = history_budget(token_budget, &system_prompt, memory.as_ref(), 0) * 3 / 4;
+    let window = select_history_window(&history, history_budget);
+    if window == history.len() && history.len() < HISTORY_FETCH_LIMIT as usize {
+        return Ok((memory, Vec::new()));
+    }
+
+    // Everything older than the window overflows, including rows past the
+    // fetch limit, so fold from the oldest up rather than from the window
+    // down; otherwise rows behind a backlog would be skipped for good.
+    let window_start = window.checked_sub(1).map(|index| history[index].id);
+    let overflow = list_llm_chat_messages_oldest_first(
+        db,
+        guild_id,
+        channel_id,
+        memory.as_ref().map(|memory| memory.summarized_through_id),
+        window_start,
+        HISTORY_FETCH_LIMIT,
+    )
+    .await?;
+
+    Ok((memory, overflow))
+}
+
+pub(crate) fn build_channel_messages(
+    context: &ChannelContext,
+    user_prompt: &str,
+    author_display_name: &str,
+    images: &[ChatImage],
+) -> Vec<ChatMessage> {
+    let mut messages = Vec::with_capacity(context.history.len() + 3);
+    messages.push(ChatMessage::system(context.system_prompt.clone()));
+
+    if let Some(memory) = &context.memory {
+        messages.push(ChatMessage::system(format!(
+            "Summary of the earlier conversation in this channel:\n{}",
+            memory
+        )));
+    }
+
+    for item in context.history.iter().rev() {
+        let mapped = match item.role.as_str() {
+            "user" => ChatMessage::user(format_history_content(
+                "user",
+                item.display_name.as_deref(),
+                &item.content,
+            )),
+            "assistant" => ChatMessage::assistant(item.content.clone()),
+            _ => continue,
+        };
+        messages.push(mapped);
+    }
+
+    let priority_prompt = format!(
+        "--- LATEST MESSAGE TO REPLY TO ---\n{}: {}",
+        author_display_name, user_prompt
+    );
+    messages.push(ChatMessage::user(priority_prompt).with_images(images.to_vec()));
+
+    messages
+}
+
+pub(crate) fn format_history_content(
+    role: &str,
+    display_name: Option<&str>,
+    content: &str,
+) -> String {
+    let normalized_name = display_name
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| name.replace('\n', " "))
+        .unwrap_or_else(|| "unknown".to_owned());
+
+    if role == "user" {
+        format!("{}: {}", normalized_name, content)
+    } else {
+        content.to_owned()
+    }
+}
+
+async fn load_unbounded_context(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+    prompt_channel_id: u64,
+) -> anyhow::Result<(String, Option<LlmChannelMemory>, Vec<LlmChatEntry>)> {
+    let system_prompt = system_prompt_for_channel(db, guild_id, prompt_channel_id).await?;
+    let memory = get_llm_channel_memory(db, guild_id, channel_id).await?;
+    let history = list_llm_chat_messages_after(
+        db,
+        guild_id,
+        channel_id,
+        memory.as_ref().map(|memory| memory.summarized_through_id),
+        HISTORY_FETCH_LIMIT,
+    )
+    .await?;
+
+    Ok((system_prompt, memory, history))
+}
+
+fn history_budget(
+    token_budget: usize,
+    system_prompt: &str,
+    memory: Option<&LlmChannelMemory>,
+    reserved: usize,
+) -> usize {
+    let memory_tokens = memory.map_or(0, |memory| {
+        estimate_tokens(&memory.summary) + MESSAGE_OVERHEAD_TOKENS
+    });
+
+    token_budget
+        .saturating_sub(estimate_tokens(system_prompt) + MESSAGE_OVERHEAD_TOKENS)
+        .saturating_sub(memory_tokens)
+        .saturating_sub(reserved)
+}
+
+fn entry_tokens(entry: &LlmChatEntry) -> usize {
+    let name_tokens = entry.display_name.as_deref().map_or(0, estimate_tokens);
+    name_tokens + estimate_tokens(&entry.content) + MESSAGE_OVERHEAD_TOKENS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(content: &str) -> LlmChatEntry {
+        LlmChatEntry {
+            id: 1,
+            user_id: 1,
+            display_name: None,
+            role: "user".to_owned(),
+            content: content.to_owned(),
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn token_estimate_rounds_up() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abc"), 1);
+        assert_eq!(estimate_tokens("abcdefgh"), 2);
+    }
+
+    #[test]
+    fn window_keeps_newest_entries_within_budget() {
+        // Each entry costs 10 content tokens + 4 overhead.
+        let history = vec![entry(&"a".repeat(40)); 5];
+
+        assert_eq!(select_history_window(&history, 0), 0);
+        assert_eq!(select_history_window(&history, 14), 1);
+        assert_eq!(select_history_window(&history, 41), 2);
+        assert_eq!(select_history_window(&history, 1000), 5);
+    }
+}
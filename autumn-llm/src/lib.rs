@@ -1,4 +1,11 @@
+pub mod classify;
 mod client;
-mod prompt;
+pub mod context;
+pub mod prompt;
+pub mod provider;
+pub mod summary;
+pub mod tools;
+pub mod usage;
 
-pub use client::LlmService;
+pub use client::{LlmReply, LlmService};
+pub use provider::{ChatProvider, TokenUsage};
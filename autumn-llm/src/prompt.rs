@@ -1,13 +1,70 @@
-use std::{fs, path::Path};
+use std::{fs, path::Path, sync::OnceLock};
+
+use anyhow::Context as _;
+use autumn_database::{Database, impls::llm_prompt::resolve_llm_system_prompt};
 
 const DEFAULT_SYSTEM_PROMPT: &str = "You are Autumn, a sarcastic but genuinely helpful Discord moderation assistant. \
 Keep answers concise, practical, and clear. Avoid hostility, harassment, and unsafe advice. \
 If context is missing, ask one focused follow-up question.";
 
-pub fn system_prompt() -> String {
-    let prompt_file = Path::new("SYSTEM_PROMPT.md");
-    match fs::read_to_string(prompt_file) {
-        Ok(value) if !value.trim().is_empty() => value,
-        _ => DEFAULT_SYSTEM_PROMPT.to_owned(),
-    }
+/// Instructions for folding old channel history into the rolling memory.
+pub const MEMORY_SUMMARY_PROMPT: &str = "You maintain the long-term memory of a Discord channel conversation. \
+Merge the existing memory with the new messages into one updated summary of at most 200 words. \
+Keep names, facts, decisions, preferences, and open questions; drop greetings and small talk. \
+Write plain prose in the third person and output only the summary.";
+
+/// Added when moderation lookup tools are offered.
+pub const TOOL_USE_PROMPT: &str = "The person asking is a moderator. You can look up moderation records with the provided tools. \
+Use them whenever the question is about cases, warnings, or notes, and only state facts the tools returned. \
+Cite cases by their label (for example B3). If a lookup finds nothing, say so instead of guessing.";
+
+/// Instructions for `!summarize`.
+pub const MODERATION_SUMMARY_PROMPT: &str = "You summarize a Discord user's moderation history for the server's moderators. \
+Use only the records provided; do not guess or add anything else. \
+Write at most 8 short bullet points covering what happened, how often, and whether it is escalating, then one line with the overall picture. \
+Cite every claim about a case with its label in square brackets, exactly as given, for example [W3] or [B1]. \
+Refer to notes and logged messages as such, since they have no labels. \
+Do not recommend a punishment. If there are no records, say so.";
+
+/// Instructions for AI moderation. The reply is parsed as JSON.
+pub const CLASSIFICATION_PROMPT: &str = "You classify Discord messages for a moderation team. \
+Categories: harassment (insults, threats, or demeaning remarks aimed at a person, even when phrased politely), \
+hate (attacks on a protected group), self_harm (intent or encouragement to self-harm), \
+spam (unsolicited ads, scams, or repeated junk), or none. \
+Banter, profanity without a target, and quoting or discussing a topic are none. \
+Respond with only a JSON object: {\"category\": \"<category>\", \"confidence\": <0.0-1.0>, \"reason\": \"<one short sentence>\"}.";
+
+/// Added to a prompt with image attachments when the model cannot view them.
+pub const IMAGES_UNSUPPORTED_NOTE: &str = "(The message above came with an image, but the current model cannot view images. \
+If the question depends on it, say so briefly and ask them to describe it or paste any text from it.)";
+
+static FILE_SYSTEM_PROMPT: OnceLock<String> = OnceLock::new();
+
+/// The fallback prompt used when a guild has not configured its own:
+/// `SYSTEM_PROMPT.md` if present and non-empty, otherwise the built-in default.
+/// The file is read once per process.
+pub fn default_system_prompt() -> &'static str {
+    FILE_SYSTEM_PROMPT.get_or_init(|| {
+        let prompt_file = Path::new("SYSTEM_PROMPT.md");
+        match fs::read_to_string(prompt_file) {
+            Ok(value) if !value.trim().is_empty() => value,
+            _ => DEFAULT_SYSTEM_PROMPT.to_owned(),
+        }
+    })
+}
+
+/// The system prompt for a channel: its override, the guild prompt, or the
+/// default, in that order.
+pub async fn system_prompt_for_channel(
+    db: &Database,
+    guild_id: u64,
+    channel_id: u64,
+) -> anyhow::Result<String> {
+    let configured = resolve_llm_system_prompt(db, guild_id, channel_id)
+        .await
+        .context("failed to resolve llm system prompt")?;
+
+    Ok(configured
+        .map(|prompt| prompt.prompt)
+        .unwrap_or_else(|| default_system_prompt().to_owned()))
 }
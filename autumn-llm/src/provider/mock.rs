@@ -0,0 +1,125 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use super::{
+    CapabilityFuture, ChatDeltaSender, ChatFuture, ChatMessage, ChatOptions, ChatProvider,
+    ChatResponse, ChatRole, ChatTurn, ChatTurnFuture, ChatTurnResponse, ToolSpec,
+};
+
+/// Local provider that never calls a model. With a fixed reply it always
+/// returns that text; otherwise it echoes the last user message. Every
+/// conversation it receives is recorded so tests can inspect the prompt.
+/// Streamed replies are sent one word at a time. Scripted turns, if any, are
+/// returned in order by tool-enabled chats before falling back to the reply.
+/// Images are only accepted after [`MockProvider::with_vision`]. Token usage
+/// is never reported.
+#[derive(Clone, Debug, Default)]
+pub struct MockProvider {
+    reply: Option<String>,
+    vision: bool,
+    turns: Arc<Mutex<VecDeque<ChatTurn>>>,
+    requests: Arc<Mutex<Vec<Vec<ChatMessage>>>>,
+}
+
+impl MockProvider {
+    pub fn echo() -> Self {
+        Self::default()
+    }
+
+    pub fn with_reply(reply: impl Into<String>) -> Self {
+        Self {
+            reply: Some(reply.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Return `turns` from the next tool-enabled chats, in order.
+    pub fn with_turns(mut self, turns: impl IntoIterator<Item = ChatTurn>) -> Self {
+        self.turns = Arc::new(Mutex::new(turns.into_iter().collect()));
+        self
+    }
+
+    pub fn with_vision(mut self) -> Self {
+        self.vision = true;
+        self
+    }
+
+    /// Conversations received so far, oldest first.
+    pub fn requests(&self) -> Vec<Vec<ChatMessage>> {
+        self.requests
+            .lock()
+            .map(|requests| requests.clone())
+            .unwrap_or_default()
+    }
+
+    fn respond(&self, messages: &[ChatMessage]) -> String {
+        if let Ok(mut requests) = self.requests.lock() {
+            requests.push(messages.to_vec());
+        }
+
+        match &self.reply {
+            Some(reply) => reply.clone(),
+            None => messages
+                .iter()
+                .rev()
+                .find(|message| message.role == ChatRole::User)
+                .map(|message| message.content.clone())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl ChatProvider for MockProvider {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    fn supports_images(&self) -> CapabilityFuture<'_> {
+        let vision = self.vision;
+        Box::pin(async move { vision })
+    }
+
+    fn chat<'a>(&'a self, messages: &'a [ChatMessage], _options: ChatOptions) -> ChatFuture<'a> {
+        Box::pin(async move { Ok(ChatResponse::new(self.respond(messages))) })
+    }
+
+    fn chat_stream<'a>(
+        &'a self,
+        messages: &'a [ChatMessage],
+        _options: ChatOptions,
+        deltas: ChatDeltaSender,
+    ) -> ChatFuture<'a> {
+        Box::pin(async move {
+            let reply = self.respond(messages);
+            for word in reply.split_inclusive(' ') {
+                let _ = deltas.send(word.to_owned());
+            }
+            Ok(ChatResponse::new(reply))
+        })
+    }
+
+    fn chat_with_tools<'a>(
+        &'a self,
+        messages: &'a [ChatMessage],
+        _tools: &'a [ToolSpec],
+        _options: ChatOptions,
+    ) -> ChatTurnFuture<'a> {
+        Box::pin(async move {
+            let scripted = self
+                .turns
+                .lock()
+                .ok()
+                .and_then(|mut turns| turns.pop_front());
+            let turn = match scripted {
+                Some(turn) => {
+                    if let Ok(mut requests) = self.requests.lock() {
+                        requests.push(messages.to_vec());
+                    }
+                    turn
+                }
+                None => ChatTurn::Reply(self.respond(messages)),
+            };
+            Ok(ChatTurnResponse { turn, usage: None })
+        })
+    }
+}
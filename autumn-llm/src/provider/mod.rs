@@ -0,0 +1,263 @@
+mod mock;
+mod ollama;
+mod openai;
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
+
+pub use mock::MockProvider;
+pub use ollama::OllamaProvider;
+pub use openai::OpenAiCompatibleProvider;
+
+pub type ChatFuture<'a> = Pin<Box<dyn Future<Output = anyhow::Result<ChatResponse>> + Send + 'a>>;
+
+pub type ChatTurnFuture<'a> =
+    Pin<Box<dyn Future<Output = anyhow::Result<ChatTurnResponse>> + Send + 'a>>;
+
+pub type CapabilityFuture<'a> = Pin<Box<dyn Future<Output = bool> + Send + 'a>>;
+
+/// Receives incremental pieces of the reply while a streamed chat is running.
+pub type ChatDeltaSender = tokio::sync::mpsc::UnboundedSender<String>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChatRole {
+    System,
+    User,
+    Assistant,
+    Tool,
+}
+
+impl ChatRole {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::System => "system",
+            Self::User => "user",
+            Self::Assistant => "assistant",
+            Self::Tool => "tool",
+        }
+    }
+}
+
+/// A provider-neutral chat message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChatMessage {
+    pub role: ChatRole,
+    pub content: String,
+    /// Calls requested by an assistant message.
+    pub tool_calls: Vec<ToolCall>,
+    /// The call a `Tool` message answers.
+    pub tool_call_id: Option<String>,
+    /// Images attached to a user message.
+    pub images: Vec<ChatImage>,
+}
+
+impl ChatMessage {
+    fn new(role: ChatRole, content: String) -> Self {
+        Self {
+            role,
+            content,
+            tool_calls: Vec::new(),
+            tool_call_id: None,
+            images: Vec::new(),
+        }
+    }
+
+    pub fn system(content: impl Into<String>) -> Self {
+        Self::new(ChatRole::System, content.into())
+    }
+
+    pub fn user(content: impl Into<String>) -> Self {
+        Self::new(ChatRole::User, content.into())
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self::new(ChatRole::Assistant, content.into())
+    }
+
+    pub fn with_images(mut self, images: Vec<ChatImage>) -> Self {
+        self.images = images;
+        self
+    }
+
+    /// An assistant turn that asked for tool calls instead of replying.
+    pub fn assistant_tool_calls(tool_calls: Vec<ToolCall>) -> Self {
+        Self {
+            tool_calls,
+            ..Self::new(ChatRole::Assistant, String::new())
+        }
+    }
+
+    /// The result of one tool call.
+    pub fn tool(tool_call_id: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            tool_call_id: Some(tool_call_id.into()),
+            ..Self::new(ChatRole::Tool, content.into())
+        }
+    }
+}
+
+/// An image for multimodal models, base64-encoded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChatImage {
+    /// e.g. `image/png`.
+    pub mime_type: String,
+    pub data: String,
+}
+
+impl ChatImage {
+    pub fn from_bytes(mime_type: impl Into<String>, bytes: &[u8]) -> Self {
+        Self {
+            mime_type: mime_type.into(),
+            data: BASE64.encode(bytes),
+        }
+    }
+
+    /// The image as a `data:` URL.
+    pub fn data_url(&self) -> String {
+        format!("data:{};base64,{}", self.mime_type, self.data)
+    }
+}
+
+/// A function the model may call, with its arguments described as a JSON
+/// schema object.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ToolSpec {
+    pub name: String,
+    pub description: String,
+    pub parameters: serde_json::Value,
+}
+
+/// A call the model asked for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ToolCall {
+    pub id: String,
+    pub name: String,
+    pub arguments: serde_json::Value,
+}
+
+/// Tokens spent on a request.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TokenUsage {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+}
+
+impl TokenUsage {
+    pub fn total(&self) -> u32 {
+        self.prompt_tokens.saturating_add(self.completion_tokens)
+    }
+}
+
+impl std::ops::AddAssign for TokenUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.prompt_tokens = self.prompt_tokens.saturating_add(other.prompt_tokens);
+        self.completion_tokens = self
+            .completion_tokens
+            .saturating_add(other.completion_tokens);
+    }
+}
+
+/// The assistant's reply, with the token counts the backend reported. Not
+/// every backend reports them, so `usage` may be missing.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChatResponse {
+    pub content: String,
+    pub usage: Option<TokenUsage>,
+}
+
+impl ChatResponse {
+    /// A reply without reported usage.
+    pub fn new(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            usage: None,
+        }
+    }
+}
+
+/// What the model did with a turn when it was offered tools.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChatTurn {
+    Reply(String),
+    ToolCalls(Vec<ToolCall>),
+}
+
+/// A [`ChatTurn`] with the token counts the backend reported, if any.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChatTurnResponse {
+    pub turn: ChatTurn,
+    pub usage: Option<TokenUsage>,
+}
+
+/// Sampling options shared by all providers. Providers ignore options their
+/// backend does not support.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChatOptions {
+    pub temperature: f32,
+    pub repeat_penalty: f32,
+}
+
+impl Default for ChatOptions {
+    fn default() -> Self {
+        Self {
+            temperature: 0.75,
+            repeat_penalty: 1.2,
+        }
+    }
+}
+
+/// A chat-completion backend.
+pub trait ChatProvider: Debug + Send + Sync {
+    /// Short name used in logs (e.g. `ollama`, `openai`).
+    fn name(&self) -> &'static str;
+
+    /// Whether the model accepts images. Images sent to a model without
+    /// vision support are ignored or rejected, depending on the backend.
+    fn supports_images(&self) -> CapabilityFuture<'_> {
+        Box::pin(async { false })
+    }
+
+    /// Send the conversation and return the assistant's reply, with token
+    /// counts when the backend reports them.
+    fn chat<'a>(&'a self, messages: &'a [ChatMessage], options: ChatOptions) -> ChatFuture<'a>;
+
+    /// Like [`ChatProvider::chat`], but send each piece of the reply to
+    /// `deltas` as soon as it arrives. Returns the full reply.
+    ///
+    /// Providers without streaming support send the whole reply as one delta.
+    fn chat_stream<'a>(
+        &'a self,
+        messages: &'a [ChatMessage],
+        options: ChatOptions,
+        deltas: ChatDeltaSender,
+    ) -> ChatFuture<'a> {
+        Box::pin(async move {
+            let reply = self.chat(messages, options).await?;
+            let _ = deltas.send(reply.content.clone());
+            Ok(reply)
+        })
+    }
+
+    /// Send the conversation along with `tools` the model may call.
+    ///
+    /// Providers without tool support ignore `tools` and always reply.
+    fn chat_with_tools<'a>(
+        &'a self,
+        messages: &'a [ChatMessage],
+        tools: &'a [ToolSpec],
+        options: ChatOptions,
+    ) -> ChatTurnFuture<'a> {
+        let _ = tools;
+        Box::pin(async move {
+            let reply = self.chat(messages, options).await?;
+            Ok(ChatTurnResponse {
+                turn: ChatTurn::Reply(reply.content),
+                usage: reply.usage,
+            })
+        })
+    }
+}
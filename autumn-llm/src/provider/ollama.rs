@@ -0,0 +1,230 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use ollama_rs::{
+    Ollama,
+    generation::{
+        chat::{
+            ChatMessage as OllamaMessage, ChatMessageFinalResponseData, request::ChatMessageRequest,
+        },
+        images::Image,
+        tools::{
+            ToolCall as OllamaToolCall, ToolCallFunction, ToolFunctionInfo, ToolInfo, ToolType,
+        },
+    },
+    models::ModelOptions,
+};
+use tokio::sync::OnceCell;
+use tokio_stream::StreamExt as _;
+use tracing::warn;
+
+use super::{
+    CapabilityFuture, ChatDeltaSender, ChatFuture, ChatMessage, ChatOptions, ChatProvider,
+    ChatResponse, ChatRole, ChatTurn, ChatTurnFuture, ChatTurnResponse, TokenUsage, ToolCall,
+    ToolSpec,
+};
+
+#[derive(Clone, Debug)]
+pub struct OllamaProvider {
+    client: Ollama,
+    model: String,
+    /// Whether the model reports the `vision` capability, looked up once.
+    vision: Arc<OnceCell<bool>>,
+}
+
+impl OllamaProvider {
+    pub fn new(host: String, port: u16, model: String) -> Self {
+        Self {
+            client: Ollama::new(host, port),
+            model,
+            vision: Arc::default(),
+        }
+    }
+
+    fn request(&self, messages: &[ChatMessage], options: ChatOptions) -> ChatMessageRequest {
+        let messages = messages
+            .iter()
+            .map(|message| match message.role {
+                ChatRole::System => OllamaMessage::system(message.content.clone()),
+                ChatRole::User if !message.images.is_empty() => {
+                    OllamaMessage::user(message.content.clone()).with_images(
+                        message
+                            .images
+                            .iter()
+                            .map(|image| Image::from_base64(image.data.clone()))
+                            .collect(),
+                    )
+                }
+                ChatRole::User => OllamaMessage::user(message.content.clone()),
+                ChatRole::Assistant => {
+                    let mut mapped = OllamaMessage::assistant(message.content.clone());
+                    mapped.tool_calls = message
+                        .tool_calls
+                        .iter()
+                        .map(|call| OllamaToolCall {
+                            function: ToolCallFunction {
+                                name: call.name.clone(),
+                                arguments: call.arguments.clone(),
+                            },
+                        })
+                        .collect();
+                    mapped
+                }
+                ChatRole::Tool => OllamaMessage::tool(message.content.clone()),
+            })
+            .collect();
+
+        ChatMessageRequest::new(self.model.clone(), messages).options(
+            ModelOptions::default()
+                .temperature(options.temperature)
+                .repeat_penalty(options.repeat_penalty),
+        )
+    }
+}
+
+impl ChatProvider for OllamaProvider {
+    fn name(&self) -> &'static str {
+        "ollama"
+    }
+
+    fn supports_images(&self) -> CapabilityFuture<'_> {
+        Box::pin(async move {
+            let lookup = self
+                .vision
+                .get_or_try_init(|| async {
+                    let info = self
+                        .client
+                        .show_model_info(self.model.clone())
+                        .await
+                        .context("failed to read ollama model info")?;
+                    anyhow::Ok(
+                        info.capabilities
+                            .iter()
+                            .any(|capability| capability == "vision"),
+                    )
+                })
+                .await;
+
+            // A failed lookup is retried on the next call.
+            lookup.copied().unwrap_or_else(|source| {
+                warn!(?source, model = %self.model, "could not check ollama vision support");
+                false
+            })
+        })
+    }
+
+    fn chat<'a>(&'a self, messages: &'a [ChatMessage], options: ChatOptions) -> ChatFuture<'a> {
+        Box::pin(async move {
+            let request = self.request(messages, options);
+            let response = self
+                .client
+                .send_chat_messages(request)
+                .await
+                .context("failed to get ollama chat response")?;
+
+            Ok(ChatResponse {
+                usage: reported_usage(response.final_data.as_ref()),
+                content: response.message.content,
+            })
+        })
+    }
+
+    fn chat_stream<'a>(
+        &'a self,
+        messages: &'a [ChatMessage],
+        options: ChatOptions,
+        deltas: ChatDeltaSender,
+    ) -> ChatFuture<'a> {
+        Box::pin(async move {
+            let request = self.request(messages, options);
+            let mut stream = self
+                .client
+                .send_chat_messages_stream(request)
+                .await
+                .context("failed to start ollama chat stream")?;
+
+            let mut reply = ChatResponse::default();
+            while let Some(chunk) = stream.next().await {
+                let chunk = chunk.map_err(|_| anyhow::anyhow!("ollama chat stream failed"))?;
+                if !chunk.message.content.is_empty() {
+                    reply.content.push_str(&chunk.message.content);
+                    let _ = deltas.send(chunk.message.content);
+                }
+                if chunk.done {
+                    // Counts arrive with the final chunk.
+                    reply.usage = reported_usage(chunk.final_data.as_ref());
+                    break;
+                }
+            }
+
+            Ok(reply)
+        })
+    }
+
+    fn chat_with_tools<'a>(
+        &'a self,
+        messages: &'a [ChatMessage],
+        tools: &'a [ToolSpec],
+        options: ChatOptions,
+    ) -> ChatTurnFuture<'a> {
+        Box::pin(async move {
+            let tools = tools
+                .iter()
+                .map(|tool| {
+                    Ok(ToolInfo {
+                        tool_type: ToolType::Function,
+                        function: ToolFunctionInfo {
+                            name: tool.name.clone(),
+                            description: tool.description.clone(),
+                            parameters: serde_json::from_value(tool.parameters.clone())
+                                .with_context(|| {
+                                    format!("invalid parameter schema for tool {}", tool.name)
+                                })?,
+                        },
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            let request = self.request(messages, options).tools(tools);
+            let response = self
+                .client
+                .send_chat_messages(request)
+                .await
+                .context("failed to get ollama chat response")?;
+
+            let usage = reported_usage(response.final_data.as_ref());
+            if response.message.tool_calls.is_empty() {
+                return Ok(ChatTurnResponse {
+                    turn: ChatTurn::Reply(response.message.content),
+                    usage,
+                });
+            }
+
+            // Ollama does not assign call ids; results are matched by order.
+            let calls = response
+                .message
+                .tool_calls
+                .into_iter()
+                .enumerate()
+                .map(|(index, call)| ToolCall {
+                    id: format!("call_{index}"),
+                    name: call.function.name,
+                    arguments: call.function.arguments,
+                })
+                .collect();
+
+            Ok(ChatTurnResponse {
+                turn: ChatTurn::ToolCalls(calls),
+                usage,
+            })
+        })
+    }
+}
+
+/// `prompt_eval_count` and `eval_count` from a finished response.
+fn reported_usage(final_data: Option<&ChatMessageFinalResponseData>) -> Option<TokenUsage> {
+    final_data.map(|data| TokenUsage {
+        prompt_tokens: u32::try_from(data.prompt_eval_count).unwrap_or(u32::MAX),
+        completion_tokens: u32::try_from(data.eval_count).unwrap_or(u32::MAX),
+    })
+}
@@ -0,0 +1,483 @@
+use std::time::Duration;
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    CapabilityFuture, ChatDeltaSender, ChatFuture, ChatMessage, ChatOptions, ChatProvider,
+    ChatResponse, ChatTurn, ChatTurnFuture, ChatTurnResponse, TokenUsage, ToolCall, ToolSpec,
+};
+
+/// Applies to each read, so long streamed replies are not cut off.
+const READ_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Provider for servers that speak the OpenAI `/v1/chat/completions` API
+/// (vLLM, llama.cpp server, LM Studio, OpenAI itself, ...).
+///
+/// `repeat_penalty` is not part of that API and is not sent. The API has no
+/// capability lookup, so vision support is configured with
+/// [`OpenAiCompatibleProvider::with_vision`].
+#[derive(Clone, Debug)]
+pub struct OpenAiCompatibleProvider {
+    http: reqwest::Client,
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+    vision: bool,
+}
+
+#[derive(Serialize)]
+struct CompletionRequest<'a> {
+    model: &'a str,
+    messages: Vec<RequestMessage<'a>>,
+    temperature: f32,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamOptions>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<RequestTool<'a>>,
+}
+
+/// Asks for a final chunk carrying `usage`; streams omit it otherwise.
+#[derive(Serialize)]
+struct StreamOptions {
+    include_usage: bool,
+}
+
+#[derive(Serialize)]
+struct RequestMessage<'a> {
+    role: &'static str,
+    content: RequestContent<'a>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tool_calls: Vec<RequestToolCall<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_call_id: Option<&'a str>,
+}
+
+/// Plain text, or text and images as content parts.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum RequestContent<'a> {
+    Text(&'a str),
+    Parts(Vec<ContentPart<'a>>),
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ContentPart<'a> {
+    Text { text: &'a str },
+    ImageUrl { image_url: ImageUrl },
+}
+
+#[derive(Serialize)]
+struct ImageUrl {
+    url: String,
+}
+
+#[derive(Serialize)]
+struct RequestTool<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    function: RequestFunction<'a>,
+}
+
+#[derive(Serialize)]
+struct RequestFunction<'a> {
+    name: &'a str,
+    description: &'a str,
+    parameters: &'a serde_json::Value,
+}
+
+#[derive(Serialize)]
+struct RequestToolCall<'a> {
+    id: &'a str,
+    #[serde(rename = "type")]
+    kind: &'static str,
+    function: FunctionCall,
+}
+
+/// Arguments travel as a JSON-encoded string in both directions.
+#[derive(Serialize, Deserialize)]
+struct FunctionCall {
+    name: String,
+    #[serde(default)]
+    arguments: String,
+}
+
+#[derive(Deserialize)]
+struct CompletionResponse {
+    choices: Vec<CompletionChoice>,
+    #[serde(default)]
+    usage: Option<ResponseUsage>,
+}
+
+#[derive(Clone, Copy, Deserialize)]
+struct ResponseUsage {
+    #[serde(default)]
+    prompt_tokens: u32,
+    #[serde(default)]
+    completion_tokens: u32,
+}
+
+impl From<ResponseUsage> for TokenUsage {
+    fn from(usage: ResponseUsage) -> Self {
+        Self {
+            prompt_tokens: usage.prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct CompletionChoice {
+    message: ResponseMessage,
+}
+
+#[derive(Deserialize)]
+struct ResponseMessage {
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<ResponseToolCall>,
+}
+
+#[derive(Deserialize)]
+struct ResponseToolCall {
+    #[serde(default)]
+    id: String,
+    function: FunctionCall,
+}
+
+#[derive(Deserialize)]
+struct StreamChunk {
+    #[serde(default)]
+    choices: Vec<StreamChoice>,
+    #[serde(default)]
+    usage: Option<ResponseUsage>,
+}
+
+#[derive(Deserialize)]
+struct StreamChoice {
+    delta: ResponseMessage,
+}
+
+/// One parsed line of a server-sent event stream.
+#[derive(Debug, PartialEq, Eq)]
+enum StreamLine {
+    Delta(String),
+    Usage(TokenUsage),
+    Done,
+    Skip,
+}
+
+impl OpenAiCompatibleProvider {
+    /// `base_url` is the API root including the version segment, e.g.
+    /// `http://127.0.0.1:8000/v1`.
+    pub fn new(base_url: String, api_key: Option<String>, model: String) -> anyhow::Result<Self> {
+        let http = reqwest::Client::builder()
+            .read_timeout(READ_TIMEOUT)
+            .build()
+            .context("failed to build openai-compatible http client")?;
+
+        Ok(Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            api_key,
+            model,
+            vision: false,
+        })
+    }
+
+    /// Declare whether the model accepts image content parts.
+    pub fn with_vision(mut self, vision: bool) -> Self {
+        self.vision = vision;
+        self
+    }
+
+    async fn send(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[ToolSpec],
+        options: ChatOptions,
+        stream: bool,
+    ) -> anyhow::Result<reqwest::Response> {
+        let body = CompletionRequest {
+            model: &self.model,
+            messages: messages
+                .iter()
+                .map(|message| RequestMessage {
+                    role: message.role.as_str(),
+                    content: request_content(message),
+                    tool_calls: message
+                        .tool_calls
+                        .iter()
+                        .map(|call| RequestToolCall {
+                            id: &call.id,
+                            kind: "function",
+                            function: FunctionCall {
+                                name: call.name.clone(),
+                                arguments: call.arguments.to_string(),
+                            },
+                        })
+                        .collect(),
+                    tool_call_id: message.tool_call_id.as_deref(),
+                })
+                .collect(),
+            temperature: options.temperature,
+            stream,
+            stream_options: stream.then_some(StreamOptions {
+                include_usage: true,
+            }),
+            tools: tools
+                .iter()
+                .map(|tool| RequestTool {
+                    kind: "function",
+                    function: RequestFunction {
+                        name: &tool.name,
+                        description: &tool.description,
+                        parameters: &tool.parameters,
+                    },
+                })
+                .collect(),
+        };
+
+        let mut request = self
+            .http
+            .post(format!("{}/chat/completions", self.base_url))
+            .json(&body);
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        request
+            .send()
+            .await
+            .context("failed to reach openai-compatible endpoint")?
+            .error_for_status()
+            .context("openai-compatible endpoint returned an error status")
+    }
+}
+
+impl ChatProvider for OpenAiCompatibleProvider {
+    fn name(&self) -> &'static str {
+        "openai"
+    }
+
+    fn supports_images(&self) -> CapabilityFuture<'_> {
+        let vision = self.vision;
+        Box::pin(async move { vision })
+    }
+
+    fn chat<'a>(&'a self, messages: &'a [ChatMessage], options: ChatOptions) -> ChatFuture<'a> {
+        Box::pin(async move {
+            let response = self
+                .send(messages, &[], options, false)
+                .await?
+                .json::<CompletionResponse>()
+                .await
+                .context("failed to decode openai-compatible chat response")?;
+
+            let content = response
+                .choices
+                .into_iter()
+                .next()
+                .and_then(|choice| choice.message.content)
+                .unwrap_or_default();
+
+            Ok(ChatResponse {
+                content,
+                usage: response.usage.map(TokenUsage::from),
+            })
+        })
+    }
+
+    fn chat_stream<'a>(
+        &'a self,
+        messages: &'a [ChatMessage],
+        options: ChatOptions,
+        deltas: ChatDeltaSender,
+    ) -> ChatFuture<'a> {
+        Box::pin(async move {
+            let mut response = self.send(messages, &[], options, true).await?;
+
+            let mut reply = ChatResponse::default();
+            let mut buffer: Vec<u8> = Vec::new();
+
+            'stream: while let Some(chunk) = response
+                .chunk()
+                .await
+                .context("openai-compatible chat stream failed")?
+            {
+                buffer.extend_from_slice(&chunk);
+
+                // Only decode complete lines so multi-byte characters split
+                // across chunks stay intact.
+                while let Some(newline) = buffer.iter().position(|byte| *byte == b'\n') {
+                    let line: Vec<u8> = buffer.drain(..=newline).collect();
+                    match parse_stream_line(&String::from_utf8_lossy(&line)) {
+                        StreamLine::Delta(delta) => {
+                            reply.content.push_str(&delta);
+                            let _ = deltas.send(delta);
+                        }
+                        StreamLine::Usage(usage) => reply.usage = Some(usage),
+                        StreamLine::Done => break 'stream,
+                        StreamLine::Skip => {}
+                    }
+                }
+            }
+
+            Ok(reply)
+        })
+    }
+
+    fn chat_with_tools<'a>(
+        &'a self,
+        messages: &'a [ChatMessage],
+        tools: &'a [ToolSpec],
+        options: ChatOptions,
+    ) -> ChatTurnFuture<'a> {
+        Box::pin(async move {
+            let response = self
+                .send(messages, tools, options, false)
+                .await?
+                .json::<CompletionResponse>()
+                .await
+                .context("failed to decode openai-compatible chat response")?;
+
+            let usage = response.usage.map(TokenUsage::from);
+            let Some(message) = response
+                .choices
+                .into_iter()
+                .next()
+                .map(|choice| choice.message)
+            else {
+                return Ok(ChatTurnResponse {
+                    turn: ChatTurn::Reply(String::new()),
+                    usage,
+                });
+            };
+
+            if message.tool_calls.is_empty() {
+                return Ok(ChatTurnResponse {
+                    turn: ChatTurn::Reply(message.content.unwrap_or_default()),
+                    usage,
+                });
+            }
+
+            let calls = message
+                .tool_calls
+                .into_iter()
+                .enumerate()
+                .map(|(index, call)| ToolCall {
+                    id: if call.id.is_empty() {
+                        format!("call_{index}")
+                    } else {
+                        call.id
+                    },
+                    name: call.function.name,
+                    // Malformed arguments become `null`; the tool reports
+                    // the missing fields back to the model.
+                    arguments: serde_json::from_str(&call.function.arguments)
+                        .unwrap_or(serde_json::Value::Null),
+                })
+                .collect();
+
+            Ok(ChatTurnResponse {
+                turn: ChatTurn::ToolCalls(calls),
+                usage,
+            })
+        })
+    }
+}
+
+fn request_content(message: &ChatMessage) -> RequestContent<'_> {
+    if message.images.is_empty() {
+        return RequestContent::Text(&message.content);
+    }
+
+    let mut parts = vec![ContentPart::Text {
+        text: &message.content,
+    }];
+    parts.extend(message.images.iter().map(|image| ContentPart::ImageUrl {
+        image_url: ImageUrl {
+            url: image.data_url(),
+        },
+    }));
+
+    RequestContent::Parts(parts)
+}
+
+fn parse_stream_line(line: &str) -> StreamLine {
+    let Some(data) = line.trim().strip_prefix("data:") else {
+        return StreamLine::Skip;
+    };
+
+    let data = data.trim();
+    if data == "[DONE]" {
+        return StreamLine::Done;
+    }
+
+    let Ok(chunk) = serde_json::from_str::<StreamChunk>(data) else {
+        return StreamLine::Skip;
+    };
+
+    let delta = chunk
+        .choices
+        .into_iter()
+        .next()
+        .and_then(|choice| choice.delta.content)
+        .filter(|content| !content.is_empty());
+    match (delta, chunk.usage) {
+        (Some(delta), _) => StreamLine::Delta(delta),
+        (None, Some(usage)) => StreamLine::Usage(usage.into()),
+        (None, None) => StreamLine::Skip,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream_lines_parse_deltas_and_done() {
+        assert_eq!(
+            parse_stream_line(r#"data: {"choices":[{"delta":{"content":"Hi"}}]}"#),
+            StreamLine::Delta("Hi".to_owned())
+        );
+        assert_eq!(
+            parse_stream_line(r#"data: {"choices":[{"delta":{"role":"assistant"}}]}"#),
+            StreamLine::Skip
+        );
+        assert_eq!(
+            parse_stream_line(
+                r#"data: {"choices":[],"usage":{"prompt_tokens":12,"completion_tokens":3}}"#
+            ),
+            StreamLine::Usage(TokenUsage {
+                prompt_tokens: 12,
+                completion_tokens: 3,
+            })
+        );
+        assert_eq!(parse_stream_line(": keep-alive"), StreamLine::Skip);
+        assert_eq!(parse_stream_line("data: [DONE]"), StreamLine::Done);
+    }
+
+    #[test]
+    fn images_are_sent_as_content_parts() {
+        let text = ChatMessage::user("hi");
+        assert_eq!(
+            serde_json::to_value(request_content(&text)).expect("serializable"),
+            serde_json::json!("hi")
+        );
+
+        let with_image = ChatMessage::user("what is this?").with_images(vec![
+            super::super::ChatImage::from_bytes("image/png", b"png"),
+        ]);
+        assert_eq!(
+            serde_json::to_value(request_content(&with_image)).expect("serializable"),
+            serde_json::json!([
+                { "type": "text", "text": "what is this?" },
+                { "type": "image_url", "image_url": { "url": "data:image/png;base64,cG5n" } },
+            ])
+        );
+    }
+}
@@ -0,0 +1,286 @@
+use std::fmt::Write as _;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use autumn_database::{
+    Database,
+    impls::{
+        cases::{CaseFilters, get_case_events, list_recent_cases},
+        notes::list_user_notes,
+        user_logs::{UserLogEntry, UserLogFilters, list_recent_user_logs},
+    },
+    model::{
+        cases::{CaseEvent, CaseSummary},
+        notes::UserNote,
+    },
+};
+
+use crate::prompt::MODERATION_SUMMARY_PROMPT;
+use crate::provider::ChatMessage;
+
+const MAX_CASES: u32 = 25;
+const MAX_NOTES: usize = 10;
+const MAX_USER_LOGS: u32 = 20;
+/// Longest excerpt of a logged message or note included in the prompt.
+const EXCERPT_MAX_CHARS: usize = 200;
+
+/// Everything on file about one user, newest first in each list.
+#[derive(Clone, Debug, Default)]
+pub struct ModerationRecord {
+    pub cases: Vec<(CaseSummary, Vec<CaseEvent>)>,
+    pub notes: Vec<UserNote>,
+    pub user_logs: Vec<UserLogEntry>,
+}
+
+impl ModerationRecord {
+    pub fn is_empty(&self) -> bool {
+        self.cases.is_empty() && self.notes.is_empty() && self.user_logs.is_empty()
+    }
+
+    /// Labels of the cases in the record, e.g. `W3`.
+    pub fn case_labels(&self) -> Vec<String> {
+        self.cases
+            .iter()
+            .map(|(case, _)| case_label(case))
+            .collect()
+    }
+}
+
+pub async fn load_moderation_record(
+    db: &Database,
+    guild_id: u64,
+    user_id: u64,
+) -> anyhow::Result<ModerationRecord> {
+    let cases = list_recent_cases(
+        db,
+        guild_id,
+        CaseFilters {
+            target_user_id: Some(user_id),
+            moderator_user_id: None,
+            action: None,
+            limit: MAX_CASES,
+        },
+    )
+    .await?;
+
+    let mut cases_with_events = Vec::with_capacity(cases.len());
+    for case in cases {
+        let events =
+            get_case_events(db, guild_id, &case.case_code, case.action_case_number).await?;
+        cases_with_events.push((case, events));
+    }
+
+    let mut notes = list_user_notes(db, guild_id, user_id).await?;
+    notes.truncate(MAX_NOTES);
+
+    let user_logs = list_recent_user_logs(
+        db,
+        guild_id,
+        UserLogFilters {
+            author_user_id: Some(user_id),
+            event_type: None,
+            limit: MAX_USER_LOGS,
+        },
+    )
+    .await?;
+
+    Ok(ModerationRecord {
+        cases: cases_with_events,
+        notes,
+        user_logs,
+    })
+}
+
+pub(crate) fn build_summary_messages(
+    record: &ModerationRecord,
+    user_label: &str,
+) -> Vec<ChatMessage> {
+    vec![
+        ChatMessage::system(MODERATION_SUMMARY_PROMPT),
+        ChatMessage::user(format_record(record, user_label, now_unix_secs())),
+    ]
+}
+
+/// Bracketed labels in `summary` (like `[W3]`) that are not in `known`.
+pub fn unknown_case_citations(summary: &str, known: &[String]) -> Vec<String> {
+    let mut unknown: Vec<String> = Vec::new();
+
+    for candidate in summary
+        .split('[')
+        .skip(1)
+        .filter_map(|part| part.split_once(']').map(|(inside, _)| inside.trim()))
+    {
+        let letters = candidate
+            .chars()
+            .take_while(char::is_ascii_alphabetic)
+            .count();
+        let digits = &candidate[letters..];
+        let looks_like_label =
+            letters > 0 && !digits.is_empty() && digits.chars().all(|ch| ch.is_ascii_digit());
+        if !looks_like_label {
+            continue;
+        }
+
+        let label = candidate.to_ascii_uppercase();
+        if !known.contains(&label) && !unknown.contains(&label) {
+            unknown.push(label);
+        }
+    }
+
+    unknown
+}
+
+fn format_record(record: &ModerationRecord, user_label: &str, now: u64) -> String {
+    let mut out = format!("User: {user_label}\n");
+
+    out.push_str("\nCases (newest first):\n");
+    if record.cases.is_empty() {
+        out.push_str("- none\n");
+    }
+    for (case, events) in &record.cases {
+        let _ = write!(
+            out,
+            "- [{}] {} by moderator {}, {}. Reason: {}",
+            case_label(case),
+            case.action,
+            case.moderator_user_id,
+            age(case.created_at, now),
+            excerpt(&case.reason)
+        );
+        if let Some(duration) = case.duration_seconds {
+            let _ = write!(out, " (duration {duration}s)");
+        }
+        out.push('\n');
+
+        for event in events.iter().filter(|event| event.event_type != "created") {
+            let detail = match (&event.old_reason, &event.new_reason, &event.note) {
+                (_, _, Some(note)) => format!("note: {}", excerpt(note)),
+                (Some(old), Some(new), _) => {
+                    format!(
+                        "reason changed from \"{}\" to \"{}\"",
+                        excerpt(old),
+                        excerpt(new)
+                    )
+                }
+                _ => event.event_type.clone(),
+            };
+            let _ = writeln!(
+                out,
+                "  - {} by {}, {}",
+                detail,
+                event.actor_user_id,
+                age(event.created_at, now)
+            );
+        }
+    }
+
+    out.push_str("\nModerator notes (newest first):\n");
+    if record.notes.is_empty() {
+        out.push_str("- none\n");
+    }
+    for note in &record.notes {
+        let _ = writeln!(
+            out,
+            "- by {}, {}: {}",
+            note.author_user_id,
+            age(note.created_at, now),
+            excerpt(&note.content)
+        );
+    }
+
+    out.push_str("\nRecently deleted or edited messages (newest first):\n");
+    if record.user_logs.is_empty() {
+        out.push_str("- none\n");
+    }
+    for entry in &record.user_logs {
+        let before = entry.before_content.as_deref().map(excerpt);
+        let after = entry.after_content.as_deref().map(excerpt);
+        let content = match (before, after) {
+            (Some(before), Some(after)) => format!("\"{before}\" -> \"{after}\""),
+            (Some(content), None) | (None, Some(content)) => format!("\"{content}\""),
+            (None, None) => entry.attachment_summary.clone().unwrap_or_default(),
+        };
+        let _ = writeln!(
+            out,
+            "- {} in channel {}, {}: {}",
+            entry.event_type,
+            entry.channel_id,
+            age(entry.created_at, now),
+            content
+        );
+    }
+
+    out
+}
+
+fn case_label(case: &CaseSummary) -> String {
+    format!(
+        "{}{}",
+        case.case_code.to_ascii_uppercase(),
+        case.action_case_number
+    )
+}
+
+fn age(timestamp: u64, now: u64) -> String {
+    match now.saturating_sub(timestamp) / 86_400 {
+        0 => "today".to_owned(),
+        1 => "1 day ago".to_owned(),
+        days => format!("{days} days ago"),
+    }
+}
+
+fn excerpt(text: &str) -> String {
+    let flattened = text.replace('\n', " ");
+    if flattened.chars().count() <= EXCERPT_MAX_CHARS {
+        return flattened;
+    }
+
+    let mut excerpt: String = flattened.chars().take(EXCERPT_MAX_CHARS - 1).collect();
+    excerpt.push('…');
+    excerpt
+}
+
+fn now_unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_citations_of_unknown_cases() {
+        let known = vec!["W3".to_owned(), "B1".to_owned()];
+        let summary = "Warned for spam [W3], banned [b1], then [K9] and [W7]. See [note] and [K9].";
+
+        assert_eq!(
+            unknown_case_citations(summary, &known),
+            vec!["K9".to_owned(), "W7".to_owned()]
+        );
+    }
+
+    #[test]
+    fn record_lists_cases_with_labels_and_ages() {
+        let case = CaseSummary {
+            case_number: 4,
+            case_code: "w".to_owned(),
+            action_case_number: 3,
+            target_user_id: Some(1),
+            moderator_user_id: 2,
+            action: "warn".to_owned(),
+            reason: "spam".to_owned(),
+            duration_seconds: None,
+            created_at: 0,
+        };
+        let record = ModerationRecord {
+            cases: vec![(case, Vec::new())],
+            ..ModerationRecord::default()
+        };
+
+        let text = format_record(&record, "Mika (ID 1)", 3 * 86_400);
+        assert!(text.contains("- [W3] warn by moderator 2, 3 days ago. Reason: spam"));
+        assert!(text.contains("Moderator notes (newest first):\n- none"));
+        assert_eq!(record.case_labels(), vec!["W3".to_owned()]);
+    }
+}
@@ -0,0 +1,19 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::provider::{ToolCall, ToolSpec};
+
+pub type ToolFuture<'a> = Pin<Box<dyn Future<Output = anyhow::Result<String>> + Send + 'a>>;
+
+/// A set of tools offered to the model for one request.
+///
+/// Registries are built per invocation so they can carry the caller's
+/// identity and refuse calls the caller is not allowed to make.
+pub trait ToolRegistry: Send + Sync {
+    /// Tools to advertise to the model.
+    fn specs(&self) -> Vec<ToolSpec>;
+
+    /// Run one call and return its result as text for the model (usually
+    /// JSON). Errors are reported back to the model rather than the user.
+    fn call<'a>(&'a self, call: &'a ToolCall) -> ToolFuture<'a>;
+}
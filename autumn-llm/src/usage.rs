@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use autumn_database::{
+    Database,
+    impls::llm_usage::{NewLlmUsage, exceeded_llm_quota, record_llm_usage},
+    model::llm_usage::LlmQuotaScope,
+};
+use tracing::error;
+
+use crate::provider::TokenUsage;
+
+const SECS_PER_DAY: u64 = 86_400;
+
+/// Who a request was made for.
+#[derive(Clone, Copy, Debug)]
+pub struct UsageSource<'a> {
+    pub guild_id: u64,
+    pub channel_id: u64,
+    pub user_id: u64,
+    /// What the request was for, e.g. `reply` or `summarize`. Background
+    /// requests (`classify`, `memory`) are made for the bot itself, so they
+    /// count towards the guild's quota but no member's.
+    pub kind: &'a str,
+}
+
+/// A refusal to show the user when they or the guild have used up today's
+/// token quota (quotas reset at UTC midnight).
+pub async fn daily_quota_refusal(
+    db: &Database,
+    source: UsageSource<'_>,
+    now_unix: u64,
+) -> anyhow::Result<Option<String>> {
+    let day_start = now_unix - now_unix % SECS_PER_DAY;
+    let resets_at = day_start + SECS_PER_DAY;
+
+    let refusal = exceeded_llm_quota(db, source.guild_id, source.user_id, day_start)
+        .await?
+        .map(|scope| match scope {
+            LlmQuotaScope::User => format!(
+                "You've used your AI quota for today. It resets <t:{}:R>.",
+                resets_at
+            ),
+            LlmQuotaScope::Guild => format!(
+                "This server has used its AI quota for today. It resets <t:{}:R>.",
+                resets_at
+            ),
+        });
+
+    Ok(refusal)
+}
+
+/// Record one request. `usage` is `None` for a failed request. Errors are
+/// logged rather than returned so accounting never blocks a reply.
+pub async fn record_usage(
+    db: &Database,
+    source: UsageSource<'_>,
+    latency: Duration,
+    usage: Option<TokenUsage>,
+) {
+    let succeeded = usage.is_some();
+    let usage = usage.unwrap_or_default();
+    let result = record_llm_usage(
+        db,
+        NewLlmUsage {
+            guild_id: source.guild_id,
+            channel_id: source.channel_id,
+            user_id: source.user_id,
+            kind: source.kind,
+            prompt_tokens: usage.prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+            latency_ms: u32::try_from(latency.as_millis()).unwrap_or(u32::MAX),
+            succeeded,
+        },
+    )
+    .await;
+
+    if let Err(source) = result {
+        error!(?source, "failed to record llm usage");
+    }
+}
@@ -17,6 +17,7 @@ pub fn action_display_name(action: &str) -> String {
         "purge" => "Purge".to_owned(),
         "terminate" => "Terminate".to_owned(),
         "auto_timeout" => "Auto Timeout".to_owned(),
+        "anti_nuke" => "Anti-Nuke".to_owned(),
         other => {
             let normalized = other.trim();
             if normalized.is_empty() {
@@ -60,6 +61,7 @@ pub fn action_past_tense(action: &str) -> String {
         "purge" => "purged".to_owned(),
         "terminate" => "terminated".to_owned(),
         "auto_timeout" => "automatically timed out".to_owned(),
+        "anti_nuke" => "stopped by anti-nuke".to_owned(),
         other => format!("{}ed", other),
     }
 }
@@ -126,12 +128,33 @@ pub fn format_compact_duration(total_seconds: u64) -> String {
     format!("{}s", seconds)
 }
 
+/// Format a byte count with binary units (e.g. 512 B, 1.5 KB, 8 MB).
+pub fn format_size_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else if value.fract() < 0.05 {
+        format!("{:.0} {}", value, UNITS[unit])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
 /// Map internal case event keys to user-facing labels.
 pub fn event_display_name(event_type: &str) -> &'static str {
     match event_type {
         "created" => "Created",
         "reason_updated" => "Reason Updated",
         "note_added" => "Note Added",
+        "status_updated" => "Status Updated",
         _ => "Updated",
     }
 }
@@ -140,7 +163,7 @@ pub fn event_display_name(event_type: &str) -> &'static str {
 mod tests {
     use super::{
         action_display_name, action_past_tense, event_display_name, format_case_label,
-        format_compact_duration, parse_case_label,
+        format_compact_duration, format_size_bytes, parse_case_label,
     };
 
     #[test]
@@ -193,4 +216,12 @@ mod tests {
         assert_eq!(format_compact_duration(86400), "1d");
         assert_eq!(format_compact_duration(90000), "1d 1h");
     }
+
+    #[test]
+    fn size_formatting_uses_binary_units() {
+        assert_eq!(format_size_bytes(512), "512 B");
+        assert_eq!(format_size_bytes(1536), "1.5 KB");
+        assert_eq!(format_size_bytes(8 * 1024 * 1024), "8 MB");
+        assert_eq!(format_size_bytes(25 * 1024 * 1024 * 1024), "25 GB");
+    }
 }
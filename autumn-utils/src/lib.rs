@@ -6,7 +6,7 @@ pub mod confirmation;
 pub mod embed;
 /// Shared formatting helpers (case labels, action names, parsing).
 pub mod formatting;
-/// Single source of truth for the message-command prefix.
+/// Default message-command prefix, for DMs and guilds without their own.
 pub const COMMAND_PREFIX: char = '!';
 /// Shared pagination helper utilities.
 pub mod pagination;
@@ -14,5 +14,7 @@ pub mod pagination;
 pub mod parse;
 /// Permission helper utilities.
 pub mod permissions;
+/// Markdown-aware splitting for Discord's message length limit.
+pub mod split;
 /// Shared time helpers.
 pub mod time;
@@ -71,3 +71,77 @@ pub fn has_duration_unit(raw: &str) -> bool {
 
     matches!(last, 's' | 'S' | 'm' | 'M' | 'h' | 'H' | 'd' | 'D')
 }
+
+/// Parse a size token like `500kb`, `8MB`, `1.5 mb`, or plain bytes into a byte count.
+pub fn parse_size_bytes(raw: &str) -> Option<u64> {
+    let compact: String = raw
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if compact.is_empty() {
+        return None;
+    }
+
+    let split_idx = compact
+        .find(|ch: char| !ch.is_ascii_digit() && ch != '.')
+        .unwrap_or(compact.len());
+    let (number_part, unit) = compact.split_at(split_idx);
+
+    let number = number_part.parse::<f64>().ok()?;
+    if !number.is_finite() || number <= 0.0 {
+        return None;
+    }
+
+    let multiplier = match unit {
+        "" | "b" => 1_u64,
+        "k" | "kb" => 1024,
+        "m" | "mb" => 1024 * 1024,
+        "g" | "gb" => 1024 * 1024 * 1024,
+        _ => return None,
+    };
+
+    let bytes = (number * multiplier as f64).round();
+    if bytes < 1.0 || bytes > u64::MAX as f64 {
+        return None;
+    }
+
+    Some(bytes as u64)
+}
+
+/// Parse a channel mention (`<#123>`) or raw channel ID into an ID.
+pub fn parse_channel_id(raw: &str) -> Option<u64> {
+    let value = raw.trim();
+    if let Ok(id) = value.parse::<u64>() {
+        return Some(id);
+    }
+
+    if value.starts_with("<#") && value.ends_with('>') {
+        return value
+            .trim_start_matches("<#")
+            .trim_end_matches('>')
+            .parse::<u64>()
+            .ok();
+    }
+
+    None
+}
+
+/// Parse a user mention (`<@123>` / `<@!123>`) or raw user ID into an ID.
+pub fn parse_user_id(raw: &str) -> Option<u64> {
+    let value = raw.trim();
+    if let Ok(id) = value.parse::<u64>() {
+        return Some(id);
+    }
+
+    if value.starts_with("<@") && value.ends_with('>') {
+        return value
+            .trim_start_matches("<@")
+            .trim_start_matches('!')
+            .trim_end_matches('>')
+            .parse::<u64>()
+            .ok();
+    }
+
+    None
+}
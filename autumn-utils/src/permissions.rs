@@ -1,4 +1,85 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
 use poise::serenity_prelude as serenity;
+use serde::{Deserialize, Serialize};
+
+use autumn_database::cache::{
+    CacheService, PERMISSION_CACHE_TTL, discord_channel_overwrites_key, discord_channel_parent_key,
+    discord_guild_roles_key, discord_member_roles_key,
+};
+
+/// The intents the bot connected with; see [`set_gateway_intents`].
+static GATEWAY_INTENTS: OnceLock<serenity::GatewayIntents> = OnceLock::new();
+
+/// Record the intents the bot connects with. Member roles are only read from
+/// the gateway cache when `GUILD_MEMBERS` is among them; without it member
+/// updates never arrive and cached roles go stale.
+pub fn set_gateway_intents(intents: serenity::GatewayIntents) {
+    let _ = GATEWAY_INTENTS.set(intents);
+}
+
+fn member_cache_is_live() -> bool {
+    GATEWAY_INTENTS
+        .get()
+        .is_some_and(|intents| intents.contains(serenity::GatewayIntents::GUILD_MEMBERS))
+}
+
+/// A guild's owner and role permissions, including `@everyone`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct GuildRoles {
+    owner_id: u64,
+    /// Role id to permission bits.
+    roles: HashMap<u64, u64>,
+}
+
+impl GuildRoles {
+    fn new(owner_id: serenity::UserId, roles: &HashMap<serenity::RoleId, serenity::Role>) -> Self {
+        Self {
+            owner_id: owner_id.get(),
+            roles: roles
+                .iter()
+                .map(|(id, role)| (id.get(), role.permissions.bits()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+enum OverwriteTarget {
+    Role(u64),
+    Member(u64),
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+struct Overwrite {
+    target: OverwriteTarget,
+    allow: u64,
+    deny: u64,
+}
+
+impl Overwrite {
+    fn from_channel(channel: &serenity::GuildChannel) -> Vec<Self> {
+        channel
+            .permission_overwrites
+            .iter()
+            .filter_map(|overwrite| {
+                let target = match overwrite.kind {
+                    serenity::PermissionOverwriteType::Role(id) => OverwriteTarget::Role(id.get()),
+                    serenity::PermissionOverwriteType::Member(id) => {
+                        OverwriteTarget::Member(id.get())
+                    }
+                    _ => return None,
+                };
+                Some(Self {
+                    target,
+                    allow: overwrite.allow.bits(),
+                    deny: overwrite.deny.bits(),
+                })
+            })
+            .collect()
+    }
+}
 
 /// Convert a permission bitset into a sorted display list.
 ///
@@ -17,41 +98,381 @@ pub fn permission_names(perms: serenity::Permissions) -> Vec<String> {
     names
 }
 
-/// Resolve the invoking author's effective guild permissions for a message command.
+/// Resolve a member's guild-wide permissions, ignoring channel overwrites.
 ///
-/// Returns `Ok(None)` when the message is not from a guild context.
+/// Reads from the gateway cache when `cache_http` has one and it knows the
+/// guild; anything missing is fetched over HTTP and kept briefly in `cache`.
+/// Member roles come from the gateway cache only if it is kept current; see
+/// [`set_gateway_intents`].
 pub async fn resolve_user_permissions(
-    http: &serenity::Http,
+    cache_http: impl serenity::CacheHttp,
+    cache: &CacheService,
     guild_id: serenity::GuildId,
     user_id: serenity::UserId,
 ) -> anyhow::Result<serenity::Permissions> {
-    let guild = guild_id.to_partial_guild(http).await?;
-    if guild.owner_id == user_id {
-        return Ok(serenity::Permissions::all());
-    }
-
-    let member = guild_id.member(http, user_id).await?;
-    let roles = guild_id.roles(http).await?;
+    resolve_permissions(&cache_http, cache, guild_id, None, user_id).await
+}
 
-    let mut resolved = serenity::Permissions::empty();
-    let everyone_role_id = serenity::RoleId::new(guild_id.get());
+/// Resolve a member's permissions in a channel, applying its overwrites.
+/// Threads use their parent channel's overwrites.
+pub async fn resolve_channel_permissions(
+    cache_http: impl serenity::CacheHttp,
+    cache: &CacheService,
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+    user_id: serenity::UserId,
+) -> anyhow::Result<serenity::Permissions> {
+    resolve_permissions(&cache_http, cache, guild_id, Some(channel_id), user_id).await
+}
 
-    for role in roles.values() {
-        if role.id == everyone_role_id || member.roles.contains(&role.id) {
-            resolved |= role.permissions;
-        }
-    }
+/// What `@everyone` alone may do in a channel, e.g. to tell whether anyone
+/// can read it. Threads use their parent channel's overwrites.
+pub async fn resolve_everyone_permissions(
+    cache_http: impl serenity::CacheHttp,
+    cache: &CacheService,
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+) -> anyhow::Result<serenity::Permissions> {
+    let guild_roles = load_guild_roles(&cache_http, cache, guild_id).await?;
+    let overwrites = load_channel_overwrites(&cache_http, cache, guild_id, channel_id).await?;
 
-    Ok(resolved)
+    // No member holds user id 0, so only `@everyone` and its overwrite apply.
+    Ok(compute_permissions(
+        guild_id.get(),
+        0,
+        &guild_roles,
+        &[],
+        &overwrites,
+    ))
 }
 
 pub async fn has_user_permission(
-    http: &serenity::Http,
+    cache_http: impl serenity::CacheHttp,
+    cache: &CacheService,
+    guild_id: serenity::GuildId,
+    user_id: serenity::UserId,
+    required: serenity::Permissions,
+) -> anyhow::Result<bool> {
+    let perms = resolve_user_permissions(cache_http, cache, guild_id, user_id).await?;
+
+    Ok(perms.contains(serenity::Permissions::ADMINISTRATOR) || perms.contains(required))
+}
+
+pub async fn has_channel_permission(
+    cache_http: impl serenity::CacheHttp,
+    cache: &CacheService,
     guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
     user_id: serenity::UserId,
     required: serenity::Permissions,
 ) -> anyhow::Result<bool> {
-    let perms = resolve_user_permissions(http, guild_id, user_id).await?;
+    let perms =
+        resolve_channel_permissions(cache_http, cache, guild_id, channel_id, user_id).await?;
 
     Ok(perms.contains(serenity::Permissions::ADMINISTRATOR) || perms.contains(required))
 }
+
+/// Whether `role_id` is one of the guild's roles, `@everyone` included.
+pub async fn is_guild_role(
+    cache_http: impl serenity::CacheHttp,
+    cache: &CacheService,
+    guild_id: serenity::GuildId,
+    role_id: u64,
+) -> anyhow::Result<bool> {
+    let guild_roles = load_guild_roles(&cache_http, cache, guild_id).await?;
+
+    Ok(guild_roles.roles.contains_key(&role_id))
+}
+
+/// The channel a per-channel setting applies to: a thread's parent channel,
+/// or the channel itself.
+pub async fn resolve_parent_channel(
+    cache_http: impl serenity::CacheHttp,
+    cache: &CacheService,
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+) -> anyhow::Result<serenity::ChannelId> {
+    let cached = cache_http.cache().and_then(|gateway| {
+        let guild = gateway.guild(guild_id)?;
+        if guild.channels.contains_key(&channel_id) {
+            return Some(channel_id);
+        }
+        let thread = guild
+            .threads
+            .iter()
+            .find(|thread| thread.id == channel_id)?;
+        thread.parent_id
+    });
+    if let Some(parent_id) = cached {
+        return Ok(parent_id);
+    }
+
+    let cache_key = discord_channel_parent_key(cache, guild_id.get(), channel_id.get());
+    let parent_id: u64 = cache
+        .get_or_load_json(&cache_key, PERMISSION_CACHE_TTL, || async {
+            let channel = channel_id
+                .to_channel(cache_http.http())
+                .await?
+                .guild()
+                .ok_or_else(|| anyhow::anyhow!("channel {channel_id} is not a guild channel"))?;
+
+            Ok(match (channel.thread_metadata, channel.parent_id) {
+                (Some(_), Some(parent_id)) => parent_id.get(),
+                _ => channel_id.get(),
+            })
+        })
+        .await?;
+
+    Ok(serenity::ChannelId::new(parent_id))
+}
+
+async fn resolve_permissions(
+    cache_http: &impl serenity::CacheHttp,
+    cache: &CacheService,
+    guild_id: serenity::GuildId,
+    channel_id: Option<serenity::ChannelId>,
+    user_id: serenity::UserId,
+) -> anyhow::Result<serenity::Permissions> {
+    let guild_roles = load_guild_roles(cache_http, cache, guild_id).await?;
+    if guild_roles.owner_id == user_id.get() {
+        return Ok(serenity::Permissions::all());
+    }
+
+    let member_roles = load_member_roles(cache_http, cache, guild_id, user_id).await?;
+    let overwrites = match channel_id {
+        Some(channel_id) => {
+            load_channel_overwrites(cache_http, cache, guild_id, channel_id).await?
+        }
+        None => Vec::new(),
+    };
+
+    Ok(compute_permissions(
+        guild_id.get(),
+        user_id.get(),
+        &guild_roles,
+        &member_roles,
+        &overwrites,
+    ))
+}
+
+async fn load_guild_roles(
+    cache_http: &impl serenity::CacheHttp,
+    cache: &CacheService,
+    guild_id: serenity::GuildId,
+) -> anyhow::Result<GuildRoles> {
+    let cached = cache_http.cache().and_then(|gateway| {
+        gateway
+            .guild(guild_id)
+            .map(|guild| GuildRoles::new(guild.owner_id, &guild.roles))
+    });
+    if let Some(roles) = cached {
+        return Ok(roles);
+    }
+
+    let cache_key = discord_guild_roles_key(cache, guild_id.get());
+    cache
+        .get_or_load_json(&cache_key, PERMISSION_CACHE_TTL, || async {
+            let guild = guild_id.to_partial_guild(cache_http.http()).await?;
+            Ok(GuildRoles::new(guild.owner_id, &guild.roles))
+        })
+        .await
+}
+
+async fn load_member_roles(
+    cache_http: &impl serenity::CacheHttp,
+    cache: &CacheService,
+    guild_id: serenity::GuildId,
+    user_id: serenity::UserId,
+) -> anyhow::Result<Vec<u64>> {
+    let gateway = cache_http.cache().filter(|_| member_cache_is_live());
+    let cached = gateway.and_then(|gateway| {
+        let guild = gateway.guild(guild_id)?;
+        let member = guild.members.get(&user_id)?;
+        Some(member.roles.iter().map(|id| id.get()).collect::<Vec<_>>())
+    });
+    if let Some(roles) = cached {
+        return Ok(roles);
+    }
+
+    let cache_key = discord_member_roles_key(cache, guild_id.get(), user_id.get());
+    cache
+        .get_or_load_json(&cache_key, PERMISSION_CACHE_TTL, || async {
+            let member = guild_id.member(cache_http.http(), user_id).await?;
+            Ok(member.roles.iter().map(|id| id.get()).collect())
+        })
+        .await
+}
+
+async fn load_channel_overwrites(
+    cache_http: &impl serenity::CacheHttp,
+    cache: &CacheService,
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+) -> anyhow::Result<Vec<Overwrite>> {
+    let cached = cache_http.cache().and_then(|gateway| {
+        let guild = gateway.guild(guild_id)?;
+        let channel = guild
+            .channels
+            .get(&channel_id)
+            .or_else(|| guild.threads.iter().find(|thread| thread.id == channel_id))?;
+        let source = match channel.thread_metadata {
+            Some(_) => guild.channels.get(&channel.parent_id?)?,
+            None => channel,
+        };
+        Some(Overwrite::from_channel(source))
+    });
+    if let Some(overwrites) = cached {
+        return Ok(overwrites);
+    }
+
+    let cache_key = discord_channel_overwrites_key(cache, guild_id.get(), channel_id.get());
+    cache
+        .get_or_load_json(&cache_key, PERMISSION_CACHE_TTL, || async {
+            let http = cache_http.http();
+            let mut channel =
+                channel_id.to_channel(http).await?.guild().ok_or_else(|| {
+                    anyhow::anyhow!("channel {channel_id} is not a guild channel")
+                })?;
+
+            if channel.thread_metadata.is_some()
+                && let Some(parent_id) = channel.parent_id
+            {
+                channel = parent_id.to_channel(http).await?.guild().ok_or_else(|| {
+                    anyhow::anyhow!("thread parent {parent_id} is not a guild channel")
+                })?;
+            }
+
+            Ok(Overwrite::from_channel(&channel))
+        })
+        .await
+}
+
+/// Discord's permission algorithm: base role permissions, then the
+/// `@everyone` overwrite, the member's role overwrites combined, and finally
+/// the member's own overwrite.
+fn compute_permissions(
+    guild_id: u64,
+    user_id: u64,
+    guild: &GuildRoles,
+    member_roles: &[u64],
+    overwrites: &[Overwrite],
+) -> serenity::Permissions {
+    if guild.owner_id == user_id {
+        return serenity::Permissions::all();
+    }
+
+    let role_bits = |role_id: &u64| guild.roles.get(role_id).copied().unwrap_or(0);
+    let base = member_roles
+        .iter()
+        .fold(role_bits(&guild_id), |bits, role_id| {
+            bits | role_bits(role_id)
+        });
+    let mut perms = serenity::Permissions::from_bits_truncate(base);
+
+    if perms.contains(serenity::Permissions::ADMINISTRATOR) {
+        return serenity::Permissions::all();
+    }
+
+    let mut apply = |allow: u64, deny: u64| {
+        perms = (perms - serenity::Permissions::from_bits_truncate(deny))
+            | serenity::Permissions::from_bits_truncate(allow);
+    };
+
+    if let Some(everyone) = overwrites
+        .iter()
+        .find(|overwrite| overwrite.target == OverwriteTarget::Role(guild_id))
+    {
+        apply(everyone.allow, everyone.deny);
+    }
+
+    let (role_allow, role_deny) = overwrites
+        .iter()
+        .filter(|overwrite| match overwrite.target {
+            OverwriteTarget::Role(role_id) => {
+                role_id != guild_id && member_roles.contains(&role_id)
+            }
+            OverwriteTarget::Member(_) => false,
+        })
+        .fold((0, 0), |(allow, deny), overwrite| {
+            (allow | overwrite.allow, deny | overwrite.deny)
+        });
+    apply(role_allow, role_deny);
+
+    if let Some(member) = overwrites
+        .iter()
+        .find(|overwrite| overwrite.target == OverwriteTarget::Member(user_id))
+    {
+        apply(member.allow, member.deny);
+    }
+
+    perms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUILD: u64 = 1;
+    const MOD_ROLE: u64 = 10;
+    const USER: u64 = 100;
+
+    fn guild() -> GuildRoles {
+        GuildRoles {
+            owner_id: 999,
+            roles: HashMap::from([
+                (GUILD, serenity::Permissions::SEND_MESSAGES.bits()),
+                (MOD_ROLE, serenity::Permissions::MANAGE_MESSAGES.bits()),
+            ]),
+        }
+    }
+
+    fn overwrite(
+        target: OverwriteTarget,
+        allow: serenity::Permissions,
+        deny: serenity::Permissions,
+    ) -> Overwrite {
+        Overwrite {
+            target,
+            allow: allow.bits(),
+            deny: deny.bits(),
+        }
+    }
+
+    #[test]
+    fn role_overwrite_can_revoke_guild_permission() {
+        let overwrites = [overwrite(
+            OverwriteTarget::Role(MOD_ROLE),
+            serenity::Permissions::empty(),
+            serenity::Permissions::MANAGE_MESSAGES,
+        )];
+
+        let guild_wide = compute_permissions(GUILD, USER, &guild(), &[MOD_ROLE], &[]);
+        let in_channel = compute_permissions(GUILD, USER, &guild(), &[MOD_ROLE], &overwrites);
+
+        assert!(guild_wide.contains(serenity::Permissions::MANAGE_MESSAGES));
+        assert!(!in_channel.contains(serenity::Permissions::MANAGE_MESSAGES));
+        assert!(in_channel.contains(serenity::Permissions::SEND_MESSAGES));
+    }
+
+    #[test]
+    fn member_overwrite_applies_last() {
+        let overwrites = [
+            overwrite(
+                OverwriteTarget::Role(GUILD),
+                serenity::Permissions::empty(),
+                serenity::Permissions::SEND_MESSAGES,
+            ),
+            overwrite(
+                OverwriteTarget::Member(USER),
+                serenity::Permissions::SEND_MESSAGES,
+                serenity::Permissions::empty(),
+            ),
+        ];
+
+        let perms = compute_permissions(GUILD, USER, &guild(), &[], &overwrites);
+        assert!(perms.contains(serenity::Permissions::SEND_MESSAGES));
+
+        let other = compute_permissions(GUILD, USER + 1, &guild(), &[], &overwrites);
+        assert!(!other.contains(serenity::Permissions::SEND_MESSAGES));
+    }
+}
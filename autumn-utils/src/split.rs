@@ -0,0 +1,289 @@
+/// Discord's maximum message length, in characters.
+pub const DISCORD_MESSAGE_LIMIT: usize = 2000;
+
+/// Smallest limit the splitter accepts; leaves room to re-open and close a
+/// code fence around at least a few characters of code.
+const MIN_CHUNK_CHARS: usize = 32;
+
+enum Block {
+    Text(String),
+    Code { opener: String, lines: Vec<String> },
+}
+
+/// Split markdown text into chunks of at most `max_chars` characters.
+///
+/// Prefers paragraph boundaries, then sentence boundaries, then words. Code
+/// blocks are never left open: when one has to be split, each chunk closes the
+/// fence and the next chunk re-opens it with the same language tag.
+pub fn split_markdown(text: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(MIN_CHUNK_CHARS);
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for (block, blank_line_before) in parse_blocks(text) {
+        let pieces = match block {
+            Block::Text(text) => split_prose(&text, max_chars),
+            Block::Code { opener, lines } => split_code(&opener, &lines, max_chars),
+        };
+
+        for (index, piece) in pieces.into_iter().enumerate() {
+            let separator = if current.is_empty() {
+                ""
+            } else if index == 0 && blank_line_before {
+                "\n\n"
+            } else {
+                "\n"
+            };
+
+            if char_len(&current) + char_len(separator) + char_len(&piece) <= max_chars {
+                current.push_str(separator);
+                current.push_str(&piece);
+            } else {
+                push_chunk(&mut chunks, &mut current);
+                current = piece;
+            }
+        }
+    }
+
+    push_chunk(&mut chunks, &mut current);
+    chunks
+}
+
+/// Group lines into paragraphs and fenced code blocks. The flag records
+/// whether a blank line separated the block from the one before it.
+fn parse_blocks(text: &str) -> Vec<(Block, bool)> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut blank_line_before = false;
+    let mut lines = text.lines();
+
+    while let Some(line) = lines.next() {
+        if is_fence(line) {
+            if !paragraph.is_empty() {
+                blocks.push((Block::Text(paragraph.join("\n")), blank_line_before));
+                paragraph.clear();
+                blank_line_before = false;
+            }
+
+            let mut code_lines = Vec::new();
+            for code_line in lines.by_ref() {
+                if is_fence(code_line) {
+                    break;
+                }
+                code_lines.push(code_line.to_owned());
+            }
+
+            blocks.push((
+                Block::Code {
+                    opener: line.trim().to_owned(),
+                    lines: code_lines,
+                },
+                blank_line_before,
+            ));
+            blank_line_before = false;
+        } else if line.trim().is_empty() {
+            if !paragraph.is_empty() {
+                blocks.push((Block::Text(paragraph.join("\n")), blank_line_before));
+                paragraph.clear();
+            }
+            blank_line_before = true;
+        } else {
+            paragraph.push(line);
+        }
+    }
+
+    if !paragraph.is_empty() {
+        blocks.push((Block::Text(paragraph.join("\n")), blank_line_before));
+    }
+
+    blocks
+}
+
+fn is_fence(line: &str) -> bool {
+    line.trim_start().starts_with("```")
+}
+
+/// Split a paragraph at sentence (or line) boundaries, falling back to words
+/// and finally to a hard cut for a single over-long word.
+fn split_prose(text: &str, max_chars: usize) -> Vec<String> {
+    if char_len(text) <= max_chars {
+        return vec![text.to_owned()];
+    }
+
+    let sentences = split_after(text, |previous, ch| {
+        ch == '\n' || (ch.is_whitespace() && matches!(previous, Some('.' | '!' | '?')))
+    });
+
+    pack(&sentences, max_chars, |sentence| {
+        let words = split_after(sentence, |_, ch| ch.is_whitespace());
+        pack(&words, max_chars, |word| hard_split(word, max_chars))
+    })
+}
+
+/// Split a code block by lines, wrapping every chunk in its own fence.
+fn split_code(opener: &str, lines: &[String], max_chars: usize) -> Vec<String> {
+    const CLOSER: &str = "```";
+
+    let wrap = |body: &str| format!("{opener}\n{body}\n{CLOSER}");
+    let body = lines.join("\n");
+    if char_len(&wrap(&body)) <= max_chars {
+        return vec![wrap(&body)];
+    }
+
+    // Opener, closer, and the two newlines around the body.
+    let body_budget = max_chars
+        .saturating_sub(char_len(opener) + CLOSER.len() + 2)
+        .max(1);
+
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    for line in lines {
+        for part in hard_split(line, body_budget) {
+            let separator = if current.is_empty() { "" } else { "\n" };
+            if char_len(&current) + separator.len() + char_len(&part) <= body_budget {
+                current.push_str(separator);
+                current.push_str(&part);
+            } else {
+                pieces.push(wrap(&current));
+                current = part;
+            }
+        }
+    }
+    pieces.push(wrap(&current));
+
+    pieces
+}
+
+/// Cut `text` after every character for which `is_boundary(previous, ch)`
+/// holds. Concatenating the result gives back the input.
+fn split_after(text: &str, is_boundary: impl Fn(Option<char>, char) -> bool) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    let mut previous = None;
+
+    for ch in text.chars() {
+        current.push(ch);
+        if is_boundary(previous, ch) {
+            pieces.push(std::mem::take(&mut current));
+        }
+        previous = Some(ch);
+    }
+
+    if !current.is_empty() {
+        pieces.push(current);
+    }
+
+    pieces
+}
+
+/// Greedily concatenate pieces into chunks of at most `max_chars`, splitting
+/// any single piece that is too long with `oversized`.
+fn pack(
+    pieces: &[String],
+    max_chars: usize,
+    oversized: impl Fn(&str) -> Vec<String>,
+) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for piece in pieces {
+        if char_len(piece) > max_chars {
+            push_chunk(&mut chunks, &mut current);
+            chunks.extend(oversized(piece));
+            continue;
+        }
+
+        if char_len(&current) + char_len(piece) > max_chars {
+            push_chunk(&mut chunks, &mut current);
+        }
+        current.push_str(piece);
+    }
+
+    push_chunk(&mut chunks, &mut current);
+    chunks
+}
+
+fn hard_split(text: &str, max_chars: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.is_empty() {
+        return vec![String::new()];
+    }
+
+    chars
+        .chunks(max_chars)
+        .map(|chunk| chunk.iter().collect())
+        .collect()
+}
+
+fn push_chunk(chunks: &mut Vec<String>, current: &mut String) {
+    let trimmed = current.trim_end();
+    if !trimmed.trim_start().is_empty() {
+        chunks.push(trimmed.to_owned());
+    }
+    current.clear();
+}
+
+fn char_len(text: &str) -> usize {
+    text.chars().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_text_is_returned_unchanged() {
+        let text = "Hello there.\n\nSecond paragraph.";
+        assert_eq!(split_markdown(text, 2000), vec![text.to_owned()]);
+    }
+
+    #[test]
+    fn prefers_paragraph_then_sentence_boundaries() {
+        let text = "First paragraph is here.\n\nSecond one. It has two sentences.";
+        let chunks = split_markdown(text, 40);
+
+        assert_eq!(
+            chunks,
+            vec![
+                "First paragraph is here.".to_owned(),
+                "Second one. It has two sentences.".to_owned(),
+            ]
+        );
+
+        let chunks = split_markdown("One sentence here. Another sentence follows.", 32);
+        assert_eq!(
+            chunks,
+            vec![
+                "One sentence here.".to_owned(),
+                "Another sentence follows.".to_owned()
+            ]
+        );
+    }
+
+    #[test]
+    fn code_blocks_are_reopened_across_chunks() {
+        let code: Vec<String> = (0..20).map(|i| format!("let value_{i} = {i};")).collect();
+        let text = format!("Here you go:\n```rust\n{}\n```", code.join("\n"));
+
+        let chunks = split_markdown(&text, 120);
+        assert!(chunks.len() > 2);
+
+        for chunk in &chunks {
+            assert!(chunk.chars().count() <= 120);
+            assert_eq!(chunk.matches("```").count() % 2, 0, "unbalanced: {chunk}");
+        }
+        for chunk in &chunks[1..] {
+            assert!(chunk.starts_with("```rust\n"));
+        }
+    }
+
+    #[test]
+    fn long_words_are_hard_split() {
+        let text = "a".repeat(100);
+        let chunks = split_markdown(&text, 40);
+
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks.iter().all(|chunk| chunk.chars().count() <= 40));
+        assert_eq!(chunks.concat(), text);
+    }
+}